@@ -1,6 +1,6 @@
 use crate::{ActionUndo, ActionUndoType, BaseTaskAction, TaskAction, impl_taskaction_from_base};
-use bee_core::Printer;
 use bee_core::task::{Task, TaskData, TaskProperties, TaskStatus};
+use bee_core::{BeeError, Printer};
 
 use log::info;
 
@@ -11,9 +11,12 @@ pub struct AddTaskAction {
 
 impl TaskAction for AddTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
         info!("Performing AddTaskAction");
-        let props = TaskProperties::from(&self.base.arguments)?;
+        let mut props = TaskProperties::from(&self.base.arguments)?;
+        if let Some((_, context)) = self.base.contexts.get_active() {
+            context.apply_defaults(&mut props);
+        }
 
         // Clone here to avoid having multiple mutable borrows
         let new_task: Task = self
@@ -35,10 +38,10 @@ impl TaskAction for AddTaskAction {
             ));
         }
 
-        self.base.undos.push(ActionUndo {
-            action_type: ActionUndoType::Add,
-            tasks: vec![new_task.to_owned()],
-        });
+        self.base.undos.push(ActionUndo::new(
+            ActionUndoType::Add,
+            vec![new_task.to_owned()],
+        ));
         Ok(())
     }
 }
@@ -47,6 +50,7 @@ impl AddTaskAction {
     pub fn get_command_description() -> String {
         r#"Add a new task
 <arguments> will define the task's summary, and potentially its properties as well
+The tags and the project of the active context are added to the task.
 "#
         .to_string()
     }
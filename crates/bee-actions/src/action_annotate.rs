@@ -2,8 +2,8 @@ use uuid::Uuid;
 
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
 
-use bee_core::Printer;
 use bee_core::task::{Task, TaskData, TaskProperties};
+use bee_core::{BeeError, Printer};
 
 #[derive(Default)]
 pub struct AnnotateTaskAction {
@@ -12,7 +12,7 @@ pub struct AnnotateTaskAction {
 
 impl TaskAction for AnnotateTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, p: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, p: &dyn Printer) -> Result<(), BeeError> {
         let mut props = TaskProperties::default();
         props.set_annotate(self.base.arguments.join(" ").to_owned());
 
@@ -32,7 +32,7 @@ impl TaskAction for AnnotateTaskAction {
                 .tasks
                 .get_task_map()
                 .get(&uuid)
-                .ok_or("Invalid UUID to annotate".to_owned())?;
+                .ok_or(BeeError::UnknownTask("Invalid UUID to annotate".to_owned()))?;
             undos.push(t.to_owned());
             match t.get_id() {
                 Some(id) => {
@@ -43,10 +43,9 @@ impl TaskAction for AnnotateTaskAction {
                 }
             }
         }
-        self.base.undos.push(ActionUndo {
-            action_type: super::ActionUndoType::Modify,
-            tasks: undos,
-        });
+        self.base
+            .undos
+            .push(ActionUndo::new(super::ActionUndoType::Modify, undos));
         Ok(())
     }
 }
@@ -0,0 +1,98 @@
+use crate::action_history::print_throughput;
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+
+use bee_core::task::TaskData;
+use bee_core::{BeeError, Printer};
+
+#[derive(Default)]
+pub struct BurndownTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl TaskAction for BurndownTaskAction {
+    impl_taskaction_from_base!();
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        print_throughput(
+            &self.base,
+            printer,
+            "Tasks remaining and done at the end of each",
+            &["Remaining", "Done"],
+            |bucket| vec![bucket.remaining, bucket.done],
+        )
+    }
+}
+
+impl BurndownTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Show how many of the tasks matched by <filter> were remaining and done at the end
+of each period.
+<arguments> are the length of the periods, 'daily', 'weekly' (the default) or
+'monthly', and '--format json' to print the numbers as JSON instead of a chart.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_false;
+    use chrono::Local;
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::chart::BarChartRow;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    #[test]
+    fn test_do_action() {
+        let mut tasks = TaskData::default();
+        for (summary, status) in [
+            ("a", TaskStatus::Pending),
+            ("b", TaskStatus::Completed),
+            ("c", TaskStatus::Deleted),
+        ] {
+            tasks
+                .add_task(
+                    &TaskProperties::from(&[summary.to_owned()]).unwrap(),
+                    status,
+                )
+                .unwrap();
+        }
+        let mut action = BurndownTaskAction::default();
+        action.base.set_tasks(tasks);
+        action.base.arguments = vec!["monthly".to_string()];
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+
+        let charts = printer.charts.borrow();
+        assert_eq!(charts.len(), 1);
+        assert_eq!(
+            charts[0].title,
+            "Tasks remaining and done at the end of each month"
+        );
+        assert_eq!(charts[0].series, vec!["Remaining", "Done"]);
+        assert_eq!(
+            charts[0].rows,
+            vec![BarChartRow {
+                label: Local::now().format("%Y-%m").to_string(),
+                values: vec![1, 1],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_do_action_no_tasks() {
+        let mut action = BurndownTaskAction::default();
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+        assert_eq!(
+            printer.messages.borrow().last().unwrap(),
+            "No task to show."
+        );
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_false!(BurndownTaskAction::get_command_description().is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::Local;
+
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+
+use bee_core::calendar::Calendar;
+use bee_core::task::TaskData;
+use bee_core::{BeeError, Printer};
+
+/// The most months a calendar shows, two years is more than a terminal can hold
+const MAX_MONTH_COUNT: usize = 24;
+
+#[derive(Default)]
+pub struct CalendarTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl CalendarTaskAction {
+    /// Read the arguments, '[<number of months>] [agenda]'
+    fn get_arguments(&self) -> Result<(usize, bool), BeeError> {
+        let mut month_count = 1;
+        let mut agenda = false;
+        for argument in &self.base.arguments {
+            if argument == "agenda" {
+                agenda = true;
+                continue;
+            }
+            month_count = match argument.parse::<usize>() {
+                Ok(count) if count > MAX_MONTH_COUNT => {
+                    return Err(BeeError::InvalidCommand(format!(
+                        "Too many months, the calendar shows at most {}.",
+                        MAX_MONTH_COUNT
+                    )));
+                }
+                Ok(count) if count > 0 => count,
+                _ => {
+                    return Err(BeeError::InvalidCommand(format!(
+                        "Invalid argument '{}', expected a number of months or 'agenda'.",
+                        argument
+                    )));
+                }
+            };
+        }
+        Ok((month_count, agenda))
+    }
+}
+
+impl TaskAction for CalendarTaskAction {
+    impl_taskaction_from_base!();
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        let (month_count, agenda) = self.get_arguments()?;
+        let calendar = Calendar::new(
+            self.base.get_tasks().iter(),
+            month_count,
+            agenda,
+            Local::now(),
+        );
+        printer.print_calendar(&calendar)
+    }
+}
+
+impl CalendarTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Show the current month, with the days that have tasks matched by <filter> due.
+<arguments> are the number of months to show, at most 24, and 'agenda' to list the
+tasks due on each day under the months, e.g. 'calendar 3 agenda'.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::{assert_false, assert_true};
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    fn action_with_arguments(arguments: &[&str]) -> CalendarTaskAction {
+        let mut action = CalendarTaskAction::default();
+        action.base.arguments = arguments.iter().map(|a| a.to_string()).collect();
+        action
+    }
+
+    #[test]
+    fn test_get_arguments() {
+        assert_eq!(
+            action_with_arguments(&[]).get_arguments().unwrap(),
+            (1, false)
+        );
+        assert_eq!(
+            action_with_arguments(&["3", "agenda"])
+                .get_arguments()
+                .unwrap(),
+            (3, true)
+        );
+        assert_eq!(
+            action_with_arguments(&["24"]).get_arguments().unwrap(),
+            (24, false)
+        );
+        assert_true!(action_with_arguments(&["0"]).get_arguments().is_err());
+        assert_true!(action_with_arguments(&["25"]).get_arguments().is_err());
+        assert_true!(
+            action_with_arguments(&["4294967297"])
+                .get_arguments()
+                .is_err()
+        );
+        assert_true!(action_with_arguments(&["week"]).get_arguments().is_err());
+    }
+
+    #[test]
+    fn test_do_action() {
+        let mut tasks = TaskData::default();
+        tasks
+            .add_task(
+                &TaskProperties::from(&["due today due:today".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap();
+
+        let mut action = action_with_arguments(&["2"]);
+        action.base.set_tasks(tasks);
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+
+        let calendars = printer.calendars.borrow();
+        assert_eq!(calendars.len(), 1);
+        assert_eq!(calendars[0].months.len(), 2);
+        assert_false!(calendars[0].agenda);
+        assert_eq!(calendars[0].days.len(), 1);
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_false!(CalendarTaskAction::get_command_description().is_empty());
+    }
+}
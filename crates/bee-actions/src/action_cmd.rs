@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
 
-use bee_core::Printer;
+use bee_core::{BeeError, Printer};
 
 use bee_core::task::TaskData;
 
@@ -47,9 +47,11 @@ fn get_tags_as_string(tasks: &TaskData) -> String {
 
 impl TaskAction for CmdTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
         if self.base.arguments.is_empty() {
-            return Err("No argument found for TaskAction Cmd".to_string());
+            return Err(BeeError::InvalidCommand(
+                "No argument found for TaskAction Cmd".to_string(),
+            ));
         }
         let do_get = self.base.arguments.first().unwrap().as_str() == "get";
 
@@ -66,11 +68,15 @@ impl TaskAction for CmdTaskAction {
                     printer.print_raw(get_tags_as_string(&self.base.tasks).as_str());
                 }
                 _ => {
-                    return Err("TaskAction::Cmd: Not a valid field to request".to_string());
+                    return Err(BeeError::InvalidCommand(
+                        "TaskAction::Cmd: Not a valid field to request".to_string(),
+                    ));
                 }
             },
             None => {
-                return Err("No argument found for command 'get'.".to_string());
+                return Err(BeeError::InvalidCommand(
+                    "No argument found for command 'get'.".to_string(),
+                ));
             }
         }
 
@@ -0,0 +1,231 @@
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+
+use bee_core::context::{Context, RESERVED_CONTEXT_NAMES};
+use bee_core::{BeeError, Printer};
+
+use bee_core::task::TaskData;
+
+#[derive(Default)]
+pub struct ContextTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl ContextTaskAction {
+    fn list_contexts(&self, printer: &dyn Printer) {
+        let contexts = self.base.contexts.get_all();
+        if contexts.is_empty() {
+            printer.show_information_message(
+                "There is no context, use 'bee context define <name> <filter>' to create one.",
+            );
+            return;
+        }
+
+        let active = self.base.contexts.get_active().map(|(name, _)| name);
+        for (name, context) in contexts {
+            let marker = if Some(name) == active { "*" } else { " " };
+            printer.print_raw(&format!("{} {}\t{}", marker, name, context.filter));
+        }
+    }
+
+    fn show_active_context(&self, printer: &dyn Printer) {
+        match self.base.contexts.get_active() {
+            Some((name, context)) => printer.show_information_message(&format!(
+                "Context '{}' is active, with the filter '{}'.",
+                name, context.filter
+            )),
+            None => printer.show_information_message("No context is active."),
+        }
+    }
+
+    fn define_context(
+        &mut self,
+        name: &str,
+        filter: &[String],
+        printer: &dyn Printer,
+    ) -> Result<(), BeeError> {
+        if RESERVED_CONTEXT_NAMES.contains(&name) {
+            return Err(BeeError::InvalidCommand(format!(
+                "'{}' can't be used as a context name.",
+                name
+            )));
+        }
+        let context = Context::from_filter(filter)?;
+        self.base
+            .contexts
+            .definitions
+            .insert(name.to_owned(), context);
+        printer.show_information_message(&format!("Context '{}' defined.", name));
+        Ok(())
+    }
+
+    fn delete_context(&mut self, name: &str, printer: &dyn Printer) -> Result<(), BeeError> {
+        if self.base.contexts.definitions.remove(name).is_none() {
+            if self.base.contexts.get(name).is_some() {
+                return Err(BeeError::InvalidCommand(format!(
+                    "Context '{}' is defined in the configuration file and can't be deleted.",
+                    name
+                )));
+            }
+            return Err(BeeError::InvalidCommand(format!(
+                "Context '{}' doesn't exist.",
+                name
+            )));
+        }
+        if self.base.contexts.active.as_deref() == Some(name) {
+            self.base.contexts.active = None;
+        }
+        printer.show_information_message(&format!("Context '{}' deleted.", name));
+        Ok(())
+    }
+
+    fn set_active_context(&mut self, name: &str, printer: &dyn Printer) -> Result<(), BeeError> {
+        let Some(context) = self.base.contexts.get(name) else {
+            return Err(BeeError::InvalidCommand(format!(
+                "Context '{}' doesn't exist, use 'bee context define {} <filter>' to create it.",
+                name, name
+            )));
+        };
+        // Every command would fail with an invalid filter, e.g. one of the configuration
+        context.get_filter()?;
+        self.base.contexts.active = Some(name.to_owned());
+        printer.show_information_message(&format!("Context '{}' set.", name));
+        Ok(())
+    }
+}
+
+impl TaskAction for ContextTaskAction {
+    impl_taskaction_from_base!();
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        let arguments = self.base.arguments.clone();
+        match arguments.as_slice() {
+            [] => {
+                self.list_contexts(printer);
+                Ok(())
+            }
+            [cmd] if cmd == "list" => {
+                self.list_contexts(printer);
+                Ok(())
+            }
+            [cmd] if cmd == "show" => {
+                self.show_active_context(printer);
+                Ok(())
+            }
+            [cmd] if cmd == "none" => {
+                self.base.contexts.active = None;
+                printer.show_information_message("Context unset.");
+                Ok(())
+            }
+            [cmd, name, filter @ ..] if cmd == "define" => {
+                self.define_context(name, filter, printer)
+            }
+            [cmd, name] if cmd == "delete" => self.delete_context(name, printer),
+            [name] => self.set_active_context(name, printer),
+            _ => Err(BeeError::InvalidCommand(
+                "Invalid arguments for 'context', see 'bee help' for its usage.".to_string(),
+            )),
+        }
+    }
+}
+
+impl ContextTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Manage the contexts, named filters applied to the commands listing tasks
+<arguments> can be:
+    - empty or 'list' to list the contexts, the active one marked with a '*'
+    - 'show' to show the active context
+    - 'define <name> <filter>' to create or replace a context. When the filter is
+      only made of tags and a project, the tasks added in the context get them.
+    - 'delete <name>' to delete a context
+    - '<name>' to activate a context, or 'none' to deactivate it
+<filter> will be ignored.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::context::Contexts;
+
+    fn run(
+        contexts: Contexts,
+        arguments: &[&str],
+    ) -> (Result<(), BeeError>, Contexts, Vec<String>) {
+        let mut action = ContextTaskAction::default();
+        action.base.contexts = contexts;
+        action.base.arguments = arguments.iter().map(|a| a.to_string()).collect();
+        let printer = MockPrinter::default();
+        let result = action.do_action(&printer);
+        let messages = printer.messages.borrow().clone();
+        (result, action.base.contexts, messages)
+    }
+
+    #[test]
+    fn test_define_and_set() {
+        let (result, contexts, _) = run(
+            Contexts::default(),
+            &["define", "work", "project:work", "+office"],
+        );
+        result.unwrap();
+        let work = &contexts.definitions["work"];
+        assert_eq!(work.filter, "project:work +office");
+        assert_eq!(work.project, Some("work".to_string()));
+        assert_eq!(contexts.active, None);
+
+        let (result, contexts, messages) = run(contexts, &["work"]);
+        result.unwrap();
+        assert_eq!(contexts.active, Some("work".to_string()));
+        assert_eq!(messages, ["Context 'work' set."]);
+
+        let (_, _, messages) = run(contexts.clone(), &["list"]);
+        assert_eq!(messages, ["* work\tproject:work +office"]);
+
+        let (result, contexts, _) = run(contexts, &["none"]);
+        result.unwrap();
+        assert_eq!(contexts.active, None);
+    }
+
+    #[test]
+    fn test_delete() {
+        let (_, contexts, _) = run(Contexts::default(), &["define", "home", "+home"]);
+        let (_, contexts, _) = run(contexts, &["home"]);
+        let (result, contexts, _) = run(contexts, &["delete", "home"]);
+        result.unwrap();
+        assert_true!(contexts.definitions.is_empty());
+        assert_eq!(contexts.active, None);
+
+        let (result, _, _) = run(contexts, &["delete", "home"]);
+        assert_true!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_invalid_filter() {
+        let mut contexts = Contexts::default();
+        contexts.definitions.insert(
+            "bad".to_string(),
+            Context {
+                filter: "project:".to_string(),
+                ..Context::default()
+            },
+        );
+        let (result, contexts, _) = run(contexts, &["bad"]);
+        assert_true!(result.is_err());
+        assert_eq!(contexts.active, None);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let (result, _, _) = run(Contexts::default(), &["unknown"]);
+        assert_true!(result.is_err());
+        let (result, _, _) = run(Contexts::default(), &["define", "list", "+home"]);
+        assert_true!(result.is_err());
+        let (result, _, _) = run(Contexts::default(), &["define", "home"]);
+        assert_true!(result.is_err());
+        let (result, _, _) = run(Contexts::default(), &["delete"]);
+        assert_true!(result.is_err());
+    }
+}
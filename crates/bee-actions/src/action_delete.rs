@@ -3,7 +3,7 @@ use uuid::Uuid;
 
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
 
-use bee_core::Printer;
+use bee_core::{BeeError, Printer};
 
 use bee_core::task::{Task, TaskData};
 use std::collections::HashMap;
@@ -15,7 +15,7 @@ pub struct DeleteTaskAction {
 
 impl TaskAction for DeleteTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, p: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, p: &dyn Printer) -> Result<(), BeeError> {
         info!("Performing DeleteTaskAction");
         let mut undos: HashMap<Uuid, Task> = HashMap::default();
         if self.base.tasks.get_task_map().is_empty() {
@@ -73,10 +73,10 @@ impl TaskAction for DeleteTaskAction {
                     }
                 }
             }
-            self.base.undos.push(ActionUndo {
-                action_type: super::ActionUndoType::Modify,
-                tasks: undos.into_values().collect(),
-            });
+            self.base.undos.push(ActionUndo::new(
+                super::ActionUndoType::Modify,
+                undos.into_values().collect(),
+            ));
         }
         Ok(())
     }
@@ -96,40 +96,18 @@ mod tests {
     use all_asserts::*;
 
     use super::*;
-    use bee_core::Printer;
-    use bee_core::config::ReportConfig;
-    use bee_core::task::{Task, TaskData, TaskProperties, TaskStatus};
-
-    struct MockPrinter;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskData, TaskProperties, TaskStatus};
 
     fn init() {
         let _ = env_logger::builder().is_test(true).try_init();
     }
 
-    impl Printer for MockPrinter {
-        fn show_help(
-            &self,
-            _help_section_description: &HashMap<String, String>,
-        ) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_task_info(&self, _task: &Task) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_raw(&self, _: &str) {}
-        fn show_information_message(&self, _message: &str) {}
-        fn error(&self, _: &str) {}
-
-        fn print_list_of_tasks(&self, _: Vec<&Task>, _: &ReportConfig) -> Result<(), String> {
-            Err("Not implemented".to_string())
-        }
-    }
-
     #[test]
     fn test_do_action_no_tasks() {
         init();
         let mut action = DeleteTaskAction::default();
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         let result = action.do_action(&printer);
         assert!(result.is_ok());
@@ -140,7 +118,7 @@ mod tests {
         init();
         let mut action = DeleteTaskAction::default();
         assert_true!(action.base.undos.is_empty());
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         // Add a task to the action
         let mut tasks = TaskData::default();
@@ -0,0 +1,109 @@
+use log::info;
+
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+use bee_core::task::TaskData;
+use bee_core::{BeeError, Printer};
+
+#[derive(Default)]
+pub struct DiagnoseTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl TaskAction for DiagnoseTaskAction {
+    impl_taskaction_from_base!();
+
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        info!("Performing DiagnoseTaskAction");
+        let tasks = self.base.get_tasks();
+        let cycles = tasks.find_dependency_cycles();
+
+        if cycles.is_empty() {
+            printer.show_information_message("No problem found.");
+            return Ok(());
+        }
+
+        printer.show_information_message(&format!("Found {} circular dependencies:", cycles.len()));
+        for cycle in &cycles {
+            printer
+                .show_information_message(&format!("  {}", tasks.describe_dependency_path(cycle)));
+        }
+        printer.show_information_message(
+            "Use 'bee <id> modify depends:none' to remove the dependencies of one of these tasks.",
+        );
+        Ok(())
+    }
+}
+
+impl DiagnoseTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Check the stored tasks for problems, such as circular dependencies.
+Both <filters> and <arguments> are treated as filter, all the tasks are checked if
+there is none.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    fn add_task(data: &mut TaskData, summary: &str) {
+        let task = data
+            .add_task(
+                &TaskProperties::from(&[summary.to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap();
+        let (id, uuid) = (task.get_id().unwrap(), *task.get_uuid());
+        data.insert_id_to_uuid(id, uuid);
+    }
+
+    #[test]
+    fn test_do_action_no_problem() {
+        let mut action = DiagnoseTaskAction::default();
+        add_task(&mut action.base.tasks, "first");
+        add_task(&mut action.base.tasks, "second depends:1");
+        let printer = MockPrinter::default();
+
+        action.do_action(&printer).unwrap();
+
+        assert_eq!(printer.messages.borrow().as_slice(), ["No problem found."]);
+    }
+
+    #[test]
+    fn test_do_action_circular_dependency() {
+        // Circular dependencies can't be created with bee anymore, write them
+        // directly like an old or hand-edited data file would
+        let data: TaskData = serde_json::from_str(
+            r#"[
+{"id":1,"uuid":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1","summary":"first","status":"Pending","tags":[],"sub":[],"project":null,
+ "date_created":"2025-03-01T12:00:00+00:00",
+ "links":[{"from":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1","to":"1c0b7c2e-0f7d-4b44-8a11-1a6c2b1e9f02","link_type":"DependsOn"}]},
+{"id":2,"uuid":"1c0b7c2e-0f7d-4b44-8a11-1a6c2b1e9f02","summary":"second","status":"Pending","tags":[],"sub":[],"project":null,
+ "date_created":"2025-03-01T13:00:00+00:00",
+ "links":[{"from":"1c0b7c2e-0f7d-4b44-8a11-1a6c2b1e9f02","to":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1","link_type":"DependsOn"}]}
+]"#,
+        )
+        .unwrap();
+        let mut action = DiagnoseTaskAction::default();
+        action.base.tasks = data;
+        let printer = MockPrinter::default();
+
+        action.do_action(&printer).unwrap();
+
+        let messages = printer.messages.borrow();
+        assert_eq!(messages[0], "Found 1 circular dependencies:");
+        assert_eq!(messages[1], "  2 'second' -> 1 'first' -> 2 'second'");
+        assert_true!(action.base.undos.is_empty());
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_true!(!DiagnoseTaskAction::get_command_description().is_empty());
+    }
+}
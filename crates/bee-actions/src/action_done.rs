@@ -3,7 +3,7 @@ use uuid::Uuid;
 
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
 
-use bee_core::Printer;
+use bee_core::{BeeError, Printer};
 
 use bee_core::task::{Task, TaskData};
 use std::collections::HashMap;
@@ -15,7 +15,7 @@ pub struct DoneTaskAction {
 
 impl TaskAction for DoneTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, p: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, p: &dyn Printer) -> Result<(), BeeError> {
         info!("Performing DoneTaskAction");
         if self.base.tasks.get_task_map().is_empty() {
             p.show_information_message(" No task to complete.");
@@ -23,6 +23,7 @@ impl TaskAction for DoneTaskAction {
         }
 
         let mut undos: HashMap<Uuid, Task> = HashMap::default();
+        let mut next_occurrences: Vec<Task> = Vec::default();
         let uuids_to_complete: Vec<Uuid> = self
             .base
             .tasks
@@ -32,13 +33,15 @@ impl TaskAction for DoneTaskAction {
             .collect();
         for uuid in uuids_to_complete {
             let task_before = self.base.tasks.get_task_map().get(&uuid).unwrap().clone();
-            self.base.tasks.task_done(&uuid);
+            if let Some(next_task) = self.base.tasks.task_done(&uuid)?.cloned() {
+                next_occurrences.push(next_task);
+            }
             let t = self
                 .base
                 .tasks
                 .get_task_map()
                 .get(&uuid)
-                .ok_or("Invalid UUID to modify".to_owned())?;
+                .ok_or(BeeError::UnknownTask("Invalid UUID to modify".to_owned()))?;
             if task_before != *t {
                 undos.insert(t.get_uuid().to_owned(), task_before.to_owned());
             }
@@ -56,6 +59,19 @@ impl TaskAction for DoneTaskAction {
             }
         }
 
+        for next_task in &next_occurrences {
+            let due = next_task
+                .get_date_due()
+                .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            p.show_information_message(&format!(
+                "Created task {} '{}', the next occurrence of the task due {}.",
+                next_task.get_id().unwrap_or_default(),
+                next_task.get_summary(),
+                due
+            ));
+        }
+
         if !undos.is_empty() {
             let mut extra_uuids: Vec<Uuid> = [
                 undos
@@ -80,10 +96,19 @@ impl TaskAction for DoneTaskAction {
                     }
                 }
             }
-            self.base.undos.push(ActionUndo {
-                action_type: super::ActionUndoType::Modify,
-                tasks: undos.into_values().collect(),
-            });
+
+            // Undoing the creation of a task amounts to deleting it. Storing the deleted
+            // state of the next occurrences keeps a single undo entry for this action.
+            // As for 'add', the undone occurrences remain in the store as deleted tasks.
+            for mut next_task in next_occurrences.drain(..) {
+                next_task.delete();
+                undos.insert(next_task.get_uuid().to_owned(), next_task);
+            }
+
+            self.base.undos.push(ActionUndo::new(
+                super::ActionUndoType::Modify,
+                undos.into_values().collect(),
+            ));
         }
         Ok(())
     }
@@ -103,40 +128,18 @@ mod tests {
     use all_asserts::*;
 
     use super::*;
-    use bee_core::Printer;
-    use bee_core::config::ReportConfig;
-    use bee_core::task::{Task, TaskData, TaskProperties, TaskStatus};
-
-    struct MockPrinter;
+    use crate::{action_undo::restore_tasks, mock_printer::MockPrinter};
+    use bee_core::task::{TaskData, TaskProperties, TaskStatus};
 
     fn init() {
         let _ = env_logger::builder().is_test(true).try_init();
     }
 
-    impl Printer for MockPrinter {
-        fn show_help(
-            &self,
-            _help_section_description: &HashMap<String, String>,
-        ) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_task_info(&self, _task: &Task) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_raw(&self, _: &str) {}
-        fn show_information_message(&self, _message: &str) {}
-        fn error(&self, _: &str) {}
-
-        fn print_list_of_tasks(&self, _: Vec<&Task>, _: &ReportConfig) -> Result<(), String> {
-            Err("Not implemented".to_string())
-        }
-    }
-
     #[test]
     fn test_do_action_no_tasks() {
         init();
         let mut action = DoneTaskAction::default();
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         let result = action.do_action(&printer);
         assert!(result.is_ok());
@@ -147,7 +150,7 @@ mod tests {
         init();
         let mut action = DoneTaskAction::default();
         assert_true!(action.base.undos.is_empty());
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         // Add a task to the action
         let mut tasks = TaskData::default();
@@ -187,6 +190,86 @@ mod tests {
         assert_eq!(action.base.undos.first().unwrap().tasks.len(), 2);
     }
 
+    #[test]
+    fn test_do_action_recurring_task() {
+        init();
+        let mut action = DoneTaskAction::default();
+        let printer = MockPrinter::default();
+
+        let mut tasks = TaskData::default();
+        let task1 = tasks
+            .add_task(
+                &TaskProperties::from(&["weekly chore recur:every week".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .clone();
+        action.base.tasks = tasks;
+
+        let result = action.do_action(&printer);
+        assert!(result.is_ok());
+
+        let task_map = action.base.tasks.get_task_map();
+        assert_eq!(task_map.len(), 2);
+        assert_eq!(
+            task_map.get(task1.get_uuid()).unwrap().get_status(),
+            &TaskStatus::Completed
+        );
+        let next_task = task_map
+            .values()
+            .find(|t| t.get_uuid() != task1.get_uuid())
+            .unwrap();
+        assert_eq!(next_task.get_status(), &TaskStatus::Pending);
+        assert_eq!(next_task.get_summary(), "weekly chore");
+        assert_eq!(next_task.get_recurrence(), task1.get_recurrence());
+        assert_true!(next_task.get_date_due().is_some());
+
+        // A single undo entry reverts both the completion and the new occurrence
+        assert_eq!(action.base.undos.len(), 1);
+        let undo_tasks = &action.base.undos.first().unwrap().tasks;
+        assert_eq!(undo_tasks.len(), 2);
+        let undo_next_task = undo_tasks
+            .iter()
+            .find(|t| t.get_uuid() == next_task.get_uuid())
+            .unwrap();
+        assert_eq!(undo_next_task.get_status(), &TaskStatus::Deleted);
+    }
+
+    #[test]
+    fn test_undo_recurring_task() {
+        init();
+        let mut action = DoneTaskAction::default();
+        let printer = MockPrinter::default();
+
+        let mut tasks = TaskData::default();
+        let task1 = tasks
+            .add_task(
+                &TaskProperties::from(&["weekly chore recur:every week".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .clone();
+        action.base.tasks = tasks;
+        action.do_action(&printer).unwrap();
+
+        let undo = action.base.undos.pop().unwrap();
+        let mut tasks = action.base.tasks.clone();
+        restore_tasks(&mut tasks, &undo);
+
+        // The completed task is pending again and its next occurrence is kept, deleted
+        let task_map = tasks.get_task_map();
+        assert_eq!(task_map.len(), 2);
+        assert_eq!(
+            task_map.get(task1.get_uuid()).unwrap().get_status(),
+            &TaskStatus::Pending
+        );
+        let next_task = task_map
+            .values()
+            .find(|t| t.get_uuid() != task1.get_uuid())
+            .unwrap();
+        assert_eq!(next_task.get_status(), &TaskStatus::Deleted);
+    }
+
     #[test]
     fn test_get_command_description() {
         assert_false!(DoneTaskAction::get_command_description().is_empty());
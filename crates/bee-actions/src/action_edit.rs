@@ -7,8 +7,8 @@ use std::{
 };
 
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
-use bee_core::Printer;
 use bee_core::task::{Task, TaskData, TaskProperties};
+use bee_core::{BeeError, Printer};
 
 #[derive(Default)]
 pub struct EditTaskAction {
@@ -18,47 +18,61 @@ pub struct EditTaskAction {
 use tempfile::Builder;
 use uuid::Uuid;
 
-fn create_and_edit_json_file(input_serialised_tasks: &str) -> Result<TaskData, String> {
+fn create_and_edit_json_file(input_serialised_tasks: &str) -> Result<TaskData, BeeError> {
     // Create a temporary file path
     let mut temp_file = Builder::new()
         .suffix(".json")
         .tempfile()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| BeeError::Storage(e.to_string()))?;
 
     // Write some initial JSON content to the file
     temp_file
         .write_all(input_serialised_tasks.as_bytes())
-        .map_err(|e| format!("Failed to write to file: {}", e))?;
+        .map_err(|e| BeeError::Storage(format!("Failed to write to file: {}", e)))?;
 
     // Determine the editor to use
     let editor = env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
 
     // Open the file in the editor
-    let status = Command::new(editor)
+    let status = Command::new(&editor)
         .arg(temp_file.path().to_string_lossy().into_owned())
         .status()
-        .expect("Failed to open editor");
+        .map_err(|e| {
+            BeeError::InvalidCommand(format!("Unable to open the editor '{}': {}", editor, e))
+        })?;
 
     if !status.success() {
-        return Err("Editor exited with an error".to_string());
+        return Err(BeeError::InvalidCommand(
+            "Editor exited with an error".to_string(),
+        ));
     }
 
     let task_data: TaskData = serde_json::from_str(
-        &fs::read_to_string(temp_file).map_err(|e| format!("Failed to read file: {}", e))?,
+        &fs::read_to_string(temp_file)
+            .map_err(|e| BeeError::Storage(format!("Failed to read file: {}", e)))?,
     )
-    .map_err(|e| format!("Could not parse the modified file: {}", e))?;
+    .map_err(|e| BeeError::parse(format!("Could not parse the modified file: {}", e)))?;
 
     Ok(task_data)
 }
 
 /// This will construct a TaskProperties that will only contain the fields
-/// that we are allowing to be modified (summary, annotations, tags, project)
+/// that we are allowing to be modified (summary, description, annotations, tags, project)
 fn get_task_property(old_task: &Task, new_task: &Task) -> TaskProperties {
     let mut props = TaskProperties::default();
     if new_task.get_summary() != old_task.get_summary() {
         props.set_summary(new_task.get_summary());
     }
 
+    // Emptying the description removes it
+    let new_description = new_task
+        .get_description()
+        .to_owned()
+        .filter(|d| !d.trim().is_empty());
+    if &new_description != old_task.get_description() {
+        props.set_description(&new_description);
+    }
+
     if new_task.get_tags() != old_task.get_tags() {
         let to_add: Vec<String> = new_task
             .get_tags()
@@ -89,7 +103,11 @@ fn get_task_property(old_task: &Task, new_task: &Task) -> TaskProperties {
 }
 
 impl EditTaskAction {
-    fn do_action_impl(&mut self, printer: &dyn Printer, new_tasks: TaskData) -> Result<(), String> {
+    fn do_action_impl(
+        &mut self,
+        printer: &dyn Printer,
+        new_tasks: TaskData,
+    ) -> Result<(), BeeError> {
         let uuids_to_modify: Vec<Uuid> = self
             .base
             .tasks
@@ -107,16 +125,20 @@ impl EditTaskAction {
                 let old_task = match self.base.tasks.get_task_map().get(uuid) {
                     Some(task) => task,
                     None => {
-                        return Err("An unexpected error happen when editing a task.".to_string());
+                        return Err(BeeError::InvalidCommand(
+                            "An unexpected error happen when editing a task.".to_string(),
+                        ));
                     }
                 };
                 let new_task = match new_tasks.get_task_map().get(uuid) {
                     Some(task) => task,
                     None => {
-                        return Err("An unexpected error happen when editing a task. \
+                        return Err(BeeError::InvalidCommand(
+                            "An unexpected error happen when editing a task. \
 It is likely that an UUID was modified when editing tasks. \
 Don't do that."
-                            .to_string());
+                                .to_string(),
+                        ));
                     }
                 };
 
@@ -145,10 +167,10 @@ Don't do that."
         }
 
         if !undos.is_empty() {
-            self.base.undos.push(ActionUndo {
-                action_type: super::ActionUndoType::Modify,
-                tasks: undos.into_values().collect(),
-            });
+            self.base.undos.push(ActionUndo::new(
+                super::ActionUndoType::Modify,
+                undos.into_values().collect(),
+            ));
         }
 
         Ok(())
@@ -158,7 +180,7 @@ Don't do that."
 impl TaskAction for EditTaskAction {
     impl_taskaction_from_base!();
 
-    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
         let new_tasks = create_and_edit_json_file(
             &serde_json::to_string_pretty(self.base.get_tasks()).unwrap(),
         )?;
@@ -172,8 +194,10 @@ impl EditTaskAction {
         r#"Edit one or more tasks using an editor.
 Only some fields can be edited (although all show in the JSON). The editable fields are:
 - Summary
+- description
 - annotations
 - tags
+- project
 
 The rest will be ignored.
 "#
@@ -187,37 +211,13 @@ mod tests {
     use log::debug;
 
     use super::*;
-    use bee_core::{
-        Printer,
-        config::ReportConfig,
-        task::{Task, TaskData, TaskProperties, TaskStatus},
-    };
-
-    struct MockPrinter;
-
-    impl Printer for MockPrinter {
-        fn show_help(
-            &self,
-            _help_section_description: &HashMap<String, String>,
-        ) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_task_info(&self, _task: &Task) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_raw(&self, _: &str) {}
-        fn show_information_message(&self, _message: &str) {}
-        fn error(&self, _: &str) {}
-
-        fn print_list_of_tasks(&self, _: Vec<&Task>, _: &ReportConfig) -> Result<(), String> {
-            Err("Not implemented".to_string())
-        }
-    }
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskData, TaskProperties, TaskStatus};
 
     #[test]
     fn test_do_action_no_tasks() {
         let mut action = EditTaskAction::default();
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         let result = action.do_action_impl(&printer, TaskData::default());
         assert!(result.is_ok());
@@ -228,7 +228,7 @@ mod tests {
         // Make sure we add something to the undo and that the task is correctly modified
         let mut action = EditTaskAction::default();
         assert_true!(action.base.undos.is_empty());
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         // Add a task to the action
         let mut old_tasks = TaskData::default();
@@ -297,7 +297,7 @@ mod tests {
         // Make sure that everything is fine if we don't modify anything
         let mut action = EditTaskAction::default();
         assert_true!(action.base.undos.is_empty());
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         // Add a task to the action
         let mut old_tasks = TaskData::default();
@@ -353,6 +353,49 @@ mod tests {
         assert_eq!(action.base.undos.len(), 0);
     }
 
+    #[test]
+    fn test_do_action_description() {
+        let mut action = EditTaskAction::default();
+        let mut old_tasks = TaskData::default();
+        let task1 = old_tasks
+            .add_task(
+                &TaskProperties::from(&["write the report".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .clone();
+        action.base.tasks = old_tasks.clone();
+
+        let mut new_tasks = old_tasks.clone();
+        let mut props = TaskProperties::default();
+        props.set_description(&Some("# Outline\n- figures\n- conclusion".to_owned()));
+        new_tasks.apply(task1.get_uuid(), &props).unwrap();
+
+        action
+            .do_action_impl(&MockPrinter::default(), new_tasks)
+            .unwrap();
+        let task = action.base.tasks.get_owned(task1.get_uuid()).unwrap();
+        assert_eq!(
+            task.get_description(),
+            &Some("# Outline\n- figures\n- conclusion".to_owned())
+        );
+        assert_eq!(
+            task.get_history().last().unwrap().value,
+            "Description updated"
+        );
+
+        // An empty description is removed
+        let mut new_tasks = action.base.tasks.clone();
+        props.set_description(&Some("  ".to_owned()));
+        new_tasks.apply(task1.get_uuid(), &props).unwrap();
+        action
+            .do_action_impl(&MockPrinter::default(), new_tasks)
+            .unwrap();
+        let task = action.base.tasks.get_owned(task1.get_uuid()).unwrap();
+        assert_eq!(task.get_description(), &None);
+        assert_eq!(action.base.undos.len(), 2);
+    }
+
     #[test]
     fn test_get_command_description() {
         assert_false!(EditTaskAction::get_command_description().is_empty());
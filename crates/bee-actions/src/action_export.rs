@@ -1,7 +1,30 @@
+use chrono::Local;
+
+use crate::command_parser::get_format_argument;
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
-use bee_core::Printer;
+use bee_core::{BeeError, Printer};
+
+use bee_core::task::{TaskData, to_ical};
 
-use bee_core::task::TaskData;
+/// The formats the tasks can be exported to
+#[derive(Debug, PartialEq, Eq)]
+enum ExportFormat {
+    Json,
+    /// An iCalendar file, with a to-do per task
+    Ics,
+}
+
+/// Read the format given with '--format <format>' or '--format=<format>'
+fn parse_export_format(arguments: &[String]) -> Result<ExportFormat, BeeError> {
+    match get_format_argument(arguments)? {
+        None | Some("json") => Ok(ExportFormat::Json),
+        Some("ics") => Ok(ExportFormat::Ics),
+        Some(format) => Err(BeeError::InvalidCommand(format!(
+            "Unknown format '{}', expected 'json' or 'ics'.",
+            format
+        ))),
+    }
+}
 
 #[derive(Default)]
 pub struct ExportTaskAction {
@@ -10,10 +33,16 @@ pub struct ExportTaskAction {
 
 impl TaskAction for ExportTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), String> {
-        printer.show_information_message(
-            &serde_json::to_string_pretty(self.base.get_tasks()).unwrap(),
-        );
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        match parse_export_format(&self.base.arguments)? {
+            ExportFormat::Json => printer.show_information_message(
+                &serde_json::to_string_pretty(self.base.get_tasks()).unwrap(),
+            ),
+            ExportFormat::Ics => {
+                let ical = to_ical(self.base.get_tasks().get_task_map().values(), Local::now());
+                printer.print_raw(ical.trim_end());
+            }
+        }
         Ok(())
     }
 }
@@ -22,8 +51,67 @@ impl ExportTaskAction {
     pub fn get_command_description() -> String {
         r#"Print the tasks as JSON format.
 This is useful for scripting access to Bee.
-Both <filters> and <arguments> are treated as filter
+Both <filters> and <arguments> are treated as filter, except '--format ics' which
+prints the tasks as an iCalendar file instead, with a to-do per task. It can be
+subscribed to by calendar applications, and read by 'import'.
 "#
         .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::{assert_false, assert_true};
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    #[test]
+    fn test_parse_export_format() {
+        let parse = |arguments: &[&str]| {
+            let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+            parse_export_format(&arguments)
+        };
+        assert_eq!(parse(&[]).unwrap(), ExportFormat::Json);
+        assert_eq!(parse(&["--format", "ics"]).unwrap(), ExportFormat::Ics);
+        assert_eq!(parse(&["--format=json"]).unwrap(), ExportFormat::Json);
+        assert_true!(parse(&["--format"]).is_err());
+        assert_true!(parse(&["--format", "csv"]).is_err());
+        assert_true!(parse(&["--format=ics", "--format=json"]).is_err());
+    }
+
+    #[test]
+    fn test_do_action_ics() {
+        let mut tasks = TaskData::default();
+        tasks
+            .add_task(
+                &TaskProperties::from(&["write the report +work due:2025-03-10".to_owned()])
+                    .unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap();
+
+        let mut action = ExportTaskAction::default();
+        action.base.set_tasks(tasks);
+        action
+            .base
+            .set_arguments(vec!["--format".to_string(), "ics".to_string()]);
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+
+        let messages = printer.messages.borrow();
+        assert_eq!(messages.len(), 1);
+        let lines: Vec<&str> = messages[0].split("\r\n").collect();
+        assert_eq!(lines.first(), Some(&"BEGIN:VCALENDAR"));
+        assert_eq!(lines.last(), Some(&"END:VCALENDAR"));
+        assert_true!(lines.contains(&"SUMMARY:write the report"));
+        assert_true!(lines.contains(&"DUE;VALUE=DATE:20250310"));
+        assert_true!(lines.contains(&"CATEGORIES:work"));
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_false!(ExportTaskAction::get_command_description().is_empty());
+    }
+}
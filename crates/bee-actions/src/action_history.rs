@@ -0,0 +1,212 @@
+use chrono::Local;
+
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+
+use bee_core::chart::{BarChart, BarChartRow};
+use bee_core::task::{TaskData, Throughput, ThroughputBucket, ThroughputPeriod};
+use bee_core::{BeeError, Printer};
+
+/// The arguments of 'history' and 'burndown', '[daily|weekly|monthly] [--format json]'
+#[derive(Debug, PartialEq)]
+pub(crate) struct ChartArguments {
+    pub(crate) period: ThroughputPeriod,
+    pub(crate) json: bool,
+}
+
+pub(crate) fn parse_chart_arguments(arguments: &[String]) -> Result<ChartArguments, BeeError> {
+    let mut chart_arguments = ChartArguments {
+        period: ThroughputPeriod::Week,
+        json: false,
+    };
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        let format = if argument == "--format" {
+            arguments
+                .next()
+                .map(|format| format.as_str())
+                .ok_or_else(|| {
+                    BeeError::InvalidCommand("A format is expected after '--format'.".to_string())
+                })?
+        } else if let Some(format) = argument.strip_prefix("--format=") {
+            format
+        } else {
+            chart_arguments.period = ThroughputPeriod::from_string(argument)?;
+            continue;
+        };
+        chart_arguments.json = match format {
+            "json" => true,
+            "chart" => false,
+            _ => {
+                return Err(BeeError::InvalidCommand(format!(
+                    "Unknown format '{}', expected 'chart' or 'json'.",
+                    format
+                )));
+            }
+        };
+    }
+    Ok(chart_arguments)
+}
+
+/// The name of the period in the title of a chart
+pub(crate) fn get_period_name(period: ThroughputPeriod) -> &'static str {
+    match period {
+        ThroughputPeriod::Day => "day",
+        ThroughputPeriod::Week => "week",
+        ThroughputPeriod::Month => "month",
+    }
+}
+
+/// Print the throughput of the tasks per period, as a chart of the `series` read from
+/// each bucket by `get_values`, or as JSON
+pub(crate) fn print_throughput(
+    base: &BaseTaskAction,
+    printer: &dyn Printer,
+    title: &str,
+    series: &[&str],
+    get_values: fn(&ThroughputBucket) -> Vec<usize>,
+) -> Result<(), BeeError> {
+    let arguments = parse_chart_arguments(&base.arguments)?;
+    let throughput = Throughput::new(base.get_tasks().iter(), arguments.period, Local::now());
+
+    if arguments.json {
+        printer.print_raw(&serde_json::to_string_pretty(&throughput).unwrap());
+        return Ok(());
+    }
+    if throughput.buckets.is_empty() {
+        printer.show_information_message("No task to show.");
+        return Ok(());
+    }
+
+    printer.print_chart(&BarChart {
+        title: format!("{} {}", title, get_period_name(arguments.period)),
+        series: series.iter().map(|name| name.to_string()).collect(),
+        rows: throughput
+            .buckets
+            .iter()
+            .map(|bucket| BarChartRow {
+                label: bucket.period.to_owned(),
+                values: get_values(bucket),
+            })
+            .collect(),
+    })
+}
+
+#[derive(Default)]
+pub struct HistoryTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl TaskAction for HistoryTaskAction {
+    impl_taskaction_from_base!();
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        print_throughput(
+            &self.base,
+            printer,
+            "Tasks added, completed and deleted per",
+            &["Added", "Completed", "Deleted"],
+            |bucket| vec![bucket.added, bucket.completed, bucket.deleted],
+        )
+    }
+}
+
+impl HistoryTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Show how many of the tasks matched by <filter> were added, completed and deleted
+over time.
+<arguments> are the length of the periods, 'daily', 'weekly' (the default) or
+'monthly', and '--format json' to print the numbers as JSON instead of a chart.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    fn arguments(arguments: &[&str]) -> Vec<String> {
+        arguments.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_chart_arguments() {
+        assert_eq!(
+            parse_chart_arguments(&[]).unwrap(),
+            ChartArguments {
+                period: ThroughputPeriod::Week,
+                json: false
+            }
+        );
+        assert_eq!(
+            parse_chart_arguments(&arguments(&["monthly", "--format", "json"])).unwrap(),
+            ChartArguments {
+                period: ThroughputPeriod::Month,
+                json: true
+            }
+        );
+        assert_eq!(
+            parse_chart_arguments(&arguments(&["--format=json", "daily"])).unwrap(),
+            ChartArguments {
+                period: ThroughputPeriod::Day,
+                json: true
+            }
+        );
+        assert_true!(parse_chart_arguments(&arguments(&["--format"])).is_err());
+        assert_true!(parse_chart_arguments(&arguments(&["--format", "svg"])).is_err());
+        assert_true!(parse_chart_arguments(&arguments(&["hourly"])).is_err());
+    }
+
+    fn task_data() -> TaskData {
+        let mut tasks = TaskData::default();
+        for (summary, status) in [("a", TaskStatus::Pending), ("b", TaskStatus::Completed)] {
+            tasks
+                .add_task(
+                    &TaskProperties::from(&[summary.to_owned()]).unwrap(),
+                    status,
+                )
+                .unwrap();
+        }
+        tasks
+    }
+
+    #[test]
+    fn test_do_action() {
+        let mut action = HistoryTaskAction::default();
+        action.base.set_tasks(task_data());
+        action.base.arguments = arguments(&["daily"]);
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+
+        let charts = printer.charts.borrow();
+        assert_eq!(charts.len(), 1);
+        assert_eq!(
+            charts[0].title,
+            "Tasks added, completed and deleted per day"
+        );
+        assert_eq!(charts[0].rows.len(), 1);
+        assert_eq!(
+            charts[0].rows[0].label,
+            Local::now().format("%Y-%m-%d").to_string()
+        );
+        assert_eq!(charts[0].rows[0].values[0], 2);
+    }
+
+    #[test]
+    fn test_do_action_json() {
+        let mut action = HistoryTaskAction::default();
+        action.base.set_tasks(task_data());
+        action.base.arguments = arguments(&["--format", "json"]);
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+
+        assert_true!(printer.charts.borrow().is_empty());
+        let output: serde_json::Value =
+            serde_json::from_str(&printer.messages.borrow()[0]).unwrap();
+        assert_eq!(output["period"], "week");
+        assert_eq!(output["buckets"][0]["added"], 2);
+    }
+}
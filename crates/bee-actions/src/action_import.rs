@@ -0,0 +1,310 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+};
+
+use log::info;
+use uuid::Uuid;
+
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+use bee_core::task::{
+    ImportStatus, Task, TaskData, TaskImport, parse_ical, parse_taskwarrior_export,
+};
+use bee_core::{BeeError, Printer};
+
+#[derive(Default)]
+pub struct ImportTaskAction {
+    pub base: BaseTaskAction,
+}
+
+/// Read the export from the file given in argument, or from the standard input
+/// if there is none (or if it is '-')
+fn read_export(arguments: &[String]) -> Result<String, BeeError> {
+    match arguments {
+        [] => io::read_to_string(io::stdin())
+            .map_err(|e| BeeError::Storage(format!("Unable to read the standard input: {}", e))),
+        [path] if path == "-" => io::read_to_string(io::stdin())
+            .map_err(|e| BeeError::Storage(format!("Unable to read the standard input: {}", e))),
+        [path] => fs::read_to_string(path)
+            .map_err(|e| BeeError::Storage(format!("Unable to read '{}': {}", path, e))),
+        _ => Err(BeeError::InvalidCommand(
+            "The import command takes a single file as argument.".to_string(),
+        )),
+    }
+}
+
+impl ImportTaskAction {
+    /// Import the tasks of a Taskwarrior export, or the to-dos of an iCalendar file
+    fn import_content(&mut self, content: &str) -> Result<Vec<(Uuid, TaskImport)>, BeeError> {
+        if content.trim_start().starts_with("BEGIN:VCALENDAR") {
+            return parse_ical(content)?
+                .iter()
+                .map(|todo| Ok((todo.get_uuid(), self.base.tasks.import_ical(todo)?)))
+                .collect();
+        }
+
+        let tw_tasks = parse_taskwarrior_export(content)?;
+        let known_uuids: HashSet<Uuid> = tw_tasks
+            .iter()
+            .map(|t| *t.get_uuid())
+            .chain(self.base.tasks.get_task_map().keys().cloned())
+            .collect();
+        tw_tasks
+            .iter()
+            .map(|tw_task| {
+                let result = self.base.tasks.import_taskwarrior(tw_task, &known_uuids)?;
+                Ok((*tw_task.get_uuid(), result))
+            })
+            .collect()
+    }
+
+    fn do_action_impl(&mut self, printer: &dyn Printer, content: &str) -> Result<(), BeeError> {
+        let imports = self.import_content(content)?;
+
+        let mut undos: HashMap<Uuid, Task> = HashMap::default();
+        let mut created_count = 0;
+        let mut updated_count = 0;
+        let mut unchanged_count = 0;
+        for (uuid, result) in imports {
+            for warning in &result.warnings {
+                printer.show_information_message(&format!("Warning: {}", warning));
+            }
+
+            match result.status {
+                ImportStatus::Created => {
+                    // Undoing the creation of a task amounts to deleting it. This keeps a
+                    // single undo entry for the whole import.
+                    let mut task = self.base.tasks.get_owned(&uuid).unwrap();
+                    task.delete();
+                    undos.entry(uuid).or_insert(task);
+                    created_count += 1;
+                }
+                ImportStatus::Updated(previous) => {
+                    // The same task can be imported twice, e.g. two to-dos with the
+                    // same UID. Undoing restores it as it was before the first one.
+                    undos.entry(uuid).or_insert(*previous);
+                    updated_count += 1;
+                }
+                ImportStatus::Unchanged => unchanged_count += 1,
+                ImportStatus::Skipped => {}
+            }
+        }
+
+        printer.show_information_message(&format!(
+            "Imported {} new tasks, updated {} tasks and left {} tasks unchanged.",
+            created_count, updated_count, unchanged_count
+        ));
+
+        if !undos.is_empty() {
+            self.base.undos.push(ActionUndo::new(
+                super::ActionUndoType::Modify,
+                undos.into_values().collect(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl TaskAction for ImportTaskAction {
+    impl_taskaction_from_base!();
+
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        info!("Performing ImportTaskAction");
+        let content = read_export(&self.base.arguments)?;
+        self.do_action_impl(printer, &content)
+    }
+}
+
+impl ImportTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Import tasks exported by Taskwarrior (with 'task export'), or the to-dos of an
+iCalendar (.ics) file, such as a shared team calendar or 'export --format ics'.
+<arguments> is the file to read the tasks from. If it is empty or '-', the tasks are read
+from the standard input.
+Tasks that were already imported are updated. The attributes Bee doesn't support
+(such as user defined attributes) are reported and not imported. Only the summary,
+status, due date, priority, categories (as tags) and description (as annotations) of
+a to-do are imported. The events of a calendar are ignored, and so are the to-dos
+changing a single occurrence of a recurring to-do.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{Task, TaskStatus};
+
+    const EXPORT: &str = r#"[
+{"description":"write the report","entry":"20250301T120000Z","status":"pending",
+ "uuid":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1","estimate":"2h"},
+{"description":"send the report","entry":"20250301T130000Z","status":"pending",
+ "uuid":"1c0b7c2e-0f7d-4b44-8a11-1a6c2b1e9f02","depends":["8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1"]}
+]"#;
+
+    #[test]
+    fn test_do_action() {
+        let mut action = ImportTaskAction::default();
+        let printer = MockPrinter::default();
+
+        action.do_action_impl(&printer, EXPORT).unwrap();
+
+        assert_eq!(action.base.tasks.get_task_map().len(), 2);
+        assert_eq!(
+            printer.messages.borrow().as_slice(),
+            [
+                "Warning: Task 'write the report': the attributes 'estimate' are not supported \
+                and were not imported."
+                    .to_string(),
+                "Imported 2 new tasks, updated 0 tasks and left 0 tasks unchanged.".to_string()
+            ]
+        );
+
+        // Undoing the import deletes the tasks
+        assert_eq!(action.base.undos.len(), 1);
+        assert_true!(
+            action.base.undos[0]
+                .tasks
+                .iter()
+                .all(|t| t.get_status() == &TaskStatus::Deleted)
+        );
+    }
+
+    #[test]
+    fn test_do_action_import_again() {
+        let mut action = ImportTaskAction::default();
+        action
+            .do_action_impl(&MockPrinter::default(), EXPORT)
+            .unwrap();
+        let tasks = action.base.tasks.clone();
+
+        let mut action = ImportTaskAction::default();
+        action.base.tasks = tasks;
+        let printer = MockPrinter::default();
+        action
+            .do_action_impl(
+                &printer,
+                &EXPORT.replace("send the report", "send the report to Bob"),
+            )
+            .unwrap();
+
+        assert_eq!(action.base.tasks.get_task_map().len(), 2);
+        assert_eq!(
+            printer.messages.borrow().last().unwrap(),
+            "Imported 0 new tasks, updated 1 tasks and left 1 tasks unchanged."
+        );
+        assert_eq!(action.base.undos.len(), 1);
+        assert_eq!(action.base.undos[0].tasks.len(), 1);
+        assert_eq!(
+            action.base.undos[0].tasks[0].get_summary(),
+            "send the report"
+        );
+    }
+
+    #[test]
+    fn test_do_action_ical() {
+        let calendar = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+            BEGIN:VTODO\r\nUID:release@example.com\r\nSUMMARY:release\r\n\
+            DUE;TZID=Europe/Paris:20250311T170000\r\nCATEGORIES:work\r\nEND:VTODO\r\n\
+            END:VCALENDAR\r\n";
+        let mut action = ImportTaskAction::default();
+        let printer = MockPrinter::default();
+        action.do_action_impl(&printer, calendar).unwrap();
+
+        let tasks: Vec<&Task> = action.base.tasks.get_task_map().values().collect();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].get_summary(), "release");
+        assert_eq!(tasks[0].get_tags(), &vec!["work".to_string()]);
+        assert_eq!(
+            printer.messages.borrow().as_slice(),
+            [
+                "Warning: Task 'release': the time zone 'Europe/Paris' of DUE is not \
+                supported, the date was read in local time."
+                    .to_string(),
+                "Imported 1 new tasks, updated 0 tasks and left 0 tasks unchanged.".to_string()
+            ]
+        );
+        assert_eq!(action.base.undos.len(), 1);
+
+        // The same calendar imported again updates the same task
+        let printer = MockPrinter::default();
+        action
+            .do_action_impl(&printer, &calendar.replace("release\r", "release v2\r"))
+            .unwrap();
+        assert_eq!(action.base.tasks.get_task_map().len(), 1);
+        assert_eq!(
+            printer.messages.borrow().last().unwrap(),
+            "Imported 0 new tasks, updated 1 tasks and left 0 tasks unchanged."
+        );
+    }
+
+    #[test]
+    fn test_do_action_ical_same_uid() {
+        let todo = |summary: &str, extra: &str| {
+            format!(
+                "BEGIN:VTODO\r\nUID:release@example.com\r\nSUMMARY:{}\r\n{}END:VTODO\r\n",
+                summary, extra
+            )
+        };
+        let calendar =
+            |todos: &[String]| format!("BEGIN:VCALENDAR\r\n{}END:VCALENDAR\r\n", todos.concat());
+        let mut action = ImportTaskAction::default();
+        action
+            .do_action_impl(&MockPrinter::default(), &calendar(&[todo("release", "")]))
+            .unwrap();
+
+        // Both to-dos update the task, and undoing restores it as it was before
+        let printer = MockPrinter::default();
+        action
+            .do_action_impl(
+                &printer,
+                &calendar(&[
+                    todo("release v2", ""),
+                    todo("release v3", ""),
+                    todo("release once", "RECURRENCE-ID:20250318T170000Z\r\n"),
+                ]),
+            )
+            .unwrap();
+        let tasks: Vec<&Task> = action.base.tasks.get_task_map().values().collect();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].get_summary(), "release v3");
+        let undo = action.base.undos.last().unwrap();
+        assert_eq!(undo.tasks.len(), 1);
+        assert_eq!(undo.tasks[0].get_summary(), "release");
+        assert_eq!(
+            printer.messages.borrow().as_slice(),
+            [
+                "Warning: The to-do 'release once' changes the occurrence 20250318T170000Z \
+                of a recurring to-do and was skipped."
+                    .to_string(),
+                "Imported 0 new tasks, updated 2 tasks and left 0 tasks unchanged.".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_do_action_invalid_export() {
+        let mut action = ImportTaskAction::default();
+        assert_true!(
+            action
+                .do_action_impl(&MockPrinter::default(), "not json")
+                .is_err()
+        );
+        assert_true!(action.base.undos.is_empty());
+    }
+
+    #[test]
+    fn test_read_export() {
+        assert_true!(read_export(&["/this/does/not/exist.json".to_string()]).is_err());
+        assert_true!(read_export(&["a.json".to_string(), "b.json".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_true!(!ImportTaskAction::get_command_description().is_empty());
+    }
+}
@@ -1,5 +1,7 @@
+use crate::command_parser::get_format_argument;
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
-use bee_core::Printer;
+use bee_core::config::ReportFormat;
+use bee_core::{BeeError, Printer};
 
 use bee_core::task::TaskData;
 
@@ -10,8 +12,13 @@ pub struct ListTaskAction {
 
 impl TaskAction for ListTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), String> {
-        printer.print_list_of_tasks(self.base.get_tasks().to_vec(), &self.base.report)?;
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        // '--format <format>' replaces the format of the report
+        let mut report = self.base.report.clone();
+        if let Some(format) = get_format_argument(&self.base.arguments)? {
+            report.format = ReportFormat::from_string(format)?;
+        }
+        printer.print_list_of_tasks(self.base.get_tasks().to_vec(), &report)?;
         Ok(())
     }
 }
@@ -19,8 +26,46 @@ impl TaskAction for ListTaskAction {
 impl ListTaskAction {
     pub fn get_command_description() -> String {
         r#"Show a list of tasks matched by <filter>
-<arguments> are treated as filters.
+<arguments> are treated as filters, except '--format <format>' which prints the
+tasks as a markdown table, or as CSV or TSV, instead of the format of the report.
 "#
         .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::{assert_false, assert_true};
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::config::ReportConfig;
+
+    #[test]
+    fn test_do_action_format() {
+        let printer = MockPrinter::default();
+        let mut action = ListTaskAction::default();
+        action.base.set_report(ReportConfig {
+            format: ReportFormat::Markdown,
+            ..Default::default()
+        });
+        action.do_action(&printer).unwrap();
+
+        action
+            .base
+            .set_arguments(vec!["--format".to_string(), "csv".to_string()]);
+        action.do_action(&printer).unwrap();
+        assert_eq!(
+            *printer.formats.borrow(),
+            vec![ReportFormat::Markdown, ReportFormat::Csv]
+        );
+
+        action.base.set_arguments(vec!["--format=pdf".to_string()]);
+        assert_true!(action.do_action(&printer).is_err());
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_false!(ListTaskAction::get_command_description().is_empty());
+    }
+}
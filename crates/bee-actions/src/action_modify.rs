@@ -3,8 +3,8 @@ use uuid::Uuid;
 
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
 
-use bee_core::Printer;
 use bee_core::task::{Task, TaskData, TaskProperties};
+use bee_core::{BeeError, Printer};
 use std::collections::HashMap;
 
 #[derive(Default)]
@@ -14,7 +14,7 @@ pub struct ModifyTaskAction {
 
 impl TaskAction for ModifyTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, p: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, p: &dyn Printer) -> Result<(), BeeError> {
         info!("Performing ModifyTaskAction");
         let props = TaskProperties::from(&self.base.arguments)?;
         let mut undos: HashMap<Uuid, Task> = HashMap::default();
@@ -35,7 +35,7 @@ impl TaskAction for ModifyTaskAction {
                 .tasks
                 .get_task_map()
                 .get(&uuid)
-                .ok_or("Invalid UUID to modify".to_owned())?;
+                .ok_or(BeeError::UnknownTask("Invalid UUID to modify".to_owned()))?;
             if task_before != *t {
                 undos.insert(t.get_uuid().to_owned(), task_before.to_owned());
             }
@@ -78,10 +78,10 @@ impl TaskAction for ModifyTaskAction {
                     }
                 }
             }
-            self.base.undos.push(ActionUndo {
-                action_type: super::ActionUndoType::Modify,
-                tasks: undos.into_values().collect(),
-            });
+            self.base.undos.push(ActionUndo::new(
+                super::ActionUndoType::Modify,
+                undos.into_values().collect(),
+            ));
         }
         Ok(())
     }
@@ -102,37 +102,13 @@ mod tests {
     use all_asserts::*;
 
     use super::*;
-    use bee_core::{
-        Printer,
-        config::ReportConfig,
-        task::{Task, TaskData, TaskProperties, TaskStatus},
-    };
-
-    struct MockPrinter;
-
-    impl Printer for MockPrinter {
-        fn show_help(
-            &self,
-            _help_section_description: &HashMap<String, String>,
-        ) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_task_info(&self, _task: &Task) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_raw(&self, _: &str) {}
-        fn show_information_message(&self, _message: &str) {}
-        fn error(&self, _: &str) {}
-
-        fn print_list_of_tasks(&self, _: Vec<&Task>, _: &ReportConfig) -> Result<(), String> {
-            Err("Not implemented".to_string())
-        }
-    }
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskData, TaskProperties, TaskStatus};
 
     #[test]
     fn test_do_action_no_tasks() {
         let mut action = ModifyTaskAction::default();
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         let result = action.do_action(&printer);
         assert!(result.is_ok());
@@ -142,7 +118,7 @@ mod tests {
     fn test_do_action_with_tasks() {
         let mut action = ModifyTaskAction::default();
         assert_true!(action.base.undos.is_empty());
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
         action.base.arguments = vec!["new description".to_string()];
 
         // Add a task to the action
@@ -0,0 +1,49 @@
+use crate::action_undo::{describe_action, parse_action_count, restore_tasks};
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+
+use bee_core::{BeeError, Printer};
+
+use bee_core::task::TaskData;
+
+#[derive(Default)]
+pub struct RedoTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl TaskAction for RedoTaskAction {
+    impl_taskaction_from_base!();
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        let count = parse_action_count(&self.base.arguments, "redo")?;
+        if self.base.redos.is_empty() {
+            return Err(BeeError::InvalidCommand(
+                "There is nothing to redo.".to_string(),
+            ));
+        }
+        if count > self.base.redos.len() {
+            return Err(BeeError::InvalidCommand(format!(
+                "Only the last {} undone actions can be redone.",
+                self.base.redos.len()
+            )));
+        }
+
+        for _ in 0..count {
+            let current_redo = self.base.redos.pop().unwrap();
+            let undo = restore_tasks(&mut self.base.tasks, &current_redo);
+            printer
+                .show_information_message(&format!("Redid '{}'.", describe_action(&current_redo)));
+            self.base.undos.push(undo);
+        }
+        Ok(())
+    }
+}
+
+impl RedoTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Redo the last operations that were undone
+<arguments> can be a number of operations to redo.
+Running any other operation after an undo makes it impossible to redo it.
+<filter> will be ignored.
+"#
+        .to_string()
+    }
+}
@@ -2,8 +2,8 @@ use uuid::Uuid;
 
 use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
 
-use bee_core::Printer;
 use bee_core::task::{Task, TaskData, TaskProperties};
+use bee_core::{BeeError, Printer};
 
 use std::collections::HashMap;
 
@@ -14,7 +14,7 @@ pub struct StartTaskAction {
 
 impl TaskAction for StartTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, p: &dyn Printer) -> Result<(), String> {
+    fn do_action(&mut self, p: &dyn Printer) -> Result<(), BeeError> {
         let mut props = TaskProperties::default();
         props.set_active_status(true);
         let mut undos: HashMap<Uuid, Task> = HashMap::default();
@@ -30,12 +30,14 @@ impl TaskAction for StartTaskAction {
             let task_before = self.base.tasks.get_task_map().get(&uuid).unwrap().clone();
             let res = self.base.tasks.apply(&uuid, &props);
 
-            // I "know" that I can expect Err here that is not fatal and should be given as
-            // a warning.
-            // TODO: Have a better Error handling with custom types
-            if let Some(err) = res.err() {
-                p.show_information_message(err.as_str());
-                continue;
+            // A task that can't change status doesn't prevent the others from changing
+            match res {
+                Err(err @ BeeError::InvalidTransition(_)) => {
+                    p.warning(&err);
+                    continue;
+                }
+                Err(err) => return Err(err),
+                Ok(()) => {}
             }
 
             let t = self
@@ -43,17 +45,17 @@ impl TaskAction for StartTaskAction {
                 .tasks
                 .get_task_map()
                 .get(&uuid)
-                .ok_or("Invalid UUID to modify".to_owned())?;
+                .ok_or(BeeError::UnknownTask("Invalid UUID to modify".to_owned()))?;
             if task_before != *t {
                 undos.insert(t.get_uuid().to_owned(), task_before.to_owned());
             }
             p.show_information_message(&format!("Started task '{}'.", t.get_summary()));
         }
         if !undos.is_empty() {
-            self.base.undos.push(ActionUndo {
-                action_type: super::ActionUndoType::Modify,
-                tasks: undos.into_values().collect(),
-            });
+            self.base.undos.push(ActionUndo::new(
+                super::ActionUndoType::Modify,
+                undos.into_values().collect(),
+            ));
         }
         Ok(())
     }
@@ -73,37 +75,13 @@ mod tests {
     use all_asserts::{assert_false, assert_true};
 
     use super::*;
-    use bee_core::{
-        Printer,
-        config::ReportConfig,
-        task::{Task, TaskData, TaskProperties, TaskStatus},
-    };
-
-    struct MockPrinter;
-
-    impl Printer for MockPrinter {
-        fn show_help(
-            &self,
-            _help_section_description: &HashMap<String, String>,
-        ) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_task_info(&self, _task: &Task) -> Result<(), String> {
-            Ok(())
-        }
-        fn print_raw(&self, _: &str) {}
-        fn show_information_message(&self, _message: &str) {}
-        fn error(&self, _: &str) {}
-
-        fn print_list_of_tasks(&self, _: Vec<&Task>, _: &ReportConfig) -> Result<(), String> {
-            Err("Not implemented".to_string())
-        }
-    }
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskData, TaskProperties, TaskStatus};
 
     #[test]
     fn test_do_action_no_tasks() {
         let mut action = StartTaskAction::default();
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         let result = action.do_action(&printer);
         assert!(result.is_ok());
@@ -114,7 +92,7 @@ mod tests {
         // Make sure we add something to the undo and that the task is correctly modified
         let mut action = StartTaskAction::default();
         assert_true!(action.base.undos.is_empty());
-        let printer = MockPrinter;
+        let printer = MockPrinter::default();
 
         // Add a task to the action
         let mut tasks = TaskData::default();
@@ -146,6 +124,44 @@ mod tests {
         assert_eq!(action.base.undos.first().unwrap().tasks.len(), 1);
     }
 
+    #[test]
+    fn test_do_action_already_active() {
+        // A task that is already active doesn't prevent the others from starting
+        let mut action = StartTaskAction::default();
+        let printer = MockPrinter::default();
+
+        let mut tasks = TaskData::default();
+        let active = tasks
+            .add_task(
+                &TaskProperties::from(&["this is active".to_owned()]).unwrap(),
+                TaskStatus::Active,
+            )
+            .unwrap()
+            .clone();
+        let pending = tasks
+            .add_task(
+                &TaskProperties::from(&["this is pending".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .clone();
+        action.base.tasks = tasks;
+
+        let result = action.do_action(&printer);
+        assert!(result.is_ok());
+
+        let task_map = action.base.tasks.get_task_map();
+        assert_eq!(
+            task_map.get(active.get_uuid()).unwrap().get_status(),
+            &TaskStatus::Active
+        );
+        assert_eq!(
+            task_map.get(pending.get_uuid()).unwrap().get_status(),
+            &TaskStatus::Active
+        );
+        assert_eq!(action.base.undos.first().unwrap().tasks.len(), 1);
+    }
+
     #[test]
     fn test_get_command_description() {
         assert_false!(StartTaskAction::get_command_description().is_empty());
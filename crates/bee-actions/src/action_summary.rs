@@ -0,0 +1,125 @@
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+
+use bee_core::task::{ProjectSummary, TaskData};
+use bee_core::{BeeError, Printer};
+
+/// The width of the bar showing the completion of a project
+const BAR_WIDTH: usize = 20;
+
+#[derive(Default)]
+pub struct SummaryTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl TaskAction for SummaryTaskAction {
+    impl_taskaction_from_base!();
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        let summary = ProjectSummary::from_tasks(self.base.get_tasks().iter());
+        if summary.is_empty() {
+            printer.show_information_message("No project has remaining tasks.");
+            return Ok(());
+        }
+
+        let names: Vec<String> = summary
+            .iter()
+            .map(|project| {
+                let name = match project.name.rsplit('.').next() {
+                    Some(name) if !name.is_empty() => name,
+                    _ => "(no project)",
+                };
+                format!("{}{}", "  ".repeat(project.depth), name)
+            })
+            .collect();
+        let width = names
+            .iter()
+            .map(|name| name.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max("Project".len());
+
+        printer.print_raw(&format!(
+            "{:<width$}  {:>9}  {:>8}",
+            "Project", "Remaining", "Complete"
+        ));
+        for (project, name) in summary.iter().zip(names) {
+            let completion = project.get_completion();
+            let filled = completion * BAR_WIDTH / 100;
+            printer.print_raw(&format!(
+                "{:<width$}  {:>9}  {:>7}%  {}{}",
+                name,
+                project.remaining,
+                completion,
+                "#".repeat(filled),
+                ".".repeat(BAR_WIDTH - filled)
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl SummaryTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Show the remaining tasks of each project matched by <filter>, and the percentage
+of its tasks that are completed. The tasks of a sub-project count for its parents.
+<arguments> are used as filters.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_false;
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    #[test]
+    fn test_do_action() {
+        let mut tasks = TaskData::default();
+        for (arguments, status) in [
+            ("a project:work", TaskStatus::Pending),
+            ("b project:work.release", TaskStatus::Pending),
+            ("c project:work.release", TaskStatus::Completed),
+            ("d", TaskStatus::Pending),
+        ] {
+            tasks
+                .add_task(
+                    &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+                    status,
+                )
+                .unwrap();
+        }
+
+        let mut action = SummaryTaskAction::default();
+        action.base.set_tasks(tasks);
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+        assert_eq!(
+            *printer.messages.borrow(),
+            vec![
+                "Project       Remaining  Complete",
+                "(no project)          1        0%  ....................",
+                "work                  2       33%  ######..............",
+                "  release             1       50%  ##########..........",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_do_action_no_tasks() {
+        let mut action = SummaryTaskAction::default();
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+        assert_eq!(
+            printer.messages.borrow().last().unwrap(),
+            "No project has remaining tasks."
+        );
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_false!(SummaryTaskAction::get_command_description().is_empty());
+    }
+}
@@ -0,0 +1,478 @@
+use std::collections::{HashMap, HashSet};
+
+use log::info;
+use uuid::Uuid;
+
+use crate::jira::JiraClient;
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+use bee_core::config::get_config;
+use bee_core::task::{ImportStatus, JiraSync, Task, TaskData, TaskStatus};
+use bee_core::{BeeError, Printer};
+
+#[derive(Default)]
+pub struct SyncTaskAction {
+    pub base: BaseTaskAction,
+}
+
+impl SyncTaskAction {
+    fn sync_jira(
+        &mut self,
+        printer: &dyn Printer,
+        client: &JiraClient,
+        jql: &str,
+    ) -> Result<(), BeeError> {
+        let mut issues = client.search(jql)?;
+
+        // The issue of an open task may not match the query anymore, e.g. once it is
+        // done. It is still needed to update the task.
+        let found: HashSet<&str> = issues.iter().map(|i| i.get_key()).collect();
+        let mut missing: Vec<String> = self
+            .base
+            .tasks
+            .get_task_map()
+            .values()
+            .filter(|t| matches!(t.get_status(), TaskStatus::Pending | TaskStatus::Active))
+            .filter_map(|t| t.get_external_id().to_owned())
+            .filter(|key| !found.contains(key.as_str()))
+            .collect();
+        missing.sort();
+        for key in missing {
+            match client.get_issue(&key)? {
+                Some(issue) => issues.push(issue),
+                None => printer.show_information_message(&format!(
+                    "Warning: The Jira issue '{}' doesn't exist anymore, its task was left \
+                    unchanged.",
+                    key
+                )),
+            }
+        }
+
+        let mut undos: HashMap<Uuid, Task> = HashMap::default();
+        let mut created_count = 0;
+        let mut updated_count = 0;
+        let mut pushed_count = 0;
+        let mut unchanged_count = 0;
+        for issue in &issues {
+            let task = self.base.tasks.get_task_by_external_id(issue.get_key());
+            let uuid = task.map(|t| *t.get_uuid());
+            match issue.get_sync(task) {
+                JiraSync::Pull => match self.base.tasks.import_jira_issue(issue) {
+                    ImportStatus::Created => {
+                        // Undoing the creation of a task amounts to deleting it
+                        let mut task = self
+                            .base
+                            .tasks
+                            .get_task_by_external_id(issue.get_key())
+                            .unwrap()
+                            .clone();
+                        task.delete();
+                        undos.insert(*task.get_uuid(), task);
+                        created_count += 1;
+                    }
+                    ImportStatus::Updated(previous) => {
+                        undos.insert(*previous.get_uuid(), *previous);
+                        updated_count += 1;
+                    }
+                    ImportStatus::Unchanged | ImportStatus::Skipped => unchanged_count += 1,
+                },
+                JiraSync::Push(category) => match client.set_status(issue.get_key(), category) {
+                    Ok(status) => {
+                        printer.show_information_message(&format!(
+                            "Changed the status of the Jira issue '{}' to '{}'.",
+                            issue.get_key(),
+                            status
+                        ));
+                        self.base.tasks.set_jira_status_pushed(&uuid.unwrap());
+                        pushed_count += 1;
+                    }
+                    Err(e) => printer.warning(&e),
+                },
+                JiraSync::Unchanged => unchanged_count += 1,
+            }
+        }
+
+        printer.show_information_message(&format!(
+            "Created {} tasks and updated {} tasks from Jira, pushed {} status changes and \
+            left {} tasks unchanged.",
+            created_count, updated_count, pushed_count, unchanged_count
+        ));
+
+        if !undos.is_empty() {
+            self.base.undos.push(ActionUndo::new(
+                super::ActionUndoType::Modify,
+                undos.into_values().collect(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl TaskAction for SyncTaskAction {
+    impl_taskaction_from_base!();
+
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        info!("Performing SyncTaskAction");
+        match self.base.arguments.as_slice() {
+            [service] if service == "jira" => {
+                let config = get_config().jira.as_ref().ok_or_else(|| {
+                    BeeError::Config(
+                        "No Jira server is configured, add a [core.jira] section with its 'url' \
+                        to the configuration."
+                            .to_string(),
+                    )
+                })?;
+                self.sync_jira(printer, &JiraClient::new(config), &config.jql)
+            }
+            _ => Err(BeeError::InvalidCommand(
+                "The sync command takes the service to synchronise with as argument, only \
+                'jira' is supported."
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+impl SyncTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Synchronise the tasks with another service.
+<arguments> is the service, only 'jira' is supported. The issues matching the query
+configured in [core.jira] become tasks: their status, summary, labels (as tags), project
+key (as project) and due date are copied. The local changes of the status of a task
+are pushed back to its issue. When both changed since the last synchronisation, the
+latest change wins.
+Undoing a synchronisation only reverts the changes made to the tasks.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use all_asserts::assert_true;
+    use chrono::{DateTime, Duration, Local};
+    use tiny_http::{Response, Server};
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::{
+        config::JiraConfig,
+        task::{TaskProperties, TaskStatus},
+    };
+
+    /// The issues of the mock server: their status category and when they were updated
+    #[derive(Default)]
+    struct MockJiraState {
+        issues: BTreeMap<String, (String, DateTime<Local>)>,
+        requests: Vec<String>,
+        authorization: Option<String>,
+    }
+
+    /// A Jira server answering one issue per page, whose search only returns the
+    /// issues that are not done
+    struct MockJira {
+        server: Arc<Server>,
+        state: Arc<Mutex<MockJiraState>>,
+        thread: Option<thread::JoinHandle<()>>,
+    }
+
+    fn issue_json(key: &str, category: &str, updated: &DateTime<Local>) -> serde_json::Value {
+        serde_json::json!({
+            "key": key,
+            "fields": {
+                "summary": format!("Summary of {}", key),
+                "status": {"name": format!("Status {}", category), "statusCategory": {"key": category}},
+                "labels": ["jira"],
+                "project": {"key": key.split('-').next().unwrap()},
+                "duedate": null,
+                "updated": updated.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string(),
+            }
+        })
+    }
+
+    fn answer(state: &Mutex<MockJiraState>, method: &str, url: &str, body: &str) -> (u16, String) {
+        let mut state = state.lock().unwrap();
+        state.requests.push(format!("{} {}", method, url));
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let parts: Vec<&str> = path.trim_start_matches("/rest/api/2/").split('/').collect();
+        match (method, parts.as_slice()) {
+            ("GET", ["search"]) => {
+                let start_at: usize = query
+                    .split('&')
+                    .find_map(|p| p.strip_prefix("startAt="))
+                    .unwrap()
+                    .parse()
+                    .unwrap();
+                let open: Vec<serde_json::Value> = state
+                    .issues
+                    .iter()
+                    .filter(|(_, (category, _))| category != "done")
+                    .map(|(key, (category, updated))| issue_json(key, category, updated))
+                    .collect();
+                let page: Vec<&serde_json::Value> = open.iter().skip(start_at).take(1).collect();
+                let body = serde_json::json!({
+                    "startAt": start_at, "maxResults": 1, "total": open.len(), "issues": page
+                });
+                (200, body.to_string())
+            }
+            ("GET", ["issue", key]) => match state.issues.get(*key) {
+                Some((category, updated)) => {
+                    (200, issue_json(key, category, updated).to_string())
+                }
+                None => (
+                    404,
+                    r#"{"errorMessages":["Issue does not exist"]}"#.to_string(),
+                ),
+            },
+            ("GET", ["issue", _, "transitions"]) => (
+                200,
+                r#"{"transitions":[
+                {"id":"11","name":"Reopen","to":{"name":"To Do","statusCategory":{"key":"new"}}},
+                {"id":"21","name":"Start","to":{"name":"In Progress","statusCategory":{"key":"indeterminate"}}},
+                {"id":"31","name":"Close","to":{"name":"Closed","statusCategory":{"key":"done"}}}
+                ]}"#
+                .to_string(),
+            ),
+            ("POST", ["issue", key, "transitions"]) => {
+                let body: serde_json::Value = serde_json::from_str(body).unwrap();
+                let category = match body["transition"]["id"].as_str().unwrap() {
+                    "11" => "new",
+                    "21" => "indeterminate",
+                    _ => "done",
+                };
+                state
+                    .issues
+                    .insert(key.to_string(), (category.to_string(), Local::now()));
+                (204, String::new())
+            }
+            _ => (
+                400,
+                r#"{"errorMessages":["Unexpected request"]}"#.to_string(),
+            ),
+        }
+    }
+
+    impl MockJira {
+        fn start(issues: &[(&str, &str, DateTime<Local>)]) -> MockJira {
+            let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+            let state = Arc::new(Mutex::new(MockJiraState {
+                issues: issues
+                    .iter()
+                    .map(|(key, category, updated)| {
+                        (key.to_string(), (category.to_string(), *updated))
+                    })
+                    .collect(),
+                ..Default::default()
+            }));
+
+            let thread = {
+                let server = server.clone();
+                let state = state.clone();
+                thread::spawn(move || {
+                    for mut request in server.incoming_requests() {
+                        let mut body = String::new();
+                        request.as_reader().read_to_string(&mut body).unwrap();
+                        state.lock().unwrap().authorization = request
+                            .headers()
+                            .iter()
+                            .find(|h| h.field.equiv("Authorization"))
+                            .map(|h| h.value.to_string());
+                        let (status, content) =
+                            answer(&state, request.method().as_str(), request.url(), &body);
+                        request
+                            .respond(Response::from_string(content).with_status_code(status))
+                            .unwrap();
+                    }
+                })
+            };
+            MockJira {
+                server,
+                state,
+                thread: Some(thread),
+            }
+        }
+
+        fn client(&self) -> JiraClient {
+            JiraClient::new(&JiraConfig {
+                url: format!("http://{}/", self.server.server_addr().to_ip().unwrap()),
+                user: Some("me@example.com".to_string()),
+                token: Some("secret".to_string()),
+                jql: String::new(),
+            })
+        }
+
+        fn get_category(&self, key: &str) -> String {
+            self.state.lock().unwrap().issues[key].0.to_owned()
+        }
+    }
+
+    impl Drop for MockJira {
+        fn drop(&mut self) {
+            self.server.unblock();
+            if let Some(thread) = self.thread.take() {
+                thread.join().unwrap();
+            }
+        }
+    }
+
+    fn sync(jira: &MockJira, tasks: TaskData) -> (SyncTaskAction, Vec<String>) {
+        let mut action = SyncTaskAction::default();
+        action.base.tasks = tasks;
+        let printer = MockPrinter::default();
+        action
+            .sync_jira(&printer, &jira.client(), "assignee = currentUser()")
+            .unwrap();
+        let messages = printer.messages.borrow().to_owned();
+        (action, messages)
+    }
+
+    #[test]
+    fn test_sync_jira_pull() {
+        let earlier = Local::now() - Duration::hours(1);
+        let jira = MockJira::start(&[
+            ("API-1", "new", earlier),
+            ("API-2", "indeterminate", earlier),
+            ("WEB-1", "done", earlier),
+        ]);
+
+        let mut tasks = TaskData::default();
+        tasks
+            .add_task(
+                &TaskProperties::from(&["local task".to_string()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap();
+        let (action, messages) = sync(&jira, tasks);
+
+        assert_eq!(
+            messages,
+            vec![
+                "Created 2 tasks and updated 0 tasks from Jira, pushed 0 status changes and \
+                left 0 tasks unchanged."
+                    .to_string()
+            ]
+        );
+        let tasks = action.base.tasks;
+        assert_eq!(tasks.get_task_map().len(), 3);
+        let task = tasks.get_task_by_external_id("API-2").unwrap();
+        assert_eq!(task.get_summary(), "Summary of API-2");
+        assert_eq!(task.get_status(), &TaskStatus::Active);
+        assert_eq!(task.get_tags(), &vec!["jira".to_string()]);
+        assert_eq!(task.get_project().as_ref().unwrap().get_name(), "API");
+
+        // Undoing the synchronisation deletes the tasks created
+        assert_eq!(action.base.undos.len(), 1);
+        assert_eq!(action.base.undos[0].tasks.len(), 2);
+        assert_true!(
+            action.base.undos[0]
+                .tasks
+                .iter()
+                .all(|t| t.get_status() == &TaskStatus::Deleted)
+        );
+
+        // Every page was requested, with the credentials
+        let state = jira.state.lock().unwrap();
+        let requests = &state.requests;
+        assert_eq!(requests.len(), 2);
+        assert_true!(
+            requests[0].starts_with(
+                "GET /rest/api/2/search?jql=assignee%20%3D%20currentUser%28%29&fields="
+            )
+        );
+    }
+
+    #[test]
+    fn test_sync_jira_again() {
+        let earlier = Local::now() - Duration::hours(1);
+        let jira = MockJira::start(&[("API-1", "new", earlier)]);
+        let (action, _) = sync(&jira, TaskData::default());
+
+        let (action, messages) = sync(&jira, action.base.tasks);
+        assert_eq!(
+            messages.last().unwrap(),
+            "Created 0 tasks and updated 0 tasks from Jira, pushed 0 status changes and \
+            left 1 tasks unchanged."
+        );
+        assert_true!(action.base.undos.is_empty());
+    }
+
+    #[test]
+    fn test_sync_jira_push() {
+        let earlier = Local::now() - Duration::hours(1);
+        let jira = MockJira::start(&[("API-1", "new", earlier)]);
+        let (action, _) = sync(&jira, TaskData::default());
+
+        let mut tasks = action.base.tasks;
+        let uuid = *tasks.get_task_by_external_id("API-1").unwrap().get_uuid();
+        tasks.task_done(&uuid).unwrap();
+        let (action, messages) = sync(&jira, tasks);
+
+        assert_eq!(
+            messages,
+            vec![
+                "Changed the status of the Jira issue 'API-1' to 'Closed'.".to_string(),
+                "Created 0 tasks and updated 0 tasks from Jira, pushed 1 status changes and \
+                left 0 tasks unchanged."
+                    .to_string()
+            ]
+        );
+        assert_eq!(jira.get_category("API-1"), "done");
+
+        // The issue is now done, it is not found by the query anymore
+        let (_, messages) = sync(&jira, action.base.tasks);
+        assert_eq!(
+            messages.last().unwrap(),
+            "Created 0 tasks and updated 0 tasks from Jira, pushed 0 status changes and \
+            left 0 tasks unchanged."
+        );
+    }
+
+    #[test]
+    fn test_sync_jira_done_remotely() {
+        let earlier = Local::now() - Duration::hours(1);
+        let jira = MockJira::start(&[("API-1", "new", earlier), ("API-2", "new", earlier)]);
+        let (action, _) = sync(&jira, TaskData::default());
+
+        {
+            let mut state = jira.state.lock().unwrap();
+            state
+                .issues
+                .insert("API-1".to_string(), ("done".to_string(), Local::now()));
+            state.issues.remove("API-2");
+        }
+        let (action, messages) = sync(&jira, action.base.tasks);
+
+        assert_eq!(
+            messages,
+            vec![
+                "Warning: The Jira issue 'API-2' doesn't exist anymore, its task was left \
+                unchanged."
+                    .to_string(),
+                "Created 0 tasks and updated 1 tasks from Jira, pushed 0 status changes and \
+                left 0 tasks unchanged."
+                    .to_string()
+            ]
+        );
+        let task = action.base.tasks.get_task_by_external_id("API-1").unwrap();
+        assert_eq!(task.get_status(), &TaskStatus::Completed);
+        assert_eq!(
+            action.base.undos[0].tasks[0].get_status(),
+            &TaskStatus::Pending
+        );
+    }
+
+    #[test]
+    fn test_sync_unknown_service() {
+        let mut action = SyncTaskAction::default();
+        action.set_arguments(vec!["trello".to_string()]);
+        assert_true!(action.do_action(&MockPrinter::default()).is_err());
+    }
+
+    #[test]
+    fn test_get_command_description() {
+        assert_true!(!SyncTaskAction::get_command_description().is_empty());
+    }
+}
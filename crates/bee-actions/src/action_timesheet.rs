@@ -0,0 +1,206 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveTime};
+
+use crate::{ActionUndo, BaseTaskAction, TaskAction, impl_taskaction_from_base};
+
+use bee_core::parse_date;
+use bee_core::task::{TaskData, Timesheet, format_duration};
+use bee_core::{BeeError, Printer};
+
+#[derive(Default)]
+pub struct TimesheetTaskAction {
+    pub base: BaseTaskAction,
+}
+
+/// The start of the current week, monday at midnight
+fn get_start_of_week(now: DateTime<Local>) -> DateTime<Local> {
+    let monday = now
+        .date_naive()
+        .checked_sub_days(Days::new(now.weekday().num_days_from_monday() as u64))
+        .unwrap_or(now.date_naive());
+    monday
+        .and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or(now)
+}
+
+impl TimesheetTaskAction {
+    /// Read the range from the arguments, '[<from>] [to <to>]'
+    fn get_range(
+        &self,
+        now: DateTime<Local>,
+    ) -> Result<(DateTime<Local>, DateTime<Local>), BeeError> {
+        let arguments = &self.base.arguments;
+        let (from_args, to_args) = match arguments.iter().position(|a| a == "to") {
+            Some(idx) => (&arguments[..idx], Some(&arguments[idx + 1..])),
+            None => (&arguments[..], None),
+        };
+
+        let from = if from_args.is_empty() {
+            get_start_of_week(now)
+        } else {
+            parse_date(&from_args.join(" "))?
+        };
+        let to = match to_args {
+            Some([]) => {
+                return Err(BeeError::InvalidCommand(
+                    "A date is expected after 'to'.".to_string(),
+                ));
+            }
+            Some(args) => parse_date(&args.join(" "))?,
+            None => now,
+        };
+        if to < from {
+            return Err(BeeError::InvalidCommand(
+                "The end of the range is before its start.".to_string(),
+            ));
+        }
+        Ok((from, to))
+    }
+}
+
+impl TaskAction for TimesheetTaskAction {
+    impl_taskaction_from_base!();
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        let now = Local::now();
+        let (from, to) = self.get_range(now)?;
+        let timesheet = Timesheet::new(self.base.get_tasks().iter(), from, to, now);
+
+        printer.print_raw(&format!(
+            "Time tracked from {} to {}",
+            from.format("%Y-%m-%d %H:%M"),
+            to.format("%Y-%m-%d %H:%M")
+        ));
+        if timesheet.tasks.is_empty() {
+            printer.show_information_message("No time was tracked in this period.");
+            return Ok(());
+        }
+
+        printer.print_raw("\nTasks:");
+        for task in &timesheet.tasks {
+            printer.print_raw(&format!(
+                "    {:>8}  {}",
+                format_duration(task.duration),
+                task.summary
+            ));
+        }
+
+        printer.print_raw("\nProjects:");
+        for (project, duration) in &timesheet.projects {
+            let name = if project.is_empty() {
+                "(no project)"
+            } else {
+                project.as_str()
+            };
+            printer.print_raw(&format!("    {:>8}  {}", format_duration(*duration), name));
+        }
+
+        if !timesheet.tags.is_empty() {
+            printer.print_raw("\nTags:");
+            for (tag, duration) in &timesheet.tags {
+                printer.print_raw(&format!("    {:>8}  +{}", format_duration(*duration), tag));
+            }
+        }
+
+        printer.print_raw(&format!("\nTotal:  {}", format_duration(timesheet.total)));
+        Ok(())
+    }
+}
+
+impl TimesheetTaskAction {
+    pub fn get_command_description() -> String {
+        r#"Show the time spent on the tasks matched by <filter>, per task, project and tag
+<arguments> are the period, '[<from>] [to <to>]', e.g. 'monday to friday' or
+'2 weeks ago'. The period starts by default at the beginning of the week and
+ends now.
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    fn action_with_arguments(arguments: &[&str]) -> TimesheetTaskAction {
+        let mut action = TimesheetTaskAction::default();
+        action.base.arguments = arguments.iter().map(|a| a.to_string()).collect();
+        action
+    }
+
+    #[test]
+    fn test_get_start_of_week() {
+        // A thursday
+        let now = Local.with_ymd_and_hms(2025, 3, 13, 15, 30, 0).unwrap();
+        assert_eq!(
+            get_start_of_week(now),
+            Local.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_range() {
+        let now = Local::now();
+        let (from, to) = action_with_arguments(&[]).get_range(now).unwrap();
+        assert_eq!(from, get_start_of_week(now));
+        assert_eq!(to, now);
+
+        let (from, to) = action_with_arguments(&["2", "days", "ago", "to", "yesterday"])
+            .get_range(now)
+            .unwrap();
+        assert_true!(from < to);
+        assert_true!(to < now);
+
+        assert_true!(action_with_arguments(&["to"]).get_range(now).is_err());
+        assert_true!(
+            action_with_arguments(&["yesterday", "to", "2", "days", "ago"])
+                .get_range(now)
+                .is_err()
+        );
+        assert_true!(action_with_arguments(&["whenever"]).get_range(now).is_err());
+    }
+
+    fn active_task_data() -> TaskData {
+        let mut tasks = TaskData::default();
+        let uuid = *tasks
+            .add_task(
+                &TaskProperties::from(&["write report".to_owned(), "project:work".to_owned()])
+                    .unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .get_uuid();
+        let mut start = TaskProperties::default();
+        start.set_active_status(true);
+        tasks.apply(&uuid, &start).unwrap();
+        tasks
+    }
+
+    #[test]
+    fn test_do_action() {
+        let mut action = action_with_arguments(&["1", "day", "ago"]);
+        action.base.set_tasks(active_task_data());
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+        let messages = printer.messages.borrow();
+        assert_true!(messages.contains(&"\nTasks:".to_string()));
+        assert_true!(messages.iter().any(|m| m.ends_with("  write report")));
+        assert_true!(messages.iter().any(|m| m.ends_with("  work")));
+        assert_true!(messages.iter().any(|m| m.starts_with("\nTotal:")));
+
+        // Nothing was tracked before the task was started
+        let mut action = action_with_arguments(&["2", "days", "ago", "to", "1", "day", "ago"]);
+        action.base.set_tasks(active_task_data());
+        let printer = MockPrinter::default();
+        action.do_action(&printer).unwrap();
+        assert_eq!(
+            printer.messages.borrow().last().unwrap(),
+            "No time was tracked in this period."
+        );
+    }
+}
@@ -4,15 +4,26 @@ use strum::{Display, EnumIter, IntoEnumIterator};
 
 use crate::{
     BaseTaskAction, TaskAction, action_add::AddTaskAction, action_annotate::AnnotateTaskAction,
-    action_cmd::CmdTaskAction, action_delete::DeleteTaskAction, action_done::DoneTaskAction,
-    action_edit::EditTaskAction, action_export::ExportTaskAction, action_help::HelpTaskAction,
+    action_burndown::BurndownTaskAction, action_calendar::CalendarTaskAction,
+    action_cmd::CmdTaskAction, action_context::ContextTaskAction, action_delete::DeleteTaskAction,
+    action_diagnose::DiagnoseTaskAction, action_done::DoneTaskAction, action_edit::EditTaskAction,
+    action_export::ExportTaskAction, action_help::HelpTaskAction,
+    action_history::HistoryTaskAction, action_import::ImportTaskAction,
     action_info::InfoTaskAction, action_list::ListTaskAction, action_modify::ModifyTaskAction,
-    action_start::StartTaskAction, action_stop::StopTaskAction, action_undo::UndoTaskAction,
+    action_redo::RedoTaskAction, action_start::StartTaskAction, action_stop::StopTaskAction,
+    action_summary::SummaryTaskAction, action_sync::SyncTaskAction,
+    action_timesheet::TimesheetTaskAction, action_undo::UndoTaskAction,
 };
 
 pub struct ActionTypeData {
     pub parsed_string: Vec<String>,
     pub use_arguments_as_filter: bool,
+    /// Load all the tasks instead of only the ones of the report
+    pub ignore_report_filters: bool,
+    /// Only show the tasks of the active context
+    pub use_context: bool,
+    /// Read the '--format <format>' argument
+    pub use_format: bool,
     pub documentation_string: String,
 }
 
@@ -20,17 +31,27 @@ pub struct ActionTypeData {
 pub enum ActionType {
     Add,
     Annotate,
+    Burndown,
+    Calendar,
     Command,
+    Context,
     Delete,
+    Diagnose,
     Done,
     Edit,
     Export,
     Help,
+    History,
+    Import,
     Info,
     List,
     Modify,
+    Redo,
     Start,
     Stop,
+    Summary,
+    Sync,
+    Timesheet,
     Undo,
 }
 
@@ -42,17 +63,27 @@ impl ActionType {
         match a {
             ActionType::Add => (),
             ActionType::Annotate => (),
+            ActionType::Burndown => (),
+            ActionType::Calendar => (),
             ActionType::Command => (),
+            ActionType::Context => (),
             ActionType::Delete => (),
+            ActionType::Diagnose => (),
             ActionType::Done => (),
             ActionType::Edit => (),
             ActionType::Export => (),
             ActionType::Help => (),
+            ActionType::History => (),
+            ActionType::Import => (),
             ActionType::Info => (),
             ActionType::List => (),
             ActionType::Modify => (),
+            ActionType::Redo => (),
             ActionType::Start => (),
             ActionType::Stop => (),
+            ActionType::Summary => (),
+            ActionType::Sync => (),
+            ActionType::Timesheet => (),
             ActionType::Undo => (),
         }
         let mut map = HashMap::new();
@@ -65,6 +96,9 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["add".to_string()],
                             use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: AddTaskAction::get_command_description(),
                         },
                     );
@@ -75,36 +109,102 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["annotate".to_string()],
                             use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: { AnnotateTaskAction::get_command_description() },
                         },
                     );
                 }
+                ActionType::Burndown => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["burndown".to_string()],
+                            use_arguments_as_filter: false,
+                            // The completed and deleted tasks are part of the chart
+                            ignore_report_filters: true,
+                            use_context: true,
+                            use_format: true,
+                            documentation_string: BurndownTaskAction::get_command_description(),
+                        },
+                    );
+                }
+                ActionType::Calendar => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["calendar".to_string()],
+                            use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: true,
+                            use_format: false,
+                            documentation_string: CalendarTaskAction::get_command_description(),
+                        },
+                    );
+                }
                 ActionType::Command => {
                     map.insert(
                         action_type,
                         ActionTypeData {
                             parsed_string: vec!["_cmd".to_string()],
                             use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: CmdTaskAction::get_command_description(),
                         },
                     );
                 }
+                ActionType::Context => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["context".to_string()],
+                            use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
+                            documentation_string: ContextTaskAction::get_command_description(),
+                        },
+                    );
+                }
                 ActionType::Delete => {
                     map.insert(
                         action_type,
                         ActionTypeData {
                             parsed_string: vec!["delete".to_string()],
                             use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: DeleteTaskAction::get_command_description(),
                         },
                     );
                 }
+                ActionType::Diagnose => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["diagnose".to_string()],
+                            use_arguments_as_filter: true,
+                            // Problems can involve tasks outside of the report
+                            ignore_report_filters: true,
+                            use_context: false,
+                            use_format: false,
+                            documentation_string: DiagnoseTaskAction::get_command_description(),
+                        },
+                    );
+                }
                 ActionType::Done => {
                     map.insert(
                         action_type,
                         ActionTypeData {
                             parsed_string: vec!["done".to_string()],
                             use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: DoneTaskAction::get_command_description(),
                         },
                     );
@@ -115,6 +215,9 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["edit".to_string()],
                             use_arguments_as_filter: true,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: EditTaskAction::get_command_description(),
                         },
                     );
@@ -125,6 +228,9 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["export".to_string()],
                             use_arguments_as_filter: true,
+                            ignore_report_filters: false,
+                            use_context: true,
+                            use_format: true,
                             documentation_string: ExportTaskAction::get_command_description(),
                         },
                     );
@@ -135,16 +241,50 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["help".to_string()],
                             use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: HelpTaskAction::get_command_description(),
                         },
                     );
                 }
+                ActionType::History => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["history".to_string()],
+                            use_arguments_as_filter: false,
+                            // The completed and deleted tasks are part of the chart
+                            ignore_report_filters: true,
+                            use_context: true,
+                            use_format: true,
+                            documentation_string: HistoryTaskAction::get_command_description(),
+                        },
+                    );
+                }
+                ActionType::Import => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["import".to_string()],
+                            use_arguments_as_filter: false,
+                            // Every task is needed to update the tasks already imported
+                            ignore_report_filters: true,
+                            use_context: false,
+                            use_format: false,
+                            documentation_string: ImportTaskAction::get_command_description(),
+                        },
+                    );
+                }
                 ActionType::Info => {
                     map.insert(
                         action_type,
                         ActionTypeData {
                             parsed_string: vec!["info".to_string()],
                             use_arguments_as_filter: true,
+                            ignore_report_filters: false,
+                            use_context: true,
+                            use_format: false,
                             documentation_string: InfoTaskAction::get_command_description(),
                         },
                     );
@@ -155,6 +295,9 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["list".to_string()],
                             use_arguments_as_filter: true,
+                            ignore_report_filters: false,
+                            use_context: true,
+                            use_format: true,
                             documentation_string: ListTaskAction::get_command_description(),
                         },
                     );
@@ -165,16 +308,36 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["modify".to_string(), "mod".to_string()],
                             use_arguments_as_filter: false,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: ModifyTaskAction::get_command_description(),
                         },
                     );
                 }
+                ActionType::Redo => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["redo".to_string()],
+                            use_arguments_as_filter: false,
+                            // The tasks to redo may not be part of the report anymore
+                            ignore_report_filters: true,
+                            use_context: false,
+                            use_format: false,
+                            documentation_string: RedoTaskAction::get_command_description(),
+                        },
+                    );
+                }
                 ActionType::Start => {
                     map.insert(
                         action_type,
                         ActionTypeData {
                             parsed_string: vec!["start".to_string()],
                             use_arguments_as_filter: true,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: StartTaskAction::get_command_description(),
                         },
                     );
@@ -185,16 +348,65 @@ impl ActionType {
                         ActionTypeData {
                             parsed_string: vec!["stop".to_string()],
                             use_arguments_as_filter: true,
+                            ignore_report_filters: false,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: StopTaskAction::get_command_description(),
                         },
                     );
                 }
+                ActionType::Summary => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["summary".to_string()],
+                            use_arguments_as_filter: true,
+                            // The completed tasks count towards the completion
+                            ignore_report_filters: true,
+                            use_context: true,
+                            use_format: false,
+                            documentation_string: SummaryTaskAction::get_command_description(),
+                        },
+                    );
+                }
+                ActionType::Sync => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["sync".to_string()],
+                            use_arguments_as_filter: false,
+                            // Every task is needed to find the ones linked to an issue
+                            ignore_report_filters: true,
+                            use_context: false,
+                            use_format: false,
+                            documentation_string: SyncTaskAction::get_command_description(),
+                        },
+                    );
+                }
+                ActionType::Timesheet => {
+                    map.insert(
+                        action_type,
+                        ActionTypeData {
+                            parsed_string: vec!["timesheet".to_string()],
+                            use_arguments_as_filter: false,
+                            // The time tracked on the completed tasks counts as well
+                            ignore_report_filters: true,
+                            use_context: true,
+                            use_format: false,
+                            documentation_string: TimesheetTaskAction::get_command_description(),
+                        },
+                    );
+                }
                 ActionType::Undo => {
                     map.insert(
                         action_type,
                         ActionTypeData {
                             parsed_string: vec!["undo".to_string()],
                             use_arguments_as_filter: false,
+                            // The tasks to undo may not be part of the report anymore
+                            ignore_report_filters: true,
+                            use_context: false,
+                            use_format: false,
                             documentation_string: UndoTaskAction::get_command_description(),
                         },
                     );
@@ -234,8 +446,12 @@ impl ActionType {
         match ActionType::from(name) {
             ActionType::Add => Box::new(AddTaskAction::default()),
             ActionType::Annotate => Box::new(AnnotateTaskAction::default()),
+            ActionType::Burndown => Box::new(BurndownTaskAction::default()),
+            ActionType::Calendar => Box::new(CalendarTaskAction::default()),
             ActionType::Command => Box::new(CmdTaskAction::default()),
+            ActionType::Context => Box::new(ContextTaskAction::default()),
             ActionType::Delete => Box::new(DeleteTaskAction::default()),
+            ActionType::Diagnose => Box::new(DiagnoseTaskAction::default()),
             ActionType::Done => Box::new(DoneTaskAction::default()),
             ActionType::Edit => Box::new(EditTaskAction::default()),
             ActionType::Export => Box::new(ExportTaskAction::default()),
@@ -243,11 +459,17 @@ impl ActionType {
                 base: BaseTaskAction::default(),
                 command_descriptions: Self::get_command_descriptions(),
             }),
+            ActionType::History => Box::new(HistoryTaskAction::default()),
+            ActionType::Import => Box::new(ImportTaskAction::default()),
             ActionType::Info => Box::new(InfoTaskAction::default()),
             ActionType::List => Box::new(ListTaskAction::default()),
             ActionType::Modify => Box::new(ModifyTaskAction::default()),
+            ActionType::Redo => Box::new(RedoTaskAction::default()),
             ActionType::Start => Box::new(StartTaskAction::default()),
             ActionType::Stop => Box::new(StopTaskAction::default()),
+            ActionType::Summary => Box::new(SummaryTaskAction::default()),
+            ActionType::Sync => Box::new(SyncTaskAction::default()),
+            ActionType::Timesheet => Box::new(TimesheetTaskAction::default()),
             ActionType::Undo => Box::new(UndoTaskAction::default()),
         }
     }
@@ -1,6 +1,6 @@
 use crate::{ActionUndo, ActionUndoType, BaseTaskAction, TaskAction, impl_taskaction_from_base};
 
-use bee_core::Printer;
+use bee_core::{BeeError, Printer};
 
 use bee_core::task::TaskData;
 
@@ -9,23 +9,115 @@ pub struct UndoTaskAction {
     pub base: BaseTaskAction,
 }
 
+/// Read how many actions to undo or redo from the arguments, one if there is none
+pub(crate) fn parse_action_count(arguments: &[String], command: &str) -> Result<usize, BeeError> {
+    match arguments {
+        [] => Ok(1),
+        [count] => match count.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err(BeeError::InvalidCommand(format!(
+                "Invalid argument '{}' for '{}', expected a number of actions.",
+                count, command
+            ))),
+        },
+        _ => Err(BeeError::InvalidCommand(format!(
+            "The '{}' command takes a single argument.",
+            command
+        ))),
+    }
+}
+
+/// Describe a logged action, e.g. "add buy milk"
+pub(crate) fn describe_action(undo: &ActionUndo) -> String {
+    if undo.command.is_empty() {
+        "unknown command".to_string()
+    } else {
+        undo.command.to_owned()
+    }
+}
+
+/// Put back the tasks saved in `undo`. The returned entry holds the tasks as they were
+/// before, so that it can be used to revert this change.
+pub(crate) fn restore_tasks(tasks: &mut TaskData, undo: &ActionUndo) -> ActionUndo {
+    let mut previous_tasks = Vec::new();
+    for t in &undo.tasks {
+        let mut restored = t.to_owned();
+        if undo.action_type == ActionUndoType::Add {
+            restored.delete();
+        }
+
+        // A task that doesn't exist anymore is brought back to the state it was in
+        // when the action was undone
+        previous_tasks.push(
+            tasks
+                .get_owned(t.get_uuid())
+                .unwrap_or_else(|| restored.to_owned()),
+        );
+        tasks.set_task(restored);
+    }
+
+    ActionUndo {
+        action_type: ActionUndoType::Modify,
+        tasks: previous_tasks,
+        date: undo.date,
+        command: undo.command.to_owned(),
+    }
+}
+
+impl UndoTaskAction {
+    fn print_history(&self, printer: &dyn Printer) -> Result<(), BeeError> {
+        if self.base.undos.is_empty() {
+            printer.show_information_message("There is nothing to undo.");
+            return Ok(());
+        }
+
+        // The most recent action comes first, so that 'undo N' reverts the first N actions
+        for (idx, undo) in self.base.undos.iter().rev().enumerate() {
+            let date = match undo.date {
+                Some(date) => date.format("%Y-%m-%d %H:%M:%S").to_string(),
+                None => "unknown date".to_string(),
+            };
+            printer.print_raw(&format!(
+                "{:>3} {} {}",
+                idx + 1,
+                date,
+                describe_action(undo)
+            ));
+            for t in &undo.tasks {
+                printer.print_raw(&format!("      '{}'", t.get_summary()));
+            }
+        }
+        Ok(())
+    }
+}
+
 impl TaskAction for UndoTaskAction {
     impl_taskaction_from_base!();
-    fn do_action(&mut self, _: &dyn Printer) -> Result<(), String> {
-        let undos = &self.base.undos;
-        for current_undo in undos.iter().rev() {
-            for t in &current_undo.tasks {
-                if !self.get_tasks().get_undos().contains_key(t.get_uuid()) {
-                    return Err(format!("Could not find task to undo: {}", t.get_uuid()));
-                }
-
-                match current_undo.action_type {
-                    ActionUndoType::Add => self.base.tasks.task_delete(t.get_uuid()),
-                    ActionUndoType::Modify => self.base.tasks.set_task(t.to_owned()),
-                }
-            }
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError> {
+        if matches!(self.base.arguments.as_slice(), [arg] if arg == "list") {
+            return self.print_history(printer);
+        }
+
+        let count = parse_action_count(&self.base.arguments, "undo")?;
+        if self.base.undos.is_empty() {
+            return Err(BeeError::InvalidCommand(
+                "There is nothing to undo.".to_string(),
+            ));
+        }
+        if count > self.base.undos.len() {
+            return Err(BeeError::InvalidCommand(format!(
+                "Only the last {} actions can be undone.",
+                self.base.undos.len()
+            )));
+        }
+
+        for _ in 0..count {
+            let current_undo = self.base.undos.pop().unwrap();
+            let redo = restore_tasks(&mut self.base.tasks, &current_undo);
+            printer
+                .show_information_message(&format!("Undid '{}'.", describe_action(&current_undo)));
+            self.base.redos.push(redo);
         }
-        self.base.undos.clear();
         Ok(())
     }
 }
@@ -33,8 +125,146 @@ impl TaskAction for UndoTaskAction {
 impl UndoTaskAction {
     pub fn get_command_description() -> String {
         r#"Undo the last operation
-Both <filter> and <arguments> will be ignored.
+<arguments> can be a number of operations to undo, or 'list' to show the operations
+that can be undone, the most recent first.
+<filter> will be ignored.
+Undoing the creation of a task, by 'add' or by completing a recurring task, leaves
+it as a deleted task.
 "#
         .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::{assert_false, assert_true};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::action_redo::RedoTaskAction;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::{TaskProperties, TaskStatus};
+
+    /// Add a task and modify its summary, logging both actions
+    fn init_tasks() -> (TaskData, Vec<ActionUndo>, Uuid) {
+        let mut tasks = TaskData::default();
+        let task = tasks
+            .add_task(
+                &TaskProperties::from(&["first".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .clone();
+        let uuid = *task.get_uuid();
+        let mut add_undo = ActionUndo::new(ActionUndoType::Add, vec![task.clone()]);
+        add_undo.command = "add first".to_owned();
+
+        tasks
+            .apply(
+                &uuid,
+                &TaskProperties::from(&["second".to_owned()]).unwrap(),
+            )
+            .unwrap();
+        let mut modify_undo = ActionUndo::new(ActionUndoType::Modify, vec![task]);
+        modify_undo.command = "1 modify second".to_owned();
+
+        (tasks, vec![add_undo, modify_undo], uuid)
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let (tasks, undos, uuid) = init_tasks();
+        let mut action = UndoTaskAction::default();
+        action.base.tasks = tasks;
+        action.base.undos = undos;
+        let printer = MockPrinter::default();
+
+        action.do_action(&printer).unwrap();
+        let task = action.base.tasks.get_owned(&uuid).unwrap();
+        assert_eq!(task.get_summary(), "first");
+        assert_eq!(task.get_status(), &TaskStatus::Pending);
+        assert_eq!(action.base.undos.len(), 1);
+        assert_eq!(action.base.redos.len(), 1);
+        assert_eq!(
+            printer.messages.borrow().as_slice(),
+            ["Undid '1 modify second'."]
+        );
+
+        // Undoing the creation of the task deletes it
+        action.do_action(&MockPrinter::default()).unwrap();
+        let task = action.base.tasks.get_owned(&uuid).unwrap();
+        assert_eq!(task.get_status(), &TaskStatus::Deleted);
+        assert_true!(action.base.undos.is_empty());
+        assert_true!(action.do_action(&MockPrinter::default()).is_err());
+
+        // Redo both actions at once
+        let mut redo = RedoTaskAction::default();
+        redo.base.tasks = action.base.tasks.clone();
+        redo.base.redos = action.base.redos.clone();
+        redo.base.arguments = vec!["2".to_owned()];
+        redo.do_action(&MockPrinter::default()).unwrap();
+        let task = redo.base.tasks.get_owned(&uuid).unwrap();
+        assert_eq!(task.get_summary(), "second");
+        assert_eq!(task.get_status(), &TaskStatus::Pending);
+        assert_true!(redo.base.redos.is_empty());
+
+        // The redone actions can be undone again
+        let commands: Vec<_> = redo.base.undos.iter().map(|u| u.command.as_str()).collect();
+        assert_eq!(commands, ["add first", "1 modify second"]);
+        assert_false!(redo.base.undos.iter().any(|u| u.is_new()));
+    }
+
+    #[test]
+    fn test_undo_count() {
+        let (tasks, undos, uuid) = init_tasks();
+        let mut action = UndoTaskAction::default();
+        action.base.tasks = tasks.clone();
+        action.base.undos = undos.clone();
+        action.base.arguments = vec!["3".to_owned()];
+        assert_true!(action.do_action(&MockPrinter::default()).is_err());
+
+        action.base.arguments = vec!["2".to_owned()];
+        action.do_action(&MockPrinter::default()).unwrap();
+        assert_eq!(
+            action.base.tasks.get_owned(&uuid).unwrap().get_status(),
+            &TaskStatus::Deleted
+        );
+        // The most recent action is the last one that can be redone
+        let commands: Vec<_> = action
+            .base
+            .redos
+            .iter()
+            .map(|u| u.command.as_str())
+            .collect();
+        assert_eq!(commands, ["1 modify second", "add first"]);
+    }
+
+    #[test]
+    fn test_undo_list() {
+        let (tasks, undos, _) = init_tasks();
+        let mut action = UndoTaskAction::default();
+        action.base.tasks = tasks;
+        action.base.undos = undos;
+        action.base.arguments = vec!["list".to_owned()];
+        let printer = MockPrinter::default();
+
+        action.do_action(&printer).unwrap();
+        let messages = printer.messages.borrow();
+        assert_eq!(messages.len(), 4);
+        assert_true!(messages[0].starts_with("  1 "));
+        assert_true!(messages[0].ends_with(" 1 modify second"));
+        assert_eq!(messages[1], "      'first'");
+        assert_true!(messages[2].ends_with(" add first"));
+        // Listing doesn't undo anything
+        assert_eq!(action.base.undos.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_action_count() {
+        assert_eq!(parse_action_count(&[], "undo"), Ok(1));
+        assert_eq!(parse_action_count(&["4".to_owned()], "undo"), Ok(4));
+        assert_true!(parse_action_count(&["0".to_owned()], "undo").is_err());
+        assert_true!(parse_action_count(&["abc".to_owned()], "undo").is_err());
+        assert_true!(parse_action_count(&["1".to_owned(), "2".to_owned()], "undo").is_err());
+    }
+}
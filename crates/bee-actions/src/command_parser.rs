@@ -1,20 +1,40 @@
+use bee_core::BeeError;
 use bee_core::filters;
 use bee_core::filters::Filter;
+use bee_core::task::parse_sort_keys;
 use log::debug;
 
 use bee_core::config::{ReportConfig, get_config};
+use bee_core::context::Context;
 
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Default)]
 pub struct Parser {
     command_parsers: Vec<ParsedCommand>,
+    /// The filter of the active context, or the error given when parsing it. The error
+    /// is only returned for the commands using the context, so that the context can
+    /// still be changed.
+    context_filter: Option<Result<Box<dyn Filter>, BeeError>>,
 }
 
 impl Parser {
     pub fn register_command_parser(&mut self, command_parser: ParsedCommand) {
         self.command_parsers.push(command_parser);
     }
+
+    pub fn set_context(&mut self, context: Option<&Context>) {
+        self.context_filter = context.map(|context| context.get_filter());
+    }
+
+    /// Restrict the filters to the active context
+    fn add_context_filter(&self, filters: Box<dyn Filter>) -> Result<Box<dyn Filter>, BeeError> {
+        match &self.context_filter {
+            Some(Ok(context_filter)) => Ok(filters::and(filters, context_filter.clone())),
+            Some(Err(error)) => Err(error.clone()),
+            None => Ok(filters),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -25,11 +45,71 @@ pub struct ParsedCommand {
     pub filters: Box<dyn Filter>,
     pub arguments: Vec<String>,
     pub arguments_as_filters: bool,
+    /// The filters of the report are not applied to the command
+    pub ignore_report_filters: bool,
+    /// The filter of the active context is applied to the command
+    pub use_context: bool,
+    /// The command reads the '--format <format>' argument
+    pub use_format: bool,
     pub report_kind: ReportConfig,
 }
 
+/// Read the keys of a 'sort:due+,project-' argument, None if the argument is not one
+fn parse_sort_argument(arg: &str) -> Result<Option<Vec<String>>, BeeError> {
+    let Some(keys) = arg.strip_prefix("sort:") else {
+        return Ok(None);
+    };
+    let keys: Vec<String> = keys
+        .split(',')
+        .filter(|key| !key.is_empty())
+        .map(|key| key.to_string())
+        .collect();
+    if keys.is_empty() {
+        return Err(BeeError::parse(
+            "Expected sort keys after 'sort:', e.g. 'sort:due+,project-'",
+        ));
+    }
+    parse_sort_keys(&keys)?;
+    Ok(Some(keys))
+}
+
+/// The arguments of a '--format <format>' or '--format=<format>' option, None if the
+/// argument is not one. The format is read by the command.
+fn take_format_argument<'a>(
+    arg: &str,
+    remaining_args: &mut impl Iterator<Item = &'a String>,
+) -> Option<Vec<String>> {
+    if arg == "--format" {
+        let mut format_args = vec![arg.to_string()];
+        format_args.extend(remaining_args.next().cloned());
+        return Some(format_args);
+    }
+    if arg.starts_with("--format=") {
+        return Some(vec![arg.to_string()]);
+    }
+    None
+}
+
+/// The format given to a command with '--format', if any
+pub(crate) fn get_format_argument(arguments: &[String]) -> Result<Option<&str>, BeeError> {
+    match arguments {
+        [] => Ok(None),
+        [option, format] if option == "--format" => Ok(Some(format.as_str())),
+        [option] if option.starts_with("--format=") => Ok(Some(&option["--format=".len()..])),
+        [option] if option == "--format" => Err(BeeError::InvalidCommand(
+            "A format is expected after '--format'.".to_string(),
+        )),
+        _ => Err(BeeError::InvalidCommand(
+            "A single format is expected.".to_string(),
+        )),
+    }
+}
+
 impl Parser {
-    pub fn parse_command_line_arguments(&self, args: Vec<String>) -> Result<ParsedCommand, String> {
+    pub fn parse_command_line_arguments(
+        &self,
+        args: Vec<String>,
+    ) -> Result<ParsedCommand, BeeError> {
         // Build a map from command name to ParsedCommand
         let mut command_to_parser = HashMap::new();
         for parsed_command in &self.command_parsers {
@@ -41,26 +121,63 @@ impl Parser {
         let mut report_kind = get_config().get_default_report().clone();
         let mut filters = Vec::new();
         let mut command_args = Vec::new();
+        // 'sort:<keys>' replaces the sort order of the report, whichever comes first
+        let mut sort_override = None;
+        // '--format <format>' is given to the command, instead of being a filter
+        let mut format_args = Vec::new();
 
-        for (idx, arg) in arguments.iter().enumerate() {
+        let mut args_iter = arguments.iter().enumerate();
+        while let Some((idx, arg)) = args_iter.next() {
             if let Some(parsed_command) = command_to_parser.get_mut(arg) {
-                for remaining_arg in &arguments[idx + 1..] {
+                let mut remaining_args = arguments[idx + 1..].iter();
+                while let Some(remaining_arg) = remaining_args.next() {
                     if let Some(report) = get_config().get_report(remaining_arg) {
                         debug!("Matched report '{}'", remaining_arg);
                         report_kind = report.clone();
                         continue;
                     }
+                    if parsed_command.arguments_as_filters
+                        && let Some(keys) = parse_sort_argument(remaining_arg)?
+                    {
+                        sort_override = Some(keys);
+                        continue;
+                    }
+                    // Taken from every command, so that the ones without the option
+                    // reject it instead of reading it as text
+                    if let Some(args) = take_format_argument(remaining_arg, &mut remaining_args) {
+                        format_args.extend(args);
+                        continue;
+                    }
                     command_args.push(remaining_arg.clone());
                 }
+                if !format_args.is_empty() && !parsed_command.use_format {
+                    return Err(BeeError::InvalidCommand(format!(
+                        "The '{}' command has no '--format' option.",
+                        parsed_command.command
+                    )));
+                }
                 if parsed_command.arguments_as_filters {
                     filters.extend(command_args.clone());
+                    parsed_command.arguments = format_args;
                 } else {
-                    parsed_command.arguments = command_args;
+                    format_args.extend(command_args);
+                    parsed_command.arguments = format_args;
+                }
+                parsed_command.filters = if parsed_command.ignore_report_filters {
+                    filters::from(&filters)?
+                } else {
+                    filters::and(
+                        filters::from(&filters)?,
+                        filters::from(&report_kind.filters)?,
+                    )
+                };
+                if parsed_command.use_context {
+                    parsed_command.filters =
+                        self.add_context_filter(parsed_command.filters.clone())?;
+                }
+                if let Some(sort) = sort_override {
+                    report_kind.sort = sort;
                 }
-                parsed_command.filters = filters::and(
-                    filters::from(&filters)?,
-                    filters::from(&report_kind.filters)?,
-                );
                 parsed_command.report_kind = report_kind;
                 return Ok(parsed_command.clone());
             }
@@ -71,19 +188,147 @@ impl Parser {
                 report_kind = report.clone();
                 continue;
             }
+            if let Some(keys) = parse_sort_argument(arg)? {
+                sort_override = Some(keys);
+                continue;
+            }
+            if let Some(args) = take_format_argument(arg, &mut args_iter.by_ref().map(|(_, a)| a)) {
+                format_args.extend(args);
+                continue;
+            }
 
             filters.push(arg.clone());
         }
+        if let Some(sort) = sort_override {
+            report_kind.sort = sort;
+        }
 
         let command_filters = filters::and(
             filters::from(&filters)?,
             filters::from(&report_kind.filters)?,
         );
         Ok(ParsedCommand {
-            filters: command_filters,
+            filters: self.add_context_filter(command_filters)?,
             command: "list".to_string(),
+            arguments: format_args,
+            use_context: true,
             report_kind,
             ..Default::default()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_sort_argument() {
+        assert_eq!(parse_sort_argument("+home").unwrap(), None);
+        assert_eq!(
+            parse_sort_argument("sort:due+,project-").unwrap(),
+            Some(vec!["due+".to_string(), "project-".to_string()])
+        );
+        assert_true!(parse_sort_argument("sort:").is_err());
+        assert_true!(parse_sort_argument("sort:colour+").is_err());
+    }
+
+    #[test]
+    fn test_take_format_argument() {
+        let remaining = ["csv".to_string(), "+home".to_string()];
+        let mut remaining_args = remaining.iter();
+        assert_eq!(take_format_argument("+home", &mut remaining_args), None);
+        assert_eq!(
+            take_format_argument("--format", &mut remaining_args),
+            Some(vec!["--format".to_string(), "csv".to_string()])
+        );
+        assert_eq!(remaining_args.next().unwrap(), "+home");
+        assert_eq!(
+            take_format_argument("--format=tsv", &mut remaining_args),
+            Some(vec!["--format=tsv".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_get_format_argument() {
+        let get = |arguments: &[&str]| {
+            let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+            get_format_argument(&arguments).map(|format| format.map(|f| f.to_string()))
+        };
+        assert_eq!(get(&[]).unwrap(), None);
+        assert_eq!(get(&["--format", "ics"]).unwrap(), Some("ics".to_string()));
+        assert_eq!(get(&["--format=csv"]).unwrap(), Some("csv".to_string()));
+        assert_true!(get(&["--format"]).is_err());
+        assert_true!(get(&["--format=csv", "--format=tsv"]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_context_filter() {
+        let mut parser = Parser::default();
+        for cmd in crate::ActionRegistry::get_parsed_commands() {
+            parser.register_command_parser(cmd);
+        }
+        parser.set_context(Some(&Context {
+            filter: "project:".to_string(),
+            ..Context::default()
+        }));
+        let parse = |parser: &Parser, arguments: &[&str]| {
+            let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+            parser.parse_command_line_arguments(arguments)
+        };
+
+        // Only the commands using the context fail, the context can still be changed
+        assert_true!(parse(&parser, &["bee"]).is_err());
+        assert_true!(parse(&parser, &["bee", "list"]).is_err());
+        assert_true!(parse(&parser, &["bee", "context", "none"]).is_ok());
+    }
+
+    #[test]
+    fn test_format_argument() {
+        let mut parser = Parser::default();
+        for cmd in crate::ActionRegistry::get_parsed_commands() {
+            parser.register_command_parser(cmd);
+        }
+        let parse = |arguments: &[&str]| {
+            let arguments: Vec<String> = arguments.iter().map(|a| a.to_string()).collect();
+            parser.parse_command_line_arguments(arguments)
+        };
+
+        assert_eq!(
+            parse(&["bee", "--format", "csv"]).unwrap().arguments,
+            vec!["--format", "csv"]
+        );
+        assert_eq!(
+            parse(&["bee", "+home", "list", "--format=tsv"])
+                .unwrap()
+                .arguments,
+            vec!["--format=tsv"]
+        );
+        assert_eq!(
+            parse(&["bee", "export", "--format", "ics"])
+                .unwrap()
+                .arguments,
+            vec!["--format", "ics"]
+        );
+        assert_eq!(
+            parse(&["bee", "--format", "json", "history", "daily"])
+                .unwrap()
+                .arguments,
+            vec!["--format", "json", "daily"]
+        );
+        assert_eq!(
+            parse(&["bee", "burndown", "monthly", "--format", "json"])
+                .unwrap()
+                .arguments,
+            vec!["--format", "json", "monthly"]
+        );
+        // The other commands don't read it, instead of silently dropping it
+        assert_true!(parse(&["bee", "1", "start", "--format", "csv"]).is_err());
+        assert_true!(parse(&["bee", "--format=csv", "1", "done"]).is_err());
+        assert_true!(parse(&["bee", "--format", "csv", "calendar"]).is_err());
+        assert_true!(parse(&["bee", "add", "buy", "milk", "--format", "csv"]).is_err());
+        assert_true!(parse(&["bee", "2", "modify", "--format=csv"]).is_err());
+    }
+}
@@ -0,0 +1,507 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use log::{debug, info};
+
+use crate::TaskAction;
+use bee_core::config::get_config;
+use bee_core::task::{Task, TaskData, TaskStatus};
+use bee_core::{BeeError, Printer};
+
+/// What happened to a task during an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Add,
+    Modify,
+    Done,
+    Delete,
+}
+
+impl HookEvent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::Add => "add",
+            HookEvent::Modify => "modify",
+            HookEvent::Done => "done",
+            HookEvent::Delete => "delete",
+        }
+    }
+}
+
+/// The pre-hooks run once the action changed the tasks but before anything is written,
+/// the post-hooks once the changes are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    Pre,
+    Post,
+}
+
+impl HookStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HookStage::Pre => "pre",
+            HookStage::Post => "post",
+        }
+    }
+}
+
+/// A task changed by an action, as given to the hooks
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskChange {
+    pub event: HookEvent,
+    /// The task before the action, none for a new task
+    pub before: Option<Task>,
+    pub after: Task,
+}
+
+impl TaskChange {
+    /// One JSON object per line, the task before the action first. A new task only has
+    /// the line of the task after the action.
+    fn to_hook_input(&self) -> Result<String, BeeError> {
+        let mut input = String::new();
+        for task in self.before.iter().chain(std::iter::once(&self.after)) {
+            input += &serde_json::to_string(task).map_err(|e| {
+                BeeError::Hook(format!(
+                    "Unable to serialize the task '{}' for the hooks: {}",
+                    task.get_summary(),
+                    e
+                ))
+            })?;
+            input += "\n";
+        }
+        Ok(input)
+    }
+}
+
+/// Compare the tasks before and after an action
+pub fn get_changes(before: &TaskData, after: &TaskData) -> Vec<TaskChange> {
+    let mut changes: Vec<TaskChange> = after
+        .get_task_map()
+        .values()
+        .filter_map(|task| {
+            let previous = before.get_task_map().get(task.get_uuid());
+            if previous == Some(task) {
+                return None;
+            }
+            let was = |status: TaskStatus| previous.is_some_and(|p| *p.get_status() == status);
+            let event = match task.get_status() {
+                _ if previous.is_none() => HookEvent::Add,
+                TaskStatus::Completed if !was(TaskStatus::Completed) => HookEvent::Done,
+                TaskStatus::Deleted if !was(TaskStatus::Deleted) => HookEvent::Delete,
+                _ => HookEvent::Modify,
+            };
+            Some(TaskChange {
+                event,
+                before: previous.cloned(),
+                after: task.clone(),
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| {
+        a.after
+            .get_date_created()
+            .cmp(b.after.get_date_created())
+            .then(a.after.get_uuid().cmp(b.after.get_uuid()))
+    });
+    changes
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Run the executables of the hooks directory named after a stage and an event, e.g.
+/// 'pre-add' or 'post-done-notify.sh'. Each hook gets the task before and after the
+/// action on its standard input.
+#[derive(Debug, Default)]
+pub struct HookRunner {
+    hooks_dir: Option<PathBuf>,
+}
+
+impl HookRunner {
+    pub fn new(hooks_dir: Option<PathBuf>) -> HookRunner {
+        HookRunner { hooks_dir }
+    }
+
+    pub fn from_config() -> HookRunner {
+        HookRunner::new(get_config().get_hooks_dir())
+    }
+
+    /// The hooks to run for an event, sorted by name
+    fn find_hooks(&self, stage: HookStage, event: HookEvent) -> Vec<PathBuf> {
+        let Some(dir) = &self.hooks_dir else {
+            return Vec::new();
+        };
+        let prefix = format!("{}-{}", stage.as_str(), event.as_str());
+        let mut hooks: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&prefix))
+                })
+                .filter(|path| {
+                    let executable = is_executable(path);
+                    if !executable {
+                        debug!("Skipping hook '{}', it isn't executable", path.display());
+                    }
+                    executable
+                })
+                .collect(),
+            Err(e) => {
+                debug!("Unable to read the hooks directory: {}", e);
+                Vec::new()
+            }
+        };
+        hooks.sort();
+        hooks
+    }
+
+    /// Run a hook, returning its standard output. A pre-hook exiting with an error
+    /// rejects the change.
+    fn run_hook(
+        &self,
+        hook: &Path,
+        stage: HookStage,
+        change: &TaskChange,
+        printer: &dyn Printer,
+    ) -> Result<String, BeeError> {
+        let name = hook
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        info!("Running hook '{}'", name);
+
+        let input = change.to_hook_input()?;
+        let mut child = Command::new(hook)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| BeeError::Hook(format!("Unable to run the hook '{}': {}", name, e)))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook may exit without reading its input
+            let _ = stdin.write_all(input.as_bytes());
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| BeeError::Hook(format!("Unable to run the hook '{}': {}", name, e)))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            let failure = match stage {
+                HookStage::Pre => "rejected the change of",
+                HookStage::Post => "failed after the change of",
+            };
+            let feedback = format!("{}{}", stdout, stderr);
+            let feedback = feedback.trim();
+            return Err(BeeError::Hook(if feedback.is_empty() {
+                format!(
+                    "The hook '{}' {} task '{}'.",
+                    name,
+                    failure,
+                    change.after.get_summary()
+                )
+            } else {
+                format!(
+                    "The hook '{}' {} task '{}': {}",
+                    name,
+                    failure,
+                    change.after.get_summary(),
+                    feedback
+                )
+            }));
+        }
+        for line in stderr.lines().filter(|l| !l.trim().is_empty()) {
+            printer.show_information_message(line);
+        }
+        Ok(stdout)
+    }
+
+    /// Run the pre-hooks of a change. A hook can print the task as JSON to replace it,
+    /// any other line it prints is shown to the user.
+    fn run_pre_hooks(
+        &self,
+        change: &mut TaskChange,
+        printer: &dyn Printer,
+    ) -> Result<(), BeeError> {
+        for hook in self.find_hooks(HookStage::Pre, change.event) {
+            let stdout = self.run_hook(&hook, HookStage::Pre, change, printer)?;
+            for line in stdout.lines().filter(|l| !l.trim().is_empty()) {
+                if !line.trim_start().starts_with('{') {
+                    printer.show_information_message(line);
+                    continue;
+                }
+                let task: Task = serde_json::from_str(line).map_err(|e| {
+                    BeeError::Hook(format!(
+                        "The hook '{}' returned an invalid task: {}",
+                        hook.display(),
+                        e
+                    ))
+                })?;
+                if task.get_uuid() != change.after.get_uuid() {
+                    return Err(BeeError::Hook(format!(
+                        "The hook '{}' can't change the UUID of task '{}'.",
+                        hook.display(),
+                        change.after.get_summary()
+                    )));
+                }
+                change.after = task;
+            }
+        }
+        Ok(())
+    }
+
+    /// Perform an action, then run the pre-hooks of every task it changed. The tasks
+    /// rewritten by the hooks replace the ones of the action. An error means the action
+    /// must not be written.
+    pub fn run_action(
+        &self,
+        action: &mut dyn TaskAction,
+        printer: &dyn Printer,
+    ) -> Result<Vec<TaskChange>, BeeError> {
+        if self.hooks_dir.is_none() {
+            action.do_action(printer)?;
+            return Ok(Vec::new());
+        }
+
+        let before = action.get_tasks().clone();
+        action.do_action(printer)?;
+        let mut changes = get_changes(&before, action.get_tasks());
+
+        let mut rewritten = false;
+        for change in &mut changes {
+            let after = change.after.clone();
+            self.run_pre_hooks(change, printer)?;
+            rewritten |= change.after != after;
+        }
+        if rewritten {
+            let mut tasks = action.get_tasks().clone();
+            for change in &changes {
+                tasks.set_task(change.after.clone());
+            }
+            action.set_tasks(tasks);
+        }
+        Ok(changes)
+    }
+
+    /// Run the post-hooks of the changes once they are written. Their output is only
+    /// shown to the user, and as the changes can't be undone anymore a failing hook is
+    /// only a warning.
+    pub fn run_post_hooks(&self, changes: &[TaskChange], printer: &dyn Printer) {
+        for change in changes {
+            for hook in self.find_hooks(HookStage::Post, change.event) {
+                match self.run_hook(&hook, HookStage::Post, change, printer) {
+                    Ok(stdout) => {
+                        for line in stdout.lines().filter(|l| !l.trim().is_empty()) {
+                            printer.show_information_message(line);
+                        }
+                    }
+                    Err(error) => printer.warning(&error),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use all_asserts::{assert_false, assert_true};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::action_add::AddTaskAction;
+    use crate::action_done::DoneTaskAction;
+    use crate::mock_printer::MockPrinter;
+    use bee_core::task::TaskProperties;
+
+    fn write_hook(dir: &TempDir, name: &str, script: &str) {
+        let path = dir.path().join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn add_action(arguments: &[&str]) -> AddTaskAction {
+        let mut action = AddTaskAction::default();
+        action.base.arguments = arguments.iter().map(|a| a.to_string()).collect();
+        action
+    }
+
+    #[test]
+    fn test_get_changes() {
+        let mut before = TaskData::default();
+        let uuid = *before
+            .add_task(
+                &TaskProperties::from(&["task 1".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .get_uuid();
+        let mut after = before.clone();
+        assert_true!(get_changes(&before, &after).is_empty());
+
+        after.task_done(&uuid).unwrap();
+        let new_uuid = *after
+            .add_task(
+                &TaskProperties::from(&["task 2".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap()
+            .get_uuid();
+
+        let changes = get_changes(&before, &after);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].event, HookEvent::Done);
+        assert_eq!(changes[0].before.as_ref().unwrap().get_uuid(), &uuid);
+        assert_eq!(changes[1].event, HookEvent::Add);
+        assert_eq!(changes[1].after.get_uuid(), &new_uuid);
+        assert_eq!(changes[1].before, None);
+    }
+
+    #[test]
+    fn test_find_hooks() {
+        let dir = TempDir::new().unwrap();
+        write_hook(&dir, "pre-add-2", "exit 0");
+        write_hook(&dir, "pre-add-1", "exit 0");
+        write_hook(&dir, "post-add", "exit 0");
+        fs::write(dir.path().join("pre-add-disabled"), "exit 0").unwrap();
+
+        let runner = HookRunner::new(Some(dir.path().to_path_buf()));
+        let hooks = runner.find_hooks(HookStage::Pre, HookEvent::Add);
+        assert_eq!(
+            hooks,
+            vec![dir.path().join("pre-add-1"), dir.path().join("pre-add-2")]
+        );
+        assert_true!(
+            runner
+                .find_hooks(HookStage::Pre, HookEvent::Done)
+                .is_empty()
+        );
+        assert_true!(
+            HookRunner::default()
+                .find_hooks(HookStage::Pre, HookEvent::Add)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_pre_hook_rejects() {
+        let dir = TempDir::new().unwrap();
+        write_hook(
+            &dir,
+            "pre-add-bug-project",
+            r#"read task
+case "$task" in
+  *'"tags":["bug"]'*) case "$task" in *'"project":null'*) echo "A bug needs a project"; exit 1;; esac;;
+esac
+exit 0"#,
+        );
+        let runner = HookRunner::new(Some(dir.path().to_path_buf()));
+        let printer = MockPrinter::default();
+
+        let mut action = add_action(&["crash", "+bug"]);
+        let result = runner.run_action(&mut action, &printer);
+        assert_eq!(
+            result.unwrap_err(),
+            BeeError::Hook(
+                "The hook 'pre-add-bug-project' rejected the change of task 'crash': \
+                A bug needs a project"
+                    .to_string()
+            )
+        );
+
+        let mut action = add_action(&["crash", "+bug", "project:app"]);
+        let changes = runner.run_action(&mut action, &printer).unwrap();
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[test]
+    fn test_pre_hook_rewrites() {
+        let dir = TempDir::new().unwrap();
+        write_hook(
+            &dir,
+            "pre-add",
+            r#"read task
+echo "Summary changed"
+echo "$task" | sed 's/"summary":"[^"]*"/"summary":"rewritten"/'"#,
+        );
+        let runner = HookRunner::new(Some(dir.path().to_path_buf()));
+        let printer = MockPrinter::default();
+
+        let mut action = add_action(&["original"]);
+        let changes = runner.run_action(&mut action, &printer).unwrap();
+        assert_eq!(changes[0].after.get_summary(), "rewritten");
+        let tasks = action.get_tasks().to_vec();
+        assert_eq!(tasks[0].get_summary(), "rewritten");
+        assert_true!(
+            printer
+                .messages
+                .borrow()
+                .contains(&"Summary changed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_post_hooks() {
+        let dir = TempDir::new().unwrap();
+        write_hook(&dir, "post-done", r#"read before; read after; echo "done""#);
+        write_hook(&dir, "post-add", "echo added");
+        let runner = HookRunner::new(Some(dir.path().to_path_buf()));
+
+        let mut tasks = TaskData::default();
+        tasks
+            .add_task(
+                &TaskProperties::from(&["task".to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap();
+        let mut action = DoneTaskAction::default();
+        action.base.tasks = tasks;
+
+        let printer = MockPrinter::default();
+        let changes = runner.run_action(&mut action, &printer).unwrap();
+        assert_eq!(changes.len(), 1);
+        runner.run_post_hooks(&changes, &printer);
+        let messages = printer.messages.borrow();
+        assert_true!(messages.contains(&"done".to_string()));
+        assert_false!(messages.contains(&"added".to_string()));
+    }
+
+    #[test]
+    fn test_failing_post_hook() {
+        let dir = TempDir::new().unwrap();
+        write_hook(&dir, "post-add-1", "echo 'no network' >&2; exit 1");
+        write_hook(&dir, "post-add-2", "echo notified");
+        let runner = HookRunner::new(Some(dir.path().to_path_buf()));
+
+        let printer = MockPrinter::default();
+        let mut action = add_action(&["task"]);
+        let changes = runner.run_action(&mut action, &printer).unwrap();
+        printer.messages.borrow_mut().clear();
+        // The change is already written, the failure is a warning and the other hooks
+        // still run
+        runner.run_post_hooks(&changes, &printer);
+        assert_eq!(
+            *printer.messages.borrow(),
+            vec![
+                "Warning: The hook 'post-add-1' failed after the change of task 'task': \
+                 no network"
+                    .to_string(),
+                "notified".to_string()
+            ]
+        );
+    }
+}
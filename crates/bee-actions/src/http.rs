@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use ureq::Agent;
+use ureq::http::Request;
+
+use bee_core::BeeError;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The part of a response the REST APIs need
+#[derive(Debug, PartialEq)]
+pub(crate) struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Send a request to an 'http://' or 'https://' address and wait for the whole
+/// response. The error statuses are responses like the others, for the caller to read
+/// the details the server gives.
+pub(crate) fn send_request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, String)],
+    body: Option<&str>,
+) -> Result<HttpResponse, BeeError> {
+    let err_msg = |e: ureq::Error| BeeError::Remote(format!("Unable to reach '{}': {}", url, e));
+
+    let mut request = Request::builder()
+        .method(method)
+        .uri(url)
+        .header("Accept", "application/json");
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    let body = body.unwrap_or_default();
+    if !body.is_empty() {
+        request = request.header("Content-Type", "application/json");
+    }
+    let request = request
+        .body(body)
+        .map_err(|e| BeeError::Remote(format!("Invalid request to '{}': {}", url, e)))?;
+
+    let agent: Agent = Agent::config_builder()
+        .timeout_global(Some(TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let mut response = agent.run(request).map_err(err_msg)?;
+    Ok(HttpResponse {
+        status: response.status().as_u16(),
+        body: response.body_mut().read_to_string().map_err(err_msg)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+    use tiny_http::{Response, Server};
+
+    use super::*;
+
+    #[test]
+    fn test_send_request() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/issue", server.server_addr().to_ip().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let authorization = request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("Authorization"))
+                .map(|h| h.value.to_string());
+            assert_eq!(request.method().as_str(), "PUT");
+            assert_eq!(authorization.as_deref(), Some("Bearer secret"));
+            assert_eq!(body, "{}");
+            request
+                .respond(Response::from_string("{\"a\":1}").with_status_code(404))
+                .unwrap();
+        });
+
+        let response = send_request(
+            "PUT",
+            &url,
+            &[("Authorization", "Bearer secret".to_string())],
+            Some("{}"),
+        )
+        .unwrap();
+        handle.join().unwrap();
+        assert_eq!(
+            response,
+            HttpResponse {
+                status: 404,
+                body: "{\"a\":1}".to_string()
+            }
+        );
+
+        assert_true!(send_request("GET", "not an address", &[], None).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use log::debug;
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use serde::Deserialize;
+
+use crate::http::{HttpResponse, send_request};
+use bee_core::BeeError;
+use bee_core::config::JiraConfig;
+use bee_core::task::{JIRA_FIELDS, JiraIssue, JiraSearchResult, JiraStatusCategory};
+
+/// How many issues are requested at once
+const PAGE_SIZE: usize = 50;
+
+#[derive(Deserialize)]
+struct JiraTransitionTarget {
+    name: String,
+    #[serde(rename = "statusCategory")]
+    category: JiraTransitionCategory,
+}
+
+#[derive(Deserialize)]
+struct JiraTransitionCategory {
+    key: JiraStatusCategory,
+}
+
+#[derive(Deserialize)]
+struct JiraTransition {
+    id: String,
+    to: JiraTransitionTarget,
+}
+
+#[derive(Deserialize)]
+struct JiraTransitions {
+    transitions: Vec<JiraTransition>,
+}
+
+#[derive(Deserialize, Default)]
+struct JiraErrors {
+    #[serde(default, rename = "errorMessages")]
+    error_messages: Vec<String>,
+}
+
+/// Talks to the REST API (version 2) of a Jira server
+pub(crate) struct JiraClient {
+    url: String,
+    authorization: Option<String>,
+}
+
+impl JiraClient {
+    pub fn new(config: &JiraConfig) -> JiraClient {
+        let authorization = match (&config.user, &config.token) {
+            (Some(user), Some(token)) => Some(format!(
+                "Basic {}",
+                BASE64.encode(format!("{}:{}", user, token))
+            )),
+            (None, Some(token)) => Some(format!("Bearer {}", token)),
+            (_, None) => None,
+        };
+        JiraClient {
+            url: config.url.trim_end_matches('/').to_string(),
+            authorization,
+        }
+    }
+
+    fn send(&self, method: &str, path: &str, body: Option<&str>) -> Result<HttpResponse, BeeError> {
+        debug!("Jira request: {} {}", method, path);
+        let headers: Vec<(&str, String)> = self
+            .authorization
+            .iter()
+            .map(|value| ("Authorization", value.to_owned()))
+            .collect();
+        let response = send_request(method, &format!("{}{}", self.url, path), &headers, body)?;
+        if response.status == 404 || (200..300).contains(&response.status) {
+            return Ok(response);
+        }
+
+        let errors: JiraErrors = serde_json::from_str(&response.body).unwrap_or_default();
+        let details = match errors.error_messages.is_empty() {
+            true => String::new(),
+            false => format!(": {}", errors.error_messages.join(" ")),
+        };
+        Err(BeeError::Remote(format!(
+            "Jira answered {} to '{} {}'{}",
+            response.status, method, path, details
+        )))
+    }
+
+    fn parse<'a, T: Deserialize<'a>>(response: &'a HttpResponse) -> Result<T, BeeError> {
+        serde_json::from_str(&response.body)
+            .map_err(|e| BeeError::Remote(format!("Unable to read the answer of Jira: {}", e)))
+    }
+
+    /// All the issues matching the query, requested one page at a time
+    pub fn search(&self, jql: &str) -> Result<Vec<JiraIssue>, BeeError> {
+        let mut issues = Vec::new();
+        loop {
+            let path = format!(
+                "/rest/api/2/search?jql={}&fields={}&startAt={}&maxResults={}",
+                utf8_percent_encode(jql, NON_ALPHANUMERIC),
+                JIRA_FIELDS,
+                issues.len(),
+                PAGE_SIZE
+            );
+            let response = self.send("GET", &path, None)?;
+            if response.status == 404 {
+                return Err(BeeError::Remote(format!(
+                    "The Jira search API was not found at '{}'",
+                    self.url
+                )));
+            }
+            let page: JiraSearchResult = Self::parse(&response)?;
+            if page.issues.is_empty() {
+                return Ok(issues);
+            }
+            issues.extend(page.issues);
+            if issues.len() >= page.total {
+                return Ok(issues);
+            }
+        }
+    }
+
+    /// The issue, if it still exists
+    pub fn get_issue(&self, key: &str) -> Result<Option<JiraIssue>, BeeError> {
+        let path = format!("/rest/api/2/issue/{}?fields={}", key, JIRA_FIELDS);
+        let response = self.send("GET", &path, None)?;
+        if response.status == 404 {
+            return Ok(None);
+        }
+        Self::parse(&response).map(Some)
+    }
+
+    /// Move the issue to a status of the category, through the first transition of its
+    /// workflow leading to one. Returns the name of the new status.
+    pub fn set_status(&self, key: &str, category: JiraStatusCategory) -> Result<String, BeeError> {
+        let path = format!("/rest/api/2/issue/{}/transitions", key);
+        let response = self.send("GET", &path, None)?;
+        if response.status == 404 {
+            return Err(BeeError::Remote(format!(
+                "The Jira issue '{}' doesn't exist anymore",
+                key
+            )));
+        }
+        let transitions: JiraTransitions = Self::parse(&response)?;
+        let transition = transitions
+            .transitions
+            .into_iter()
+            .find(|t| t.to.category.key == category)
+            .ok_or_else(|| {
+                BeeError::Remote(format!(
+                    "The Jira issue '{}' has no transition to a status of the category '{}'",
+                    key, category
+                ))
+            })?;
+
+        let body = serde_json::json!({"transition": {"id": transition.id}}).to_string();
+        let response = self.send("POST", &path, Some(&body))?;
+        if response.status == 404 {
+            return Err(BeeError::Remote(format!(
+                "The Jira issue '{}' doesn't exist anymore",
+                key
+            )));
+        }
+        Ok(transition.to.name)
+    }
+}
@@ -1,29 +1,48 @@
 pub mod command_parser;
+pub mod hooks;
+
+mod http;
+mod jira;
 
 mod action_type;
 
 mod action_add;
 mod action_annotate;
+mod action_burndown;
+mod action_calendar;
 mod action_cmd;
+mod action_context;
 mod action_delete;
+mod action_diagnose;
 mod action_done;
 mod action_edit;
 mod action_export;
 mod action_help;
+mod action_history;
+mod action_import;
 mod action_info;
 mod action_list;
 mod action_modify;
+mod action_redo;
 mod action_start;
 mod action_stop;
+mod action_summary;
+mod action_sync;
+mod action_timesheet;
 mod action_undo;
 
+#[cfg(test)]
+mod mock_printer;
+
 use action_type::ActionType;
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
 use crate::command_parser::ParsedCommand;
 use bee_core::{
-    Printer,
+    BeeError, Printer,
     config::ReportConfig,
+    context::Contexts,
     task::{Task, TaskData},
 };
 
@@ -37,7 +56,7 @@ pub enum ActionUndoType {
 pub trait TaskAction {
     /// This is the main execution of the action. This is where it will affect
     /// the tasks it targets or call the printer
-    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), String>;
+    fn do_action(&mut self, printer: &dyn Printer) -> Result<(), BeeError>;
 
     /// Setter for the ActionUndo vector
     fn set_undos(&mut self, undos: Vec<ActionUndo>);
@@ -45,6 +64,18 @@ pub trait TaskAction {
     /// Getter for the ActionUndo vector
     fn get_undos(&self) -> &Vec<ActionUndo>;
 
+    /// Setter for the actions that were undone and can be redone
+    fn set_redos(&mut self, redos: Vec<ActionUndo>);
+
+    /// Getter for the actions that were undone and can be redone
+    fn get_redos(&self) -> &Vec<ActionUndo>;
+
+    /// Setter for the contexts and the active one
+    fn set_contexts(&mut self, contexts: Contexts);
+
+    /// Getter for the contexts, that the action may have changed
+    fn get_contexts(&self) -> &Contexts;
+
     /// Setter for the TaskData this action will operate upon
     fn set_tasks(&mut self, tasks: TaskData);
 
@@ -63,6 +94,29 @@ pub trait TaskAction {
 pub struct ActionUndo {
     pub action_type: ActionUndoType,
     pub tasks: Vec<Task>,
+    /// When the action was performed. Unknown for the actions logged by older versions.
+    #[serde(default)]
+    pub date: Option<DateTime<Local>>,
+    /// The command line that performed the action, filled in once the action is done
+    #[serde(default)]
+    pub command: String,
+}
+
+impl ActionUndo {
+    pub fn new(action_type: ActionUndoType, tasks: Vec<Task>) -> Self {
+        ActionUndo {
+            action_type,
+            tasks,
+            date: Some(Local::now()),
+            command: String::new(),
+        }
+    }
+
+    /// The action was performed by the current command, and its command line is not
+    /// known yet
+    pub fn is_new(&self) -> bool {
+        self.date.is_some() && self.command.is_empty()
+    }
 }
 
 #[derive(Default)]
@@ -76,6 +130,9 @@ impl ActionRegistry {
                 v.push(ParsedCommand {
                     command: alias.to_string(),
                     arguments_as_filters: data.use_arguments_as_filter,
+                    ignore_report_filters: data.ignore_report_filters,
+                    use_context: data.use_context,
+                    use_format: data.use_format,
                     ..Default::default()
                 })
             }
@@ -95,6 +152,8 @@ impl ActionRegistry {
 pub struct BaseTaskAction {
     tasks: TaskData,
     undos: Vec<ActionUndo>,
+    redos: Vec<ActionUndo>,
+    contexts: Contexts,
     arguments: Vec<String>,
     report: ReportConfig,
 }
@@ -123,6 +182,22 @@ impl BaseTaskAction {
     pub fn get_undos(&self) -> &Vec<ActionUndo> {
         &self.undos
     }
+
+    pub fn set_redos(&mut self, redos: Vec<ActionUndo>) {
+        self.redos = redos;
+    }
+
+    pub fn get_redos(&self) -> &Vec<ActionUndo> {
+        &self.redos
+    }
+
+    pub fn set_contexts(&mut self, contexts: Contexts) {
+        self.contexts = contexts;
+    }
+
+    pub fn get_contexts(&self) -> &Contexts {
+        &self.contexts
+    }
 }
 
 mod macros {
@@ -135,6 +210,18 @@ mod macros {
             fn get_undos(&self) -> &Vec<ActionUndo> {
                 self.base.get_undos()
             }
+            fn set_redos(&mut self, redos: Vec<ActionUndo>) {
+                self.base.set_redos(redos)
+            }
+            fn get_redos(&self) -> &Vec<ActionUndo> {
+                self.base.get_redos()
+            }
+            fn set_contexts(&mut self, contexts: bee_core::context::Contexts) {
+                self.base.set_contexts(contexts)
+            }
+            fn get_contexts(&self) -> &bee_core::context::Contexts {
+                self.base.get_contexts()
+            }
             fn set_tasks(&mut self, tasks: TaskData) {
                 self.base.set_tasks(tasks)
             }
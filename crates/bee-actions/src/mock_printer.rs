@@ -0,0 +1,55 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use bee_core::{
+    BeeError, Printer,
+    calendar::Calendar,
+    chart::BarChart,
+    config::{ReportConfig, ReportFormat},
+    task::Task,
+};
+
+/// The printer of the tests of the actions, recording what they print
+#[derive(Default)]
+pub(crate) struct MockPrinter {
+    /// The raw output and the information messages, in the order they were printed
+    pub messages: RefCell<Vec<String>>,
+    /// The format of each list of tasks
+    pub formats: RefCell<Vec<ReportFormat>>,
+    pub charts: RefCell<Vec<BarChart>>,
+    pub calendars: RefCell<Vec<Calendar>>,
+}
+
+impl Printer for MockPrinter {
+    fn print_list_of_tasks(
+        &self,
+        _: Vec<&Task>,
+        report_kind: &ReportConfig,
+    ) -> Result<(), BeeError> {
+        self.formats.borrow_mut().push(report_kind.format);
+        Ok(())
+    }
+    fn print_task_info(&self, _task: &Task) -> Result<(), BeeError> {
+        Ok(())
+    }
+    fn show_help(
+        &self,
+        _help_section_description: &HashMap<String, String>,
+    ) -> Result<(), BeeError> {
+        Ok(())
+    }
+    fn show_information_message(&self, message: &str) {
+        self.messages.borrow_mut().push(message.to_string());
+    }
+    fn error(&self, _: &str) {}
+    fn print_chart(&self, chart: &BarChart) -> Result<(), BeeError> {
+        self.charts.borrow_mut().push(chart.to_owned());
+        Ok(())
+    }
+    fn print_calendar(&self, calendar: &Calendar) -> Result<(), BeeError> {
+        self.calendars.borrow_mut().push(calendar.to_owned());
+        Ok(())
+    }
+    fn print_raw(&self, message: &str) {
+        self.messages.borrow_mut().push(message.to_string());
+    }
+}
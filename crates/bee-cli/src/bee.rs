@@ -1,127 +1,149 @@
 mod cli;
 mod config;
+mod plain_table;
 mod table;
+mod tui;
 
 use bee_actions::{ActionRegistry, command_parser::Parser};
 use bee_core::{
-    Printer,
+    BeeError, Printer,
+    config::{StorageBackend, get_config},
     filters::{self, Filter},
-    task::TaskProperties,
 };
-use bee_storage::storage::{JsonStore, Store};
+use bee_storage::{
+    execute::execute,
+    sqlite_storage::{self, SqliteStore},
+    storage::{JsonStore, Store},
+};
 
 use crate::{
     cli::SimpleTaskTextPrinter,
     config::{SectionType, get_cli_config},
 };
 
-use log::{debug, trace};
 use std::process::exit;
 
-fn get_section_filters() -> Result<Option<Box<dyn Filter>>, String> {
+/// The exit code of the command, telling the scripts calling it what went wrong
+fn get_exit_code(error: &BeeError) -> i32 {
+    match error {
+        BeeError::UnknownTask(_) | BeeError::InvalidTransition(_) => 1,
+        BeeError::Parse { .. } | BeeError::InvalidCommand(_) => 2,
+        BeeError::Config(_) => 3,
+        BeeError::Storage(_) => 4,
+        BeeError::Hook(_) => 5,
+        BeeError::Remote(_) => 6,
+        BeeError::Output(_) => 7,
+    }
+}
+
+fn fail(error: &BeeError) -> ! {
+    SimpleTaskTextPrinter.error(&error.to_string());
+    let command_line = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    if let Some(diagnostic) = error.get_diagnostic(&command_line) {
+        SimpleTaskTextPrinter.print_raw(&diagnostic);
+    }
+    exit(get_exit_code(error));
+}
+
+fn get_section_filters() -> Result<Option<Box<dyn Filter>>, BeeError> {
     let mut report_filter = filters::new_empty();
     let section_config = &get_cli_config().section;
-    if let Some(session_type) = &section_config.section_type {
-        if *session_type == SectionType::Filters {
-            for filter in section_config.filters.values() {
-                report_filter = filters::or(report_filter, filters::from(filter)?);
-            }
-            return Ok(Some(report_filter));
+    if let Some(session_type) = &section_config.section_type
+        && *session_type == SectionType::Filters
+    {
+        for filter in section_config.filters.values() {
+            report_filter = filters::or(report_filter, filters::from(filter)?);
         }
+        return Ok(Some(report_filter));
     }
 
     Ok(None)
 }
 
+fn migrate_storage() {
+    match sqlite_storage::migrate_from_json() {
+        Ok(count) => {
+            println!(
+                "Migrated {} tasks to the SQLite database. Set 'storage = \"sqlite\"' in the \
+                [core] section of the configuration to use it.",
+                count
+            );
+        }
+        Err(error) => fail(&error),
+    }
+}
+
 fn main() {
     env_logger::init();
-    let undo_count = 1;
 
     match config::load_config() {
         Ok(_) => {}
-        Err(msg) => {
-            SimpleTaskTextPrinter.error(&msg);
-            exit(1);
+        Err(error) => fail(&error),
+    }
+
+    // Moving the data in between stores is not an action, since actions don't know
+    // where the tasks are stored
+    if std::env::args().nth(1).is_some_and(|arg| arg == "migrate") {
+        migrate_storage();
+        return;
+    }
+
+    // The interface runs the actions itself, like the command line does
+    if std::env::args().nth(1).is_some_and(|arg| arg == "tui") {
+        match get_config().storage {
+            StorageBackend::Json => run_tui::<JsonStore>(),
+            StorageBackend::Sqlite => run_tui::<SqliteStore>(),
         }
+        return;
     }
 
+    match get_config().storage {
+        StorageBackend::Json => run::<JsonStore>(),
+        StorageBackend::Sqlite => run::<SqliteStore>(),
+    }
+}
+
+fn run_tui<S: Store>() {
+    let filter = std::env::args().skip(2).collect::<Vec<_>>().join(" ");
+    if let Err(error) = tui::run_tui::<S>(filter) {
+        fail(&error);
+    }
+}
+
+fn run<S: Store>() {
+    let contexts = match S::load_contexts() {
+        Ok(res) => res,
+        Err(error) => fail(&error),
+    };
+
     let mut arg_parser = Parser::default();
     for cmd in ActionRegistry::get_parsed_commands() {
         arg_parser.register_command_parser(cmd);
     }
+    arg_parser.set_context(contexts.get_active().map(|(_, context)| context));
 
     let mut command = match arg_parser.parse_command_line_arguments(std::env::args().collect()) {
         Ok(res) => res,
-        Err(msg) => {
-            SimpleTaskTextPrinter.error(&msg);
-            exit(1);
-        }
+        Err(error) => fail(&error),
     };
     let section_filters = match get_section_filters() {
         Ok(res) => res,
-        Err(msg) => {
-            SimpleTaskTextPrinter.error(&msg);
-            exit(1);
-        }
+        Err(error) => fail(&error),
     };
-    if let Some(f) = section_filters {
+    // The sections are part of the report, the commands ignoring it don't need them
+    if let Some(f) = section_filters
+        && !command.ignore_report_filters
+    {
         command.filters = filters::or(command.filters.clone(), f);
     }
 
-    let undos = JsonStore::load_undos(undo_count);
-    let undos_uuid: Vec<uuid::Uuid> = undos
-        .iter()
-        .flat_map(|x| x.tasks.iter().map(|y| *y.get_uuid()))
-        .collect();
-
-    debug!("Loaded {} undos", undos.len());
-    trace!("Undos: {:?}", undos);
-    trace!("Undo uuids: {:?}", undos_uuid);
-
-    let mut props: Option<TaskProperties> = None;
-
-    if !command.arguments_as_filters {
-        match TaskProperties::from(&command.arguments) {
-            Ok(props_from_args) => {
-                props = Some(props_from_args);
-            }
-            Err(msg) => {
-                SimpleTaskTextPrinter.error(&msg);
-                exit(1);
-            }
-        }
+    let command_line = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    if let Err(error) = execute::<S>(
+        &mut command,
+        contexts,
+        &SimpleTaskTextPrinter,
+        &command_line,
+    ) {
+        fail(&error);
     }
-
-    let mut tasks = match JsonStore::load_tasks(Some(&command.filters), props) {
-        Ok(t) => t,
-        Err(msg) => {
-            SimpleTaskTextPrinter.error(&msg);
-            exit(1);
-        }
-    };
-    command.filters.convert_id_to_uuid(tasks.get_id_to_uuid());
-
-    for undo_action in &undos {
-        tasks.set_undos(&undo_action.tasks);
-    }
-
-    let mut action = ActionRegistry::get_action_from_command_parser(&command);
-    action.set_tasks(tasks);
-    action.set_undos(undos);
-    match action.do_action(&SimpleTaskTextPrinter) {
-        Ok(_) => {}
-        Err(msg) => {
-            SimpleTaskTextPrinter.error(&msg);
-            exit(1);
-        }
-    }
-
-    match JsonStore::write_tasks(action.get_tasks()) {
-        Ok(_) => (),
-        Err(msg) => {
-            SimpleTaskTextPrinter.error(&msg);
-            exit(1);
-        }
-    };
-    JsonStore::log_undo(undo_count, action.get_undos().to_owned());
 }
@@ -1,16 +1,21 @@
 use crate::{
     config::get_cli_config,
-    table::{StyledText, Table},
+    plain_table::PlainTable,
+    table::{StyledText, Table, get_terminal_width},
 };
 
 use crate::config::SectionType;
 use bee_core::{
-    Printer,
-    config::ReportConfig,
+    BeeError, Printer,
+    calendar::Calendar,
+    chart::BarChart,
+    config::{ReportConfig, ReportFormat},
     filters,
-    task::{Task, TaskStatus},
+    task::{
+        GroupAggregate, SortKey, Task, TaskStatus, compare_tasks, format_duration, parse_sort_keys,
+    },
 };
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Datelike, Local};
 use colored::{ColoredString, Colorize};
 use indexmap::IndexMap;
 use log::{debug, trace};
@@ -51,7 +56,7 @@ fn format_relative_time(t: DateTime<Local>) -> String {
 pub struct SimpleTaskTextPrinter;
 
 // Return the style that should be applied to a Task
-fn get_style_for_task(task: &Task) -> Result<Option<StyledText>, String> {
+pub(crate) fn get_style_for_task(task: &Task) -> Result<Option<StyledText>, BeeError> {
     let conf = get_cli_config();
 
     for colour_conf in &conf.colour_fields {
@@ -89,13 +94,13 @@ fn get_style_for_task(task: &Task) -> Result<Option<StyledText>, String> {
                     }));
                 }
             }
-            "primary_colour" | "secondary_colour" => {}
+            "primary_colour" | "secondary_colour" | "calendar_due" | "calendar_overdue" => {}
             _ => {
-                return Err(format!(
+                return Err(BeeError::Config(format!(
                     "Unable to colour the output based on the unknown field '{}'.\
                     Please check your configuration.",
                     colour_conf.field
-                ));
+                )));
             }
         }
     }
@@ -103,11 +108,14 @@ fn get_style_for_task(task: &Task) -> Result<Option<StyledText>, String> {
 }
 
 #[derive(Eq, PartialEq, Clone)]
-struct RowTask {
-    task: Task,
-    row: Vec<String>,
+pub(crate) struct RowTask {
+    pub(crate) task: Task,
+    pub(crate) row: Vec<String>,
 }
 
+/// The name of a section of a report and its rows, the unnamed section is ""
+pub(crate) type Section = (String, Vec<RowTask>);
+
 impl PartialOrd for RowTask {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -116,24 +124,28 @@ impl PartialOrd for RowTask {
 
 impl Ord for RowTask {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.task.cmp(&other.task) {
-            Ordering::Equal => unreachable!("Two rows have the same task"),
-            other => other,
-        }
+        self.task
+            .cmp(&other.task)
+            .then_with(|| self.task.get_uuid().cmp(other.task.get_uuid()))
     }
 }
 
 impl Printer for SimpleTaskTextPrinter {
-    fn show_help(&self, help_section_description: &HashMap<String, String>) -> Result<(), String> {
+    fn show_help(
+        &self,
+        help_section_description: &HashMap<String, String>,
+    ) -> Result<(), BeeError> {
         let mut tbl = Table::new(
             &vec!["Action name".to_string(), "Description".to_string()],
             io::stdout(),
-        )?;
+        )
+        .map_err(|e| BeeError::Config(e.to_string()))?;
         for (section, content) in help_section_description.iter() {
             if section == "header" {
                 continue;
             }
-            tbl.add_row(vec![section.to_string(), content.to_string()], None)?;
+            tbl.add_row(vec![section.to_string(), content.to_string()], None)
+                .map_err(|e| BeeError::Config(e.to_string()))?;
         }
         if let Some(header_description) = help_section_description.get("header") {
             println!("{}\n", header_description)
@@ -142,7 +154,57 @@ impl Printer for SimpleTaskTextPrinter {
         Ok(())
     }
 
-    fn print_task_info(&self, task: &Task) -> Result<(), String> {
+    fn print_task_info(&self, task: &Task) -> Result<(), BeeError> {
+        println!("{}", self.format_task_info(task));
+        Ok(())
+    }
+
+    fn print_list_of_tasks(
+        &self,
+        tasks: Vec<&Task>,
+        report_kind: &ReportConfig,
+    ) -> Result<(), BeeError> {
+        let mut writer = io::stdout();
+        self.print_list_of_tasks_impl(tasks, report_kind, &mut writer)
+    }
+
+    fn show_information_message(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn error(&self, message: &str) {
+        println!(
+            "{}",
+            ColoredString::from("Error: ")
+                .bold()
+                .bright_red()
+                .to_string()
+                + message
+        );
+    }
+
+    fn print_raw(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn print_chart(&self, chart: &BarChart) -> Result<(), BeeError> {
+        let mut writer = io::stdout();
+        self.print_chart_impl(chart, get_terminal_width(), &mut writer)
+    }
+
+    fn print_calendar(&self, calendar: &Calendar) -> Result<(), BeeError> {
+        for line in self.format_calendar(calendar) {
+            println!("{}", line);
+        }
+        Ok(())
+    }
+}
+
+// Given a report and tasks, build object containing meta information
+// required for printing out tasks (RowTask)
+impl SimpleTaskTextPrinter {
+    /// The details of a task, as shown by 'info'
+    pub(crate) fn format_task_info(&self, task: &Task) -> String {
         let status = match task.get_status() {
             TaskStatus::Active => task.get_status().to_string().to_uppercase().green(),
             TaskStatus::Pending => task.get_status().to_string().to_uppercase().blue(),
@@ -170,6 +232,14 @@ impl Printer for SimpleTaskTextPrinter {
             output_str += format!("\nTags:\t\t{}", task.get_tags().join(" ").bold()).as_str();
         }
 
+        if let Some(priority) = task.get_priority() {
+            output_str += format!("\nPriority:\t{}", priority.to_string().bold()).as_str();
+        }
+
+        if let Some(external_id) = task.get_external_id() {
+            output_str += format!("\nJira issue:\t{}", external_id.bold()).as_str();
+        }
+
         output_str += "\n";
 
         output_str += format!(
@@ -201,6 +271,25 @@ impl Printer for SimpleTaskTextPrinter {
             .as_str();
         }
 
+        if let Some(recurrence) = task.get_recurrence() {
+            output_str += format!("\nRecurrence:\t{}", recurrence.to_string().bold()).as_str();
+        }
+
+        if !task.get_intervals().is_empty() {
+            output_str += format!(
+                "\nTime tracked:\t{}",
+                format_duration(task.get_time_tracked(Local::now())).bold()
+            )
+            .as_str();
+        }
+
+        if let Some(description) = task.get_description() {
+            output_str += "\n\nDescription:";
+            for line in description.lines() {
+                output_str += format!("\n    {}", line).as_str();
+            }
+        }
+
         if !task.get_annotations().is_empty() {
             output_str += "\n\nAnnotations:";
         }
@@ -255,43 +344,9 @@ impl Printer for SimpleTaskTextPrinter {
             }
         }
 
-        println!("{}", output_str);
-
-        Ok(())
-    }
-
-    fn print_list_of_tasks(
-        &self,
-        tasks: Vec<&Task>,
-        report_kind: &ReportConfig,
-    ) -> Result<(), String> {
-        let mut writer = io::stdout();
-        self.print_list_of_tasks_impl(tasks, report_kind, &mut writer)
-    }
-
-    fn show_information_message(&self, message: &str) {
-        println!("{}", message);
-    }
-
-    fn error(&self, message: &str) {
-        println!(
-            "{}",
-            ColoredString::from("Error: ")
-                .bold()
-                .bright_red()
-                .to_string()
-                + message
-        );
+        output_str
     }
 
-    fn print_raw(&self, message: &str) {
-        println!("{}", message);
-    }
-}
-
-// Given a report and tasks, build object containing meta information
-// required for printing out tasks (RowTask)
-impl SimpleTaskTextPrinter {
     fn build_row_task_objects(
         &self,
         tasks: Vec<&Task>,
@@ -397,13 +452,27 @@ impl SimpleTaskTextPrinter {
     fn split_rows_into_groups(
         &self,
         mut rows: Vec<RowTask>,
+        report_kind: &ReportConfig,
         empty_key: &str,
-    ) -> Result<IndexMap<String, Vec<RowTask>>, String> {
+    ) -> Result<IndexMap<String, Vec<RowTask>>, BeeError> {
         let mut group_on_value = IndexMap::<String, Vec<RowTask>>::new();
 
         let section_config = &get_cli_config().section;
 
-        if let Some(section_type) = &section_config.section_type {
+        if let Some(group_by) = &report_kind.group_by {
+            for row in rows.drain(0..) {
+                let mut group_names = group_by.get_group_names(&row.task);
+                if group_names.is_empty() {
+                    group_names.push(empty_key.to_string());
+                }
+                for group_name in group_names {
+                    group_on_value
+                        .entry(group_name)
+                        .or_default()
+                        .push(row.clone());
+                }
+            }
+        } else if let Some(section_type) = &section_config.section_type {
             match section_type {
                 SectionType::Project => {
                     for row in rows.drain(0..) {
@@ -477,48 +546,81 @@ impl SimpleTaskTextPrinter {
         Ok(group_on_value)
     }
 
-    fn print_list_of_tasks_impl<W: Write>(
+    /// Build the rows of the tasks, sorted and grouped into sections, along with the
+    /// names of the columns that are used. The rows without a section come first, under
+    /// an empty name.
+    pub(crate) fn build_sections(
         &self,
         tasks: Vec<&Task>,
         report_kind: &ReportConfig,
-        writer: &mut W,
-    ) -> Result<(), String> {
+    ) -> Result<(Vec<String>, Vec<Section>), BeeError> {
         let rows: Vec<RowTask> = self.build_row_task_objects(tasks, report_kind);
-
         if rows.is_empty() {
-            return writeln!(writer, "No task to show.").map_err(|e| e.to_string());
+            return Ok((Vec::new(), Vec::new()));
         }
 
+        let sort_keys = parse_sort_keys(&report_kind.sort).map_err(|e| {
+            BeeError::Config(format!(
+                "Unable to sort the tasks of the report. {}",
+                e.get_message()
+            ))
+        })?;
+
         let (rows, header_names) = self.remove_unused_columns(rows, report_kind);
 
         let empty_key = "__empty_value".to_string();
-        let mut group_on_value = self.split_rows_into_groups(rows, &empty_key)?;
-
-        let mut tbl = Table::new(&header_names, writer)?;
-        if let Some(rows) = group_on_value.get_mut(&empty_key) {
-            rows.sort();
-            rows.reverse();
+        let mut group_on_value = self.split_rows_into_groups(rows, report_kind, &empty_key)?;
 
-            tbl.add_section("".to_string());
-
-            for row in rows {
-                tbl.add_row(row.row.clone(), get_style_for_task(&row.task)?)
-                    .unwrap();
-            }
+        let mut sections = Vec::new();
+        if let Some(mut rows) = group_on_value.shift_remove(&empty_key) {
+            sort_rows(&mut rows, &sort_keys);
+            sections.push(("".to_string(), rows));
         }
 
-        for (section_name, rows) in group_on_value.iter_mut() {
-            if section_name == &empty_key {
-                continue;
-            }
+        for (section_name, mut rows) in group_on_value {
             if rows.is_empty() {
                 debug!("Dropping section {} because it is empty!", section_name);
                 continue;
             }
-            rows.sort();
-            rows.reverse();
+            sort_rows(&mut rows, &sort_keys);
+            let section_name = if report_kind.group_by.is_some() {
+                let aggregate = GroupAggregate::new(rows.iter().map(|row| &row.task), Local::now());
+                format!("{}  ({})", section_name, aggregate)
+            } else {
+                section_name
+            };
+            sections.push((section_name, rows));
+        }
+        Ok((header_names, sections))
+    }
+
+    fn print_list_of_tasks_impl<W: Write>(
+        &self,
+        tasks: Vec<&Task>,
+        report_kind: &ReportConfig,
+        writer: &mut W,
+    ) -> Result<(), BeeError> {
+        let (header_names, sections) = self.build_sections(tasks, report_kind)?;
+
+        if report_kind.format != ReportFormat::Table {
+            // Without tasks, all the columns of the report are in the header
+            let header_names = if sections.is_empty() {
+                report_kind.column_names.clone()
+            } else {
+                header_names
+            };
+            return self.print_plain_table(header_names, sections, report_kind.format, writer);
+        }
 
-            tbl.add_section(section_name.to_string());
+        if sections.is_empty() {
+            return writeln!(writer, "No task to show.")
+                .map_err(|e| BeeError::Output(format!("Unable to print the tasks: {}", e)));
+        }
+
+        let mut tbl =
+            Table::new(&header_names, writer).map_err(|e| BeeError::Config(e.to_string()))?;
+        for (section_name, rows) in sections {
+            tbl.add_section(section_name);
 
             for row_task in rows {
                 tbl.add_row(row_task.row.clone(), get_style_for_task(&row_task.task)?)
@@ -530,6 +632,132 @@ impl SimpleTaskTextPrinter {
     }
 }
 
+impl SimpleTaskTextPrinter {
+    /// Print the sections of a report as markdown, CSV or TSV, without colours. Without
+    /// tasks, CSV and TSV only have their header row and markdown is empty.
+    fn print_plain_table<W: Write>(
+        &self,
+        header_names: Vec<String>,
+        sections: Vec<Section>,
+        format: ReportFormat,
+        writer: &mut W,
+    ) -> Result<(), BeeError> {
+        let mut tbl = PlainTable::new(format, &header_names, writer)
+            .map_err(|e| BeeError::Config(e.to_string()))?;
+        for (section_name, rows) in sections {
+            tbl.add_section(section_name);
+            for row_task in rows {
+                tbl.add_row(row_task.row).unwrap();
+            }
+        }
+        tbl.print()
+            .map_err(|e| BeeError::Output(format!("Unable to print the tasks: {}", e)))
+    }
+}
+
+/// Sort the rows of a section on the keys of the report. Without sort keys, the most
+/// urgent tasks come first.
+fn sort_rows(rows: &mut [RowTask], sort_keys: &[SortKey]) {
+    if sort_keys.is_empty() {
+        rows.sort();
+        rows.reverse();
+    } else {
+        rows.sort_by(|lhs, rhs| compare_tasks(sort_keys, &lhs.task, &rhs.task));
+    }
+}
+
+impl SimpleTaskTextPrinter {
+    /// The lines of the calendar, with the days that have tasks due highlighted
+    /// depending on their number and on whether some are overdue
+    pub(crate) fn format_calendar(&self, calendar: &Calendar) -> Vec<String> {
+        let conf = get_cli_config();
+        calendar.render(|day, tasks| {
+            let day_text = format!("{:>2}", day.day());
+            let mut text = if tasks.is_empty() {
+                day_text.normal()
+            } else {
+                let overdue = tasks.iter().any(|task| task.overdue);
+                conf.get_calendar_colours(tasks.len(), overdue)
+                    .apply(&day_text)
+            };
+            if day == calendar.today {
+                text = text.bold().underline();
+            }
+            format!("{} ", text)
+        })
+    }
+
+    /// Print the chart as horizontal bars, the longest taking the whole width. The
+    /// series are coloured with the section palette.
+    fn print_chart_impl<W: Write>(
+        &self,
+        chart: &BarChart,
+        width: usize,
+        writer: &mut W,
+    ) -> Result<(), BeeError> {
+        let write_error =
+            |e: io::Error| BeeError::Output(format!("Unable to print the chart: {}", e));
+        let palette = &get_cli_config().section.colour_palette;
+        let get_colour = |idx: usize| {
+            if palette.is_empty() {
+                (153, 153, 153)
+            } else {
+                palette[idx % palette.len()]
+            }
+        };
+
+        let label_width = chart
+            .rows
+            .iter()
+            .map(|row| row.label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let max_total = chart.get_max_total();
+        let bar_width = width
+            .saturating_sub(label_width + max_total.to_string().len() + 2)
+            .max(1);
+
+        writeln!(writer, "{}", chart.title.bold()).map_err(write_error)?;
+        for row in &chart.rows {
+            let mut bar = String::default();
+            let mut bar_length = 0;
+            let mut total = 0;
+            for (idx, value) in row.values.iter().enumerate() {
+                // Rounding the end of each segment keeps the length of the bar exact
+                total += value;
+                let end = (total * bar_width).div_ceil(max_total.max(1));
+                let (r, g, b) = get_colour(idx);
+                bar += &" "
+                    .repeat(end - bar_length)
+                    .on_truecolor(r, g, b)
+                    .to_string();
+                bar_length = end;
+            }
+            writeln!(
+                writer,
+                "{:<label_width$} {} {}",
+                row.label,
+                bar,
+                total,
+                label_width = label_width
+            )
+            .map_err(write_error)?;
+        }
+
+        let legend: Vec<String> = chart
+            .series
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                let (r, g, b) = get_colour(idx);
+                format!("{} {}", "  ".on_truecolor(r, g, b), name)
+            })
+            .collect();
+        writeln!(writer, "\n{}", legend.join("   ")).map_err(write_error)?;
+        Ok(())
+    }
+}
+
 fn print_value(value: &Value) -> String {
     match value {
         Value::String(s) => s.to_string(),
@@ -56,3 +56,211 @@ fn test_print_table_no_task() {
         "No task to show.\n"
     )
 }
+
+#[test]
+fn test_print_table_csv() {
+    use bee_core::task::{TaskData, TaskProperties};
+
+    let mut data = TaskData::default();
+    for arguments in ["buy milk, eggs", "call \"Bob\""] {
+        data.add_task(
+            &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap();
+    }
+    let report = ReportConfig {
+        columns: vec![
+            "id".to_string(),
+            "summary".to_string(),
+            "project".to_string(),
+        ],
+        column_names: vec![
+            "ID".to_string(),
+            "Summary".to_string(),
+            "Project".to_string(),
+        ],
+        sort: vec!["id+".to_string()],
+        format: ReportFormat::Csv,
+        ..Default::default()
+    };
+
+    // The project column is dropped as none of the tasks has one
+    let mut buffer = Cursor::new(Vec::new());
+    SimpleTaskTextPrinter
+        .print_list_of_tasks_impl(data.get_task_map().values().collect(), &report, &mut buffer)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer.into_inner()).unwrap(),
+        "ID,Summary\n1,\"buy milk, eggs\"\n2,\"call \"\"Bob\"\"\"\n"
+    );
+
+    // Only the header is printed without tasks, with all the columns of the report
+    let mut buffer = Cursor::new(Vec::new());
+    SimpleTaskTextPrinter
+        .print_list_of_tasks_impl(Vec::new(), &report, &mut buffer)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer.into_inner()).unwrap(),
+        "ID,Summary,Project\n"
+    );
+
+    // An empty markdown table would only be a header, nothing is printed
+    let report = ReportConfig {
+        format: ReportFormat::Markdown,
+        ..report
+    };
+    let mut buffer = Cursor::new(Vec::new());
+    SimpleTaskTextPrinter
+        .print_list_of_tasks_impl(Vec::new(), &report, &mut buffer)
+        .unwrap();
+    assert!(buffer.into_inner().is_empty());
+}
+
+#[test]
+fn test_build_sections_sorted() {
+    use bee_core::task::{TaskData, TaskProperties};
+
+    let mut data = TaskData::default();
+    for arguments in ["a", "b due:2030-01-02", "c due:2030-01-01"] {
+        data.add_task(
+            &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap();
+    }
+    let tasks: Vec<&Task> = data.get_task_map().values().collect();
+
+    let report = ReportConfig {
+        sort: vec!["due+".to_string()],
+        ..Default::default()
+    };
+    let (_, sections) = SimpleTaskTextPrinter
+        .build_sections(tasks.clone(), &report)
+        .unwrap();
+    let summaries: Vec<&str> = sections[0]
+        .1
+        .iter()
+        .map(|row| row.task.get_summary())
+        .collect();
+    assert_eq!(summaries, vec!["c", "b", "a"]);
+
+    let report = ReportConfig {
+        sort: vec!["due".to_string(), "colour-".to_string()],
+        ..Default::default()
+    };
+    assert!(
+        SimpleTaskTextPrinter
+            .build_sections(tasks, &report)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_build_sections_grouped() {
+    use bee_core::task::{GroupBy, TaskData, TaskProperties};
+
+    let mut data = TaskData::default();
+    for arguments in [
+        "a +home due:2030-01-02",
+        "b +home +work due:2030-01-01",
+        "c",
+    ] {
+        data.add_task(
+            &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap();
+    }
+    let tasks: Vec<&Task> = data.get_task_map().values().collect();
+
+    let report = ReportConfig {
+        sort: vec!["summary+".to_string()],
+        group_by: Some(GroupBy::Tag),
+        ..Default::default()
+    };
+    let (_, sections) = SimpleTaskTextPrinter
+        .build_sections(tasks, &report)
+        .unwrap();
+    let sections: Vec<(&str, Vec<&str>)> = sections
+        .iter()
+        .map(|(name, rows)| {
+            (
+                name.as_str(),
+                rows.iter().map(|row| row.task.get_summary()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        sections,
+        vec![
+            ("", vec!["c"]),
+            ("home  (2 tasks, first due 2030-01-01)", vec!["a", "b"]),
+            ("work  (1 task, first due 2030-01-01)", vec!["b"]),
+        ]
+    );
+}
+
+#[test]
+fn test_print_chart() {
+    use bee_core::chart::BarChartRow;
+
+    let chart = BarChart {
+        title: "Tasks".to_string(),
+        series: vec!["Added".to_string(), "Completed".to_string()],
+        rows: vec![
+            BarChartRow {
+                label: "2025-W10".to_string(),
+                values: vec![3, 1],
+            },
+            BarChartRow {
+                label: "2025-W11".to_string(),
+                values: vec![1, 1],
+            },
+        ],
+    };
+    let mut buffer = Cursor::new(Vec::new());
+    SimpleTaskTextPrinter
+        .print_chart_impl(&chart, 20, &mut buffer)
+        .unwrap();
+
+    let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+    let output = String::from_utf8(buffer.into_inner()).unwrap();
+    assert_eq!(
+        ansi.replace_all(&output, ""),
+        "Tasks\n2025-W10           4\n2025-W11       2\n\n   Added      Completed\n"
+    );
+}
+
+#[test]
+fn test_format_calendar() {
+    use bee_core::task::{TaskData, TaskProperties};
+    use chrono::TimeZone;
+
+    let mut data = TaskData::default();
+    data.add_task(
+        &TaskProperties::from(&["late due:2025-03-10".to_owned()]).unwrap(),
+        TaskStatus::Pending,
+    )
+    .unwrap();
+    let now = Local.with_ymd_and_hms(2025, 3, 12, 12, 0, 0).unwrap();
+    let calendar = Calendar::new(data.get_task_map().values(), 1, true, now);
+
+    let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+    let lines: Vec<String> = SimpleTaskTextPrinter
+        .format_calendar(&calendar)
+        .iter()
+        .map(|line| ansi.replace_all(line, "").to_string())
+        .collect();
+    // The same grid as without colours, without the markers
+    let plain: Vec<String> = calendar
+        .to_plain_text()
+        .iter()
+        .map(|line| line.replace(['!', '*'], " ").trim_end().to_string())
+        .collect();
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|line| line.trim_end().to_string())
+        .collect();
+    assert_eq!(lines, plain);
+}
@@ -1,3 +1,4 @@
+use bee_core::BeeError;
 use bee_core::config::find_config_file;
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
@@ -8,6 +9,8 @@ use std::{fmt, fs, num::ParseIntError};
 use serde::Deserialize;
 use serde::Deserializer;
 
+use crate::table::StyledText;
+
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct Config {
     #[serde(default = "default_colour_field")]
@@ -19,12 +22,28 @@ pub struct Config {
 }
 
 impl Config {
-    fn validate(&self) -> Result<(), String> {
-        if let Some(section_type) = &self.section.section_type {
-            if section_type == &SectionType::Filters && self.section.filters.is_empty() {
-                return Err("Configuration: Section: The section configuration type is \
+    fn validate(&self) -> Result<(), BeeError> {
+        if let Some(section_type) = &self.section.section_type
+            && section_type == &SectionType::Filters
+            && self.section.filters.is_empty()
+        {
+            return Err(BeeError::Config(
+                "Configuration: Section: The section configuration type is \
                                'filters' but no filter was provided."
-                    .to_string());
+                    .to_string(),
+            ));
+        }
+
+        for colour_conf in &self.colour_fields {
+            if colour_conf.field == "calendar_due"
+                && let Some(value) = &colour_conf.value
+                && value.parse::<usize>().is_err()
+            {
+                return Err(BeeError::Config(format!(
+                    "Configuration: Colours: The value of 'calendar_due' is the number of \
+                     tasks due from which the colour is used, found '{}'.",
+                    value
+                )));
             }
         }
 
@@ -74,13 +93,6 @@ fn default_section_header_bg() -> (u8, u8, u8) {
     (26, 26, 26)
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
-pub struct CoeffientField {
-    pub field: String,
-    pub value: Option<String>,
-    pub coefficient: i64,
-}
-
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct ColourField {
     pub field: String,
@@ -212,8 +224,10 @@ where
 impl Config {
     pub fn get_primary_colour_fg(&self) -> (u8, u8, u8) {
         for c in &self.colour_fields {
-            if c.field == "primary_colour" && c.fg.is_some() {
-                return c.fg.unwrap();
+            if c.field == "primary_colour"
+                && let Some(colour) = c.fg
+            {
+                return colour;
             }
         }
         (220, 220, 220)
@@ -221,8 +235,10 @@ impl Config {
 
     pub fn get_primary_colour_bg(&self) -> (u8, u8, u8) {
         for c in &self.colour_fields {
-            if c.field == "primary_colour" && c.bg.is_some() {
-                return c.bg.unwrap();
+            if c.field == "primary_colour"
+                && let Some(colour) = c.bg
+            {
+                return colour;
             }
         }
         (89, 89, 89)
@@ -230,8 +246,10 @@ impl Config {
 
     pub fn get_secondary_colour_fg(&self) -> (u8, u8, u8) {
         for c in &self.colour_fields {
-            if c.field == "secondary_colour" && c.fg.is_some() {
-                return c.fg.unwrap();
+            if c.field == "secondary_colour"
+                && let Some(colour) = c.fg
+            {
+                return colour;
             }
         }
         (220, 220, 220)
@@ -239,26 +257,74 @@ impl Config {
 
     pub fn get_secondary_colour_bg(&self) -> (u8, u8, u8) {
         for c in &self.colour_fields {
-            if c.field == "secondary_colour" && c.bg.is_some() {
-                return c.bg.unwrap();
+            if c.field == "secondary_colour"
+                && let Some(colour) = c.bg
+            {
+                return colour;
             }
         }
         (38, 38, 38)
     }
 }
 
+impl Config {
+    /// The style of a day of the calendar with `count` tasks due. The
+    /// 'calendar_due' colour with the highest value up to `count` is used, or the
+    /// 'calendar_overdue' one when some of the tasks are overdue.
+    pub fn get_calendar_colours(&self, count: usize, overdue: bool) -> StyledText {
+        if overdue {
+            return match self
+                .colour_fields
+                .iter()
+                .find(|c| c.field == "calendar_overdue")
+            {
+                Some(c) => calendar_style(c.fg, c.bg),
+                None => calendar_style(Some((255, 255, 255)), Some((204, 51, 51))),
+            };
+        }
+
+        let mut colour: Option<(usize, &ColourField)> = None;
+        for c in &self.colour_fields {
+            if c.field != "calendar_due" {
+                continue;
+            }
+            let min_count = c
+                .value
+                .as_ref()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(1);
+            if min_count <= count && colour.is_none_or(|(highest, _)| min_count >= highest) {
+                colour = Some((min_count, c));
+            }
+        }
+        match colour {
+            Some((_, c)) => calendar_style(c.fg, c.bg),
+            None if count >= 3 => calendar_style(Some((0, 0, 0)), Some((255, 217, 102))),
+            None => calendar_style(Some((0, 0, 0)), Some((133, 153, 199))),
+        }
+    }
+}
+
+fn calendar_style(fg: Option<(u8, u8, u8)>, bg: Option<(u8, u8, u8)>) -> StyledText {
+    StyledText {
+        styles: vec![],
+        background_color: bg,
+        foreground_color: fg,
+    }
+}
+
 pub fn get_cli_config() -> &'static Config {
     CONFIG.as_ref().unwrap()
 }
 
 // The code is used as soon as it is first acces, thanks to the Lazy library
 #[allow(dead_code)]
-static CONFIG: Lazy<Result<Config, String>> = Lazy::new(|| match load_config() {
+static CONFIG: Lazy<Result<Config, BeeError>> = Lazy::new(|| match load_config() {
     Ok(config) => Ok(config),
     Err(e) => Err(e),
 });
 
-pub fn load_config() -> Result<Config, String> {
+pub fn load_config() -> Result<Config, BeeError> {
     match find_config_file() {
         Some(file) => {
             let content = match fs::read_to_string(file) {
@@ -274,19 +340,22 @@ pub fn load_config() -> Result<Config, String> {
         None => Ok(Config::default()),
     }
 }
-fn load_config_from_string(content: &str) -> Result<Config, String> {
-    let toml_value: toml::Value =
-        toml::from_str(content).map_err(|e| format!("Unable to read configuration file: {}", e))?;
+fn load_config_from_string(content: &str) -> Result<Config, BeeError> {
+    let toml_value: toml::Value = toml::from_str(content)
+        .map_err(|e| BeeError::Config(format!("Unable to read configuration file: {}", e)))?;
     let config: Config = if let Some(cli_config) = toml_value.get("cli") {
         cli_config.clone().try_into().map_err(|e| {
-            format!(
+            BeeError::Config(format!(
                 "Unable to parse the [cli] section of the configuration. {}",
                 e
-            )
+            ))
         })?
     } else {
         toml::from_str("").map_err(|e| {
-            format!("Unable to read an empty string as valid TOML! err={}", e).to_string()
+            BeeError::Config(format!(
+                "Unable to read an empty string as valid TOML! err={}",
+                e
+            ))
         })?
     };
 
@@ -335,4 +404,48 @@ mod test {
         let result = toml::from_str::<ColourField>(content);
         assert!(result.is_ok(), "Failed to parse: {:?}", result.unwrap_err());
     }
+
+    #[test]
+    fn test_get_calendar_colours() {
+        let config = load_config_from_string(
+            r###"
+[cli]
+[[cli.colours]]
+field = "calendar_due"
+bg = "#000001"
+
+[[cli.colours]]
+field = "calendar_due"
+value = "4"
+bg = "#000004"
+
+[[cli.colours]]
+field = "calendar_overdue"
+fg = "#ff0000"
+"###,
+        )
+        .unwrap();
+        let colours = |count, overdue| {
+            let style = config.get_calendar_colours(count, overdue);
+            (style.foreground_color, style.background_color)
+        };
+        assert_eq!(colours(1, false), (None, Some((0, 0, 1))));
+        assert_eq!(colours(3, false), (None, Some((0, 0, 1))));
+        assert_eq!(colours(5, false), (None, Some((0, 0, 4))));
+        assert_eq!(colours(1, true), (Some((255, 0, 0)), None));
+
+        // The default colours
+        let config = load_config_from_string("").unwrap();
+        let one = config.get_calendar_colours(1, false);
+        let three = config.get_calendar_colours(3, false);
+        assert_true!(one.background_color.is_some());
+        assert_true!(one.background_color != three.background_color);
+
+        assert_true!(
+            load_config_from_string(
+                "[cli]\n[[cli.colours]]\nfield = \"calendar_due\"\nvalue = \"many\"\n"
+            )
+            .is_err()
+        );
+    }
 }
@@ -0,0 +1,158 @@
+use std::io::{self, Write};
+
+use bee_core::config::ReportFormat;
+
+/// A table without colours, printed as markdown, CSV or TSV so that it can be pasted
+/// in other documents. It takes the same rows and sections as the terminal `Table`.
+pub struct PlainTable<W: Write> {
+    format: ReportFormat,
+    columns: Vec<String>,
+    /// The name of each section and its rows, the unnamed section is ""
+    sections: Vec<(String, Vec<Vec<String>>)>,
+    writer: W,
+}
+
+/// Escape a markdown cell, which must fit on a single line
+fn escape_markdown(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Quote a CSV cell when it contains a separator, a quote or a new line
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// TSV has no way of escaping, the tabs and new lines become spaces
+fn escape_tsv(cell: &str) -> String {
+    cell.replace(['\t', '\n', '\r'], " ")
+}
+
+impl<W: Write> PlainTable<W> {
+    pub fn new(
+        format: ReportFormat,
+        column_headers: &[String],
+        writer: W,
+    ) -> Result<PlainTable<W>, &'static str> {
+        if column_headers.is_empty() {
+            return Err("table must have at least one column");
+        }
+        if format == ReportFormat::Table {
+            return Err("the table format is printed with colours by Table");
+        }
+        Ok(PlainTable {
+            format,
+            columns: column_headers.to_owned(),
+            sections: Vec::new(),
+            writer,
+        })
+    }
+
+    pub fn add_section(&mut self, section_name: String) {
+        self.sections.push((section_name, Vec::new()));
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) -> Result<&mut Self, &'static str> {
+        if row.len() != self.columns.len() {
+            return Err("row length does not match column length");
+        }
+        if self.sections.is_empty() {
+            self.sections.push((String::new(), Vec::new()));
+        }
+        self.sections.last_mut().unwrap().1.push(row);
+        Ok(self)
+    }
+
+    fn has_section(&self) -> bool {
+        self.sections.iter().any(|(name, _)| !name.is_empty())
+    }
+
+    pub fn print(&mut self) -> io::Result<()> {
+        match self.format {
+            ReportFormat::Markdown => self.print_markdown(),
+            ReportFormat::Csv => self.print_separated(',', escape_csv),
+            ReportFormat::Tsv => self.print_separated('\t', escape_tsv),
+            ReportFormat::Table => unreachable!("PlainTable is never created for a table"),
+        }
+    }
+
+    /// A markdown table per section, the named ones under a heading
+    fn print_markdown(&mut self) -> io::Result<()> {
+        let header: Vec<String> = self.columns.iter().map(|c| escape_markdown(c)).collect();
+        let sections: Vec<(String, Vec<Vec<String>>)> = self
+            .sections
+            .iter()
+            .map(|(name, rows)| {
+                let rows = rows
+                    .iter()
+                    .map(|row| row.iter().map(|cell| escape_markdown(cell)).collect())
+                    .collect();
+                (name.to_owned(), rows)
+            })
+            .collect();
+
+        // The columns are aligned, to keep the table readable before it is rendered
+        let mut widths: Vec<usize> = header.iter().map(|c| c.chars().count().max(3)).collect();
+        for row in sections.iter().flat_map(|(_, rows)| rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let format_row = |row: &[String]| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+        for (idx, (name, rows)) in sections.iter().enumerate() {
+            if idx > 0 {
+                writeln!(self.writer)?;
+            }
+            if !name.is_empty() {
+                writeln!(self.writer, "### {}", name)?;
+                writeln!(self.writer)?;
+            }
+            writeln!(self.writer, "{}", format_row(&header))?;
+            writeln!(self.writer, "|-{}-|", separator.join("-|-"))?;
+            for row in rows {
+                writeln!(self.writer, "{}", format_row(row))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// A line per row, with the name of its section in the first column if the table
+    /// has sections
+    fn print_separated(&mut self, separator: char, escape: fn(&str) -> String) -> io::Result<()> {
+        let has_section = self.has_section();
+        let separator = separator.to_string();
+
+        let mut header: Vec<String> = self.columns.iter().map(|c| escape(c)).collect();
+        if has_section {
+            header.insert(0, "Section".to_string());
+        }
+        writeln!(self.writer, "{}", header.join(&separator))?;
+
+        for (name, rows) in &self.sections {
+            for row in rows {
+                let mut cells: Vec<String> = row.iter().map(|cell| escape(cell)).collect();
+                if has_section {
+                    cells.insert(0, escape(name));
+                }
+                writeln!(self.writer, "{}", cells.join(&separator))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "plain_table_test.rs"]
+mod plain_table_test;
@@ -0,0 +1,101 @@
+use all_asserts::assert_true;
+
+use super::*;
+
+fn print_table(format: ReportFormat, sections: &[(&str, Vec<[&str; 2]>)]) -> String {
+    let mut output = Vec::new();
+    let mut table = PlainTable::new(
+        format,
+        &["ID".to_string(), "Summary".to_string()],
+        &mut output,
+    )
+    .unwrap();
+    for (name, rows) in sections {
+        table.add_section(name.to_string());
+        for row in rows {
+            table
+                .add_row(row.iter().map(|cell| cell.to_string()).collect())
+                .unwrap();
+        }
+    }
+    table.print().unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn test_new() {
+    assert_true!(PlainTable::new(ReportFormat::Csv, &[], Vec::new()).is_err());
+    assert_true!(PlainTable::new(ReportFormat::Table, &["ID".to_string()], Vec::new()).is_err());
+
+    let mut table = PlainTable::new(ReportFormat::Csv, &["ID".to_string()], Vec::new()).unwrap();
+    assert_true!(
+        table
+            .add_row(vec!["1".to_string(), "a".to_string()])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_print_markdown() {
+    let rows = vec![
+        ["1", "write | review"],
+        ["12", "ship\n  2025-03-10  ask Bob"],
+    ];
+    assert_eq!(
+        print_table(ReportFormat::Markdown, &[("", rows)]),
+        "| ID  | Summary                       |\n\
+        |-----|-------------------------------|\n\
+        | 1   | write \\| review               |\n\
+        | 12  | ship<br>  2025-03-10  ask Bob |\n"
+    );
+
+    assert_eq!(
+        print_table(
+            ReportFormat::Markdown,
+            &[("", vec![["3", "a"]]), ("work  (1 task)", vec![["1", "b"]])]
+        ),
+        "| ID  | Summary |\n\
+        |-----|---------|\n\
+        | 3   | a       |\n\
+        \n\
+        ### work  (1 task)\n\
+        \n\
+        | ID  | Summary |\n\
+        |-----|---------|\n\
+        | 1   | b       |\n"
+    );
+}
+
+#[test]
+fn test_print_csv() {
+    assert_eq!(
+        print_table(
+            ReportFormat::Csv,
+            &[("", vec![["1", "buy milk, eggs"], ["2", "say \"hi\""]])]
+        ),
+        "ID,Summary\n1,\"buy milk, eggs\"\n2,\"say \"\"hi\"\"\"\n"
+    );
+
+    // The sections are a column of their own
+    assert_eq!(
+        print_table(
+            ReportFormat::Csv,
+            &[("", vec![["3", "a"]]), ("work", vec![["1", "b\nc"]])]
+        ),
+        "Section,ID,Summary\n,3,a\nwork,1,\"b\nc\"\n"
+    );
+}
+
+#[test]
+fn test_print_tsv() {
+    assert_eq!(
+        print_table(
+            ReportFormat::Tsv,
+            &[
+                ("home", vec![["1", "a\tb\n  note"]]),
+                ("work", vec![["2", "c"]])
+            ]
+        ),
+        "Section\tID\tSummary\nhome\t1\ta b   note\nwork\t2\tc\n"
+    );
+}
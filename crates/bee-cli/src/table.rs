@@ -455,7 +455,7 @@ fn split_most_whitespaces(input: &str) -> Vec<String> {
     output
 }
 
-fn get_terminal_width() -> usize {
+pub(crate) fn get_terminal_width() -> usize {
     if let Some((Width(w), _)) = terminal_size() {
         w.into()
     } else {
@@ -0,0 +1,566 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use bee_actions::ActionRegistry;
+use bee_core::{
+    BeeError, Printer,
+    config::{ReportConfig, get_config},
+    context::Contexts,
+    filters::{self, Filter},
+    task::Task,
+};
+use bee_storage::{execute::execute, storage::Store};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Wrap},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use uuid::Uuid;
+
+use crate::cli::{RowTask, Section, SimpleTaskTextPrinter, get_style_for_task};
+use crate::config::get_cli_config;
+use crate::table::StyledText;
+
+const KEYS_HELP: &str = "q quit  / filter  s start  S stop  d done  a annotate  m modify  u undo";
+
+/// What the keys typed are used for
+#[derive(Debug, Clone, PartialEq)]
+enum Mode {
+    Browse,
+    /// Typing the filter, the list is updated after each key
+    Filter,
+    /// Typing the arguments of an action on the selected task
+    Prompt {
+        action: &'static str,
+        input: String,
+    },
+}
+
+/// What the event loop has to do after a key was handled
+#[derive(Debug, PartialEq)]
+enum Intent {
+    Quit,
+    Reload,
+    /// Run an action on a task, or on no task for 'undo'
+    Run {
+        action: &'static str,
+        uuid: Option<Uuid>,
+        arguments: Vec<String>,
+    },
+}
+
+/// Collect the messages of the actions, to show them in the status line instead of
+/// writing them over the interface
+#[derive(Default)]
+struct StatusPrinter {
+    messages: RefCell<Vec<String>>,
+    errors: RefCell<Vec<String>>,
+}
+
+impl Printer for StatusPrinter {
+    fn print_list_of_tasks(&self, _: Vec<&Task>, _: &ReportConfig) -> Result<(), BeeError> {
+        Ok(())
+    }
+    fn print_task_info(&self, _: &Task) -> Result<(), BeeError> {
+        Ok(())
+    }
+    fn show_help(&self, _: &HashMap<String, String>) -> Result<(), BeeError> {
+        Ok(())
+    }
+    fn show_information_message(&self, message: &str) {
+        self.messages.borrow_mut().push(message.to_string());
+    }
+    fn error(&self, message: &str) {
+        self.errors.borrow_mut().push(message.to_string());
+    }
+    fn print_raw(&self, message: &str) {
+        self.messages.borrow_mut().push(message.to_string());
+    }
+}
+
+struct App {
+    mode: Mode,
+    filter: String,
+    /// Set while the filter typed can't be parsed, the previous list is kept
+    filter_error: Option<String>,
+    header: Vec<String>,
+    sections: Vec<Section>,
+    /// Index of the selected task, counting the tasks of all the sections
+    selected: usize,
+    status: Option<Result<String, String>>,
+}
+
+impl App {
+    fn new(filter: String) -> App {
+        App {
+            mode: Mode::Browse,
+            filter,
+            filter_error: None,
+            header: Vec::new(),
+            sections: Vec::new(),
+            selected: 0,
+            status: None,
+        }
+    }
+
+    fn get_rows(&self) -> impl Iterator<Item = &RowTask> {
+        self.sections.iter().flat_map(|(_, rows)| rows.iter())
+    }
+
+    fn get_selected_task(&self) -> Option<&Task> {
+        self.get_rows().nth(self.selected).map(|r| &r.task)
+    }
+
+    /// Replace the list, keeping the same task selected when it is still part of it
+    fn set_sections(&mut self, header: Vec<String>, sections: Vec<Section>) {
+        let selected_uuid = self.get_selected_task().map(|t| *t.get_uuid());
+        self.header = header;
+        self.sections = sections;
+        let count = self.get_rows().count();
+        self.selected = selected_uuid
+            .and_then(|uuid| self.get_rows().position(|r| *r.task.get_uuid() == uuid))
+            .unwrap_or(self.selected.min(count.saturating_sub(1)));
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let count = self.get_rows().count();
+        if count == 0 {
+            return;
+        }
+        self.selected = self.selected.saturating_add_signed(offset).min(count - 1);
+    }
+
+    /// An action on the selected task, or an error in the status line if there is none
+    fn run_on_selected(&mut self, action: &'static str, arguments: Vec<String>) -> Option<Intent> {
+        match self.get_selected_task() {
+            Some(task) => Some(Intent::Run {
+                action,
+                uuid: Some(*task.get_uuid()),
+                arguments,
+            }),
+            None => {
+                self.status = Some(Err("No task is selected.".to_string()));
+                None
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Intent> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Intent::Quit);
+        }
+
+        match &mut self.mode {
+            Mode::Browse => self.handle_browse_key(key),
+            Mode::Filter => match key.code {
+                KeyCode::Enter | KeyCode::Esc => {
+                    self.mode = Mode::Browse;
+                    None
+                }
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                    Some(Intent::Reload)
+                }
+                KeyCode::Char(c) => {
+                    self.filter.push(c);
+                    Some(Intent::Reload)
+                }
+                _ => None,
+            },
+            Mode::Prompt { action, input } => match key.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Browse;
+                    None
+                }
+                KeyCode::Enter => {
+                    let action = *action;
+                    let input = input.trim().to_string();
+                    self.mode = Mode::Browse;
+                    if input.is_empty() {
+                        return None;
+                    }
+                    self.run_on_selected(action, vec![input])
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                    None
+                }
+                KeyCode::Char(c) => {
+                    input.push(c);
+                    None
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) -> Option<Intent> {
+        self.status = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Some(Intent::Quit),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.move_selection(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.move_selection(-1);
+                None
+            }
+            KeyCode::PageDown => {
+                self.move_selection(10);
+                None
+            }
+            KeyCode::PageUp => {
+                self.move_selection(-10);
+                None
+            }
+            KeyCode::Char('g') | KeyCode::Home => {
+                self.selected = 0;
+                None
+            }
+            KeyCode::Char('G') | KeyCode::End => {
+                self.move_selection(isize::MAX);
+                None
+            }
+            KeyCode::Char('/') => {
+                self.mode = Mode::Filter;
+                None
+            }
+            KeyCode::Char('s') => self.run_on_selected("start", vec![]),
+            KeyCode::Char('S') => self.run_on_selected("stop", vec![]),
+            KeyCode::Char('d') => self.run_on_selected("done", vec![]),
+            KeyCode::Char('a') | KeyCode::Char('m') if self.get_selected_task().is_none() => {
+                self.status = Some(Err("No task is selected.".to_string()));
+                None
+            }
+            KeyCode::Char('a') => {
+                self.mode = Mode::Prompt {
+                    action: "annotate",
+                    input: String::new(),
+                };
+                None
+            }
+            KeyCode::Char('m') => {
+                self.mode = Mode::Prompt {
+                    action: "modify",
+                    input: String::new(),
+                };
+                None
+            }
+            KeyCode::Char('u') => Some(Intent::Run {
+                action: "undo",
+                uuid: None,
+                arguments: vec![],
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Replace the tabs by spaces up to the next multiple of 8 columns, since the terminal
+/// interface doesn't render them
+fn expand_tabs(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let mut expanded = String::new();
+            for ch in line.chars() {
+                if ch == '\t' {
+                    let len = expanded.graphemes(true).count();
+                    expanded.push_str(&" ".repeat(8 - len % 8));
+                } else {
+                    expanded.push(ch);
+                }
+            }
+            expanded
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Align the columns of the rows on the widest value. Only the first line of a value
+/// is shown, the annotations are in the details.
+fn format_rows(header: &[String], rows: &[&RowTask]) -> (String, Vec<String>) {
+    let first_line = |value: &str| value.lines().next().unwrap_or("").to_string();
+    let mut widths: Vec<usize> = header.iter().map(|h| h.graphemes(true).count()).collect();
+    for row in rows {
+        for (i, value) in row.row.iter().enumerate() {
+            widths[i] = widths[i].max(first_line(value).graphemes(true).count());
+        }
+    }
+
+    let format_line = |values: Vec<String>| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| {
+                let padding = width - value.graphemes(true).count();
+                format!("{}{}", value, " ".repeat(padding))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end()
+            .to_string()
+    };
+    (
+        format_line(header.to_vec()),
+        rows.iter()
+            .map(|row| format_line(row.row.iter().map(|v| first_line(v)).collect()))
+            .collect(),
+    )
+}
+
+fn to_color((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb(r, g, b)
+}
+
+fn to_style(styled: &StyledText) -> Style {
+    let mut style = Style::default();
+    if let Some(colour) = styled.background_color {
+        style = style.bg(to_color(colour));
+    }
+    if let Some(colour) = styled.foreground_color {
+        style = style.fg(to_color(colour));
+    }
+    style
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [main_area, filter_area, status_area] = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list_area, details_area] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .areas(main_area);
+
+    // The rows alternate between the primary and the secondary colours, like the table
+    let conf = get_cli_config();
+    let row_styles = [
+        Style::default()
+            .bg(to_color(conf.get_primary_colour_bg()))
+            .fg(to_color(conf.get_primary_colour_fg())),
+        Style::default()
+            .bg(to_color(conf.get_secondary_colour_bg()))
+            .fg(to_color(conf.get_secondary_colour_fg())),
+    ];
+
+    let rows: Vec<&RowTask> = app.get_rows().collect();
+    let (header, lines) = format_rows(&app.header, &rows);
+    let mut items = Vec::new();
+    let mut selected_item = None;
+    let mut lines = lines.into_iter();
+    let mut task_index = 0;
+    for (section_name, section_rows) in &app.sections {
+        if !section_name.is_empty() {
+            items.push(
+                ListItem::new(Line::from(section_name.to_owned())).style(
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .bg(to_color(conf.section.section_header_bg)),
+                ),
+            );
+        }
+        for row in section_rows {
+            let style = match get_style_for_task(&row.task) {
+                Ok(Some(styled)) => row_styles[task_index % 2].patch(to_style(&styled)),
+                _ => row_styles[task_index % 2],
+            };
+            if task_index == app.selected {
+                selected_item = Some(items.len());
+            }
+            items.push(ListItem::new(lines.next().unwrap_or_default()).style(style));
+            task_index += 1;
+        }
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(format!("Tasks ({})", rows.len()))
+                .title_bottom(header),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD));
+    let mut list_state = ListState::default().with_selected(selected_item);
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+
+    let details = match app.get_selected_task() {
+        Some(task) => expand_tabs(
+            SimpleTaskTextPrinter
+                .format_task_info(task)
+                .trim_start_matches('\n'),
+        ),
+        None => "No task to show.".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(details)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title("Details")),
+        details_area,
+    );
+
+    let mut filter_line = vec![Span::styled(
+        "Filter: ",
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    filter_line.push(Span::raw(app.filter.to_owned()));
+    if app.mode == Mode::Filter {
+        filter_line.push(Span::styled(
+            "_",
+            Style::default().add_modifier(Modifier::SLOW_BLINK),
+        ));
+    }
+    if let Some(error) = &app.filter_error {
+        filter_line.push(Span::styled(
+            format!("  {}", error),
+            Style::default().fg(Color::LightRed),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(filter_line)), filter_area);
+
+    let status = match (&app.mode, &app.status) {
+        (Mode::Prompt { action, input }, _) => Line::from(vec![
+            Span::styled(
+                format!("{} ", action),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("{}_", input)),
+        ]),
+        (_, Some(Ok(message))) => Line::from(message.to_owned()),
+        (_, Some(Err(error))) => {
+            Line::styled(error.to_owned(), Style::default().fg(Color::LightRed))
+        }
+        (_, None) => Line::styled(KEYS_HELP, Style::default().add_modifier(Modifier::DIM)),
+    };
+    frame.render_widget(Paragraph::new(status), status_area);
+}
+
+/// The filter of the list: the one typed, restricted by the default report and the
+/// active context, like 'bee list'
+fn build_filter(
+    filter: &str,
+    report: &ReportConfig,
+    context_filter: Option<Box<dyn Filter>>,
+) -> Result<Box<dyn Filter>, BeeError> {
+    let mut result = filters::and(
+        filters::from(&[filter.to_owned()])?,
+        filters::from(&report.filters)?,
+    );
+    if let Some(context_filter) = context_filter {
+        result = filters::and(result, context_filter);
+    }
+    Ok(result)
+}
+
+fn reload<S: Store>(app: &mut App, context_filter: &Option<Box<dyn Filter>>) {
+    let report = get_config().get_default_report();
+    let result = build_filter(&app.filter, report, context_filter.clone()).and_then(|filter| {
+        let tasks = S::load_tasks(Some(&filter), None)?;
+        SimpleTaskTextPrinter.build_sections(tasks.to_vec(), report)
+    });
+    match result {
+        Ok((header, sections)) => {
+            app.filter_error = None;
+            app.set_sections(header, sections);
+        }
+        Err(e) => app.filter_error = Some(e.to_string()),
+    }
+}
+
+/// Run an action through the same path as the command line, so that it can be undone
+fn run_action<S: Store>(
+    action: &str,
+    uuid: Option<Uuid>,
+    arguments: Vec<String>,
+    contexts: &Contexts,
+) -> Result<String, BeeError> {
+    let mut command = ActionRegistry::get_parsed_commands()
+        .into_iter()
+        .find(|c| c.command == action)
+        .ok_or_else(|| BeeError::InvalidCommand(format!("Unknown action '{}'", action)))?;
+    command.filters = match &uuid {
+        Some(uuid) => filters::from(&[uuid.to_string()])?,
+        None => filters::new_empty(),
+    };
+    command.arguments = arguments;
+    command.report_kind = get_config().get_default_report().clone();
+
+    let command_line = uuid
+        .map(|u| u.to_string())
+        .into_iter()
+        .chain(std::iter::once(action.to_string()))
+        .chain(command.arguments.iter().cloned())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let printer = StatusPrinter::default();
+    execute::<S>(&mut command, contexts.clone(), &printer, &command_line)?;
+
+    if let Some(error) = printer.errors.borrow().last() {
+        return Err(BeeError::InvalidCommand(error.to_owned()));
+    }
+    Ok(printer.messages.borrow().join(" "))
+}
+
+fn event_loop<S: Store>(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    contexts: &Contexts,
+    context_filter: &Option<Box<dyn Filter>>,
+) -> Result<(), BeeError> {
+    reload::<S>(app, context_filter);
+    loop {
+        terminal
+            .draw(|frame| draw(frame, app))
+            .map_err(|e| BeeError::Output(format!("Unable to draw the interface: {}", e)))?;
+
+        let Event::Key(key) =
+            event::read().map_err(|e| BeeError::Storage(format!("Unable to read a key: {}", e)))?
+        else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match app.handle_key(key) {
+            None => {}
+            Some(Intent::Quit) => return Ok(()),
+            Some(Intent::Reload) => reload::<S>(app, context_filter),
+            Some(Intent::Run {
+                action,
+                uuid,
+                arguments,
+            }) => {
+                let result = run_action::<S>(action, uuid, arguments, contexts);
+                app.status = Some(result.map_err(|e| e.to_string()));
+                reload::<S>(app, context_filter);
+            }
+        }
+    }
+}
+
+/// Browse the tasks of the default report matching `filter`, and act on them
+pub fn run_tui<S: Store>(filter: String) -> Result<(), BeeError> {
+    let contexts = S::load_contexts()?;
+    let context_filter = match contexts.get_active() {
+        Some((_, context)) => Some(context.get_filter()?),
+        None => None,
+    };
+
+    // The details are rendered by the interface, without the colours of the terminal
+    colored::control::set_override(false);
+    let mut terminal = ratatui::init();
+    let mut app = App::new(filter);
+    let result = event_loop::<S>(&mut terminal, &mut app, &contexts, &context_filter);
+    ratatui::restore();
+    colored::control::unset_override();
+    result
+}
+
+#[cfg(test)]
+#[path = "tui_test.rs"]
+mod tui_test;
@@ -0,0 +1,194 @@
+use all_asserts::{assert_false, assert_true};
+use bee_core::task::{TaskData, TaskProperties, TaskStatus};
+
+use super::*;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn type_text(app: &mut App, text: &str) -> Vec<Option<Intent>> {
+    text.chars()
+        .map(|c| app.handle_key(key(KeyCode::Char(c))))
+        .collect()
+}
+
+/// An app listing tasks with the given summaries, in a single section
+fn app_with_tasks(summaries: &[&str]) -> App {
+    let mut data = TaskData::default();
+    let rows = summaries
+        .iter()
+        .map(|summary| {
+            let task = data
+                .add_task(
+                    &TaskProperties::from(&[summary.to_string()]).unwrap(),
+                    TaskStatus::Pending,
+                )
+                .unwrap()
+                .clone();
+            RowTask {
+                row: vec![task.get_id().unwrap().to_string(), summary.to_string()],
+                task,
+            }
+        })
+        .collect();
+    let mut app = App::new(String::new());
+    app.set_sections(
+        vec!["ID".to_string(), "Summary".to_string()],
+        vec![("".to_string(), rows)],
+    );
+    app
+}
+
+#[test]
+fn test_expand_tabs() {
+    assert_eq!(expand_tabs("IDs:\t\t1"), "IDs:            1");
+    assert_eq!(expand_tabs("Project:\twork"), "Project:        work");
+    assert_eq!(expand_tabs("a\nb\tc"), "a\nb       c");
+}
+
+#[test]
+fn test_format_rows() {
+    let app = app_with_tasks(&["first task", "second"]);
+    let rows: Vec<&RowTask> = app.get_rows().collect();
+    let (header, lines) = format_rows(&app.header, &rows);
+    assert_eq!(header, "ID Summary");
+    assert_eq!(lines, vec!["1  first task", "2  second"]);
+}
+
+#[test]
+fn test_move_selection() {
+    let mut app = app_with_tasks(&["first", "second", "third"]);
+    assert_eq!(app.get_selected_task().unwrap().get_summary(), "first");
+
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Char('j')));
+    app.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(app.get_selected_task().unwrap().get_summary(), "third");
+
+    app.handle_key(key(KeyCode::Char('k')));
+    assert_eq!(app.get_selected_task().unwrap().get_summary(), "second");
+
+    app.handle_key(key(KeyCode::Char('g')));
+    assert_eq!(app.selected, 0);
+    app.handle_key(key(KeyCode::Char('G')));
+    assert_eq!(app.selected, 2);
+}
+
+#[test]
+fn test_selection_kept_on_reload() {
+    let mut app = app_with_tasks(&["first", "second", "third"]);
+    app.handle_key(key(KeyCode::Char('G')));
+    let selected = app.get_selected_task().unwrap().clone();
+
+    // The selected task moves to the top of the list
+    let mut rows = app.sections[0].1.clone();
+    rows.reverse();
+    app.set_sections(app.header.clone(), vec![("".to_string(), rows)]);
+    assert_eq!(app.selected, 0);
+    assert_eq!(app.get_selected_task(), Some(&selected));
+
+    // The selected task is gone
+    let rows = app.sections[0].1[1..].to_vec();
+    app.set_sections(app.header.clone(), vec![("".to_string(), rows)]);
+    assert_eq!(app.selected, 0);
+    assert_true!(app.get_selected_task().is_some());
+}
+
+#[test]
+fn test_filter_mode() {
+    let mut app = app_with_tasks(&["first"]);
+    assert_eq!(app.handle_key(key(KeyCode::Char('/'))), None);
+    assert_eq!(app.mode, Mode::Filter);
+
+    let intents = type_text(&mut app, "+home");
+    assert_true!(intents.iter().all(|i| *i == Some(Intent::Reload)));
+    assert_eq!(app.filter, "+home");
+
+    // The actions keys are typed in the filter
+    assert_eq!(
+        app.handle_key(key(KeyCode::Backspace)),
+        Some(Intent::Reload)
+    );
+    assert_eq!(app.filter, "+hom");
+    assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+    assert_eq!(app.mode, Mode::Browse);
+}
+
+#[test]
+fn test_action_keys() {
+    let mut app = app_with_tasks(&["first"]);
+    let uuid = *app.get_selected_task().unwrap().get_uuid();
+
+    assert_eq!(
+        app.handle_key(key(KeyCode::Char('d'))),
+        Some(Intent::Run {
+            action: "done",
+            uuid: Some(uuid),
+            arguments: vec![],
+        })
+    );
+    assert_eq!(
+        app.handle_key(key(KeyCode::Char('S'))),
+        Some(Intent::Run {
+            action: "stop",
+            uuid: Some(uuid),
+            arguments: vec![],
+        })
+    );
+    assert_eq!(
+        app.handle_key(key(KeyCode::Char('u'))),
+        Some(Intent::Run {
+            action: "undo",
+            uuid: None,
+            arguments: vec![],
+        })
+    );
+    assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Some(Intent::Quit));
+    assert_eq!(
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Some(Intent::Quit)
+    );
+}
+
+#[test]
+fn test_prompt() {
+    let mut app = app_with_tasks(&["first"]);
+    let uuid = *app.get_selected_task().unwrap().get_uuid();
+
+    app.handle_key(key(KeyCode::Char('a')));
+    assert_true!(matches!(
+        app.mode,
+        Mode::Prompt {
+            action: "annotate",
+            ..
+        }
+    ));
+    assert_true!(type_text(&mut app, "call Bob").iter().all(|i| i.is_none()));
+    assert_eq!(
+        app.handle_key(key(KeyCode::Enter)),
+        Some(Intent::Run {
+            action: "annotate",
+            uuid: Some(uuid),
+            arguments: vec!["call Bob".to_string()],
+        })
+    );
+    assert_eq!(app.mode, Mode::Browse);
+
+    // Cancelled
+    app.handle_key(key(KeyCode::Char('m')));
+    type_text(&mut app, "+urgent");
+    assert_eq!(app.handle_key(key(KeyCode::Esc)), None);
+    assert_eq!(app.mode, Mode::Browse);
+}
+
+#[test]
+fn test_no_task_selected() {
+    let mut app = App::new(String::new());
+    assert_eq!(app.handle_key(key(KeyCode::Char('d'))), None);
+    assert_true!(matches!(app.status, Some(Err(_))));
+    assert_eq!(app.handle_key(key(KeyCode::Char('a'))), None);
+    assert_false!(matches!(app.mode, Mode::Prompt { .. }));
+    // Undo doesn't need a task
+    assert_true!(app.handle_key(key(KeyCode::Char('u'))).is_some());
+}
@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate};
+
+use crate::task::{Task, TaskStatus};
+
+/// A task shown in the calendar, on the day it is due
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarTask {
+    pub id: Option<usize>,
+    pub summary: String,
+    pub due: DateTime<Local>,
+    /// The task is still open and its due date has passed
+    pub overdue: bool,
+}
+
+/// The months of a calendar and the tasks due on their days
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    pub today: NaiveDate,
+    /// The first day of each month shown
+    pub months: Vec<NaiveDate>,
+    /// The tasks due on each day of the months, the earliest first
+    pub days: BTreeMap<NaiveDate, Vec<CalendarTask>>,
+    /// List the tasks of each day under the grid of the months
+    pub agenda: bool,
+}
+
+impl Calendar {
+    /// A calendar of `month_count` months, starting with the month of `now`
+    pub fn new<'a>(
+        tasks: impl Iterator<Item = &'a Task>,
+        month_count: usize,
+        agenda: bool,
+        now: DateTime<Local>,
+    ) -> Calendar {
+        let today = now.date_naive();
+        let first_month = today.with_day(1).unwrap_or(today);
+        let months: Vec<NaiveDate> = (0..month_count)
+            .filter_map(|idx| first_month.checked_add_months(Months::new(idx as u32)))
+            .collect();
+        let end = months
+            .last()
+            .and_then(|month| month.checked_add_months(Months::new(1)))
+            .unwrap_or(first_month);
+
+        let mut days: BTreeMap<NaiveDate, Vec<CalendarTask>> = BTreeMap::new();
+        for task in tasks {
+            let Some(due) = *task.get_date_due() else {
+                continue;
+            };
+            let day = due.date_naive();
+            if day < first_month || day >= end {
+                continue;
+            }
+            let open = matches!(task.get_status(), TaskStatus::Pending | TaskStatus::Active);
+            days.entry(day).or_default().push(CalendarTask {
+                id: task.get_id(),
+                summary: task.get_summary().to_owned(),
+                due,
+                overdue: open && due < now,
+            });
+        }
+        for tasks in days.values_mut() {
+            tasks.sort_by(|a, b| a.due.cmp(&b.due).then(a.summary.cmp(&b.summary)));
+        }
+
+        Calendar {
+            today,
+            months,
+            days,
+            agenda,
+        }
+    }
+
+    /// The weeks of the month, from Monday to Sunday. The days of the other months are
+    /// None.
+    pub fn get_weeks(month: NaiveDate) -> Vec<[Option<NaiveDate>; 7]> {
+        let mut weeks = Vec::new();
+        let mut week = [None; 7];
+        let mut day = month.with_day(1).unwrap_or(month);
+        while day.month() == month.month() {
+            let weekday = day.weekday().num_days_from_monday() as usize;
+            week[weekday] = Some(day);
+            if weekday == 6 {
+                weeks.push(week);
+                week = [None; 7];
+            }
+            match day.checked_add_days(Days::new(1)) {
+                Some(next) => day = next,
+                None => break,
+            }
+        }
+        if week.iter().any(|day| day.is_some()) {
+            weeks.push(week);
+        }
+        weeks
+    }
+
+    /// The lines of the calendar. Each day is formatted by `format_day`, given the
+    /// tasks due that day, and must be 3 characters wide.
+    pub fn render(&self, format_day: impl Fn(NaiveDate, &[CalendarTask]) -> String) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (idx, month) in self.months.iter().enumerate() {
+            if idx > 0 {
+                lines.push(String::default());
+            }
+            lines.push(format!("{:^27}", month.format("%B %Y").to_string()));
+            lines.push("Mo  Tu  We  Th  Fr  Sa  Su".to_string());
+            for week in Calendar::get_weeks(*month) {
+                let cells: Vec<String> = week
+                    .iter()
+                    .map(|day| match day {
+                        Some(day) => format_day(
+                            *day,
+                            self.days.get(day).map(|t| t.as_slice()).unwrap_or_default(),
+                        ),
+                        None => "   ".to_string(),
+                    })
+                    .collect();
+                lines.push(cells.join(" ").trim_end().to_string());
+            }
+        }
+
+        if self.agenda && !self.days.is_empty() {
+            lines.push(String::default());
+            for (day, tasks) in &self.days {
+                lines.push(day.format("%a %Y-%m-%d").to_string());
+                for task in tasks {
+                    lines.push(format!(
+                        "    {:>4}  {}  {}{}",
+                        task.id.map(|id| id.to_string()).unwrap_or_default(),
+                        task.due.format("%H:%M"),
+                        task.summary,
+                        if task.overdue { " (overdue)" } else { "" }
+                    ));
+                }
+            }
+        }
+        lines
+    }
+
+    /// The calendar without colours, the days with due tasks are followed by a '*', or
+    /// a '!' when some are overdue
+    pub fn to_plain_text(&self) -> Vec<String> {
+        self.render(|day, tasks| {
+            let marker = if tasks.iter().any(|task| task.overdue) {
+                '!'
+            } else if tasks.is_empty() {
+                ' '
+            } else {
+                '*'
+            };
+            format!("{:>2}{}", day.day(), marker)
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "calendar_test.rs"]
+mod calendar_test;
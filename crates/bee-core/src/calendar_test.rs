@@ -0,0 +1,103 @@
+use all_asserts::assert_true;
+use chrono::TimeZone;
+
+use super::*;
+use crate::task::{TaskData, TaskProperties};
+
+fn now() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, 3, 12, 12, 0, 0).unwrap()
+}
+
+fn task_data() -> TaskData {
+    let mut data = TaskData::default();
+    for (arguments, status) in [
+        ("late due:2025-03-10", TaskStatus::Pending),
+        ("done late due:2025-03-10", TaskStatus::Completed),
+        ("soon due:2025-03-14", TaskStatus::Pending),
+        ("next month due:2025-04-01", TaskStatus::Pending),
+        ("last month due:2025-02-28", TaskStatus::Pending),
+        ("whenever", TaskStatus::Pending),
+    ] {
+        data.add_task(
+            &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+            status,
+        )
+        .unwrap();
+    }
+    data
+}
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+#[test]
+fn test_get_weeks() {
+    // March 2025 starts on a Saturday and ends on a Monday
+    let weeks = Calendar::get_weeks(date(3, 1));
+    assert_eq!(weeks.len(), 6);
+    assert_eq!(
+        weeks[0],
+        [
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(date(3, 1)),
+            Some(date(3, 2))
+        ]
+    );
+    assert_eq!(weeks[5][0], Some(date(3, 31)));
+    assert_true!(weeks[5][1..].iter().all(|day| day.is_none()));
+}
+
+#[test]
+fn test_new_calendar() {
+    let data = task_data();
+    let calendar = Calendar::new(data.get_task_map().values(), 1, false, now());
+    assert_eq!(calendar.today, date(3, 12));
+    assert_eq!(calendar.months, vec![date(3, 1)]);
+    assert_eq!(
+        calendar.days.keys().copied().collect::<Vec<_>>(),
+        vec![date(3, 10), date(3, 14)]
+    );
+
+    let overdue: Vec<(&str, bool)> = calendar.days[&date(3, 10)]
+        .iter()
+        .map(|task| (task.summary.as_str(), task.overdue))
+        .collect();
+    assert_eq!(overdue, vec![("done late", false), ("late", true)]);
+    assert_true!(!calendar.days[&date(3, 14)][0].overdue);
+
+    let calendar = Calendar::new(data.get_task_map().values(), 2, false, now());
+    assert_eq!(calendar.months, vec![date(3, 1), date(4, 1)]);
+    assert_true!(calendar.days.contains_key(&date(4, 1)));
+}
+
+#[test]
+fn test_to_plain_text() {
+    let data = task_data();
+    let calendar = Calendar::new(data.get_task_map().values(), 1, true, now());
+    let lines = calendar.to_plain_text();
+    assert_eq!(
+        lines[..9],
+        [
+            "        March 2025         ",
+            "Mo  Tu  We  Th  Fr  Sa  Su",
+            "                     1   2",
+            " 3   4   5   6   7   8   9",
+            "10! 11  12  13  14* 15  16",
+            "17  18  19  20  21  22  23",
+            "24  25  26  27  28  29  30",
+            "31",
+            "",
+        ]
+    );
+    assert_true!(lines.contains(&"Mon 2025-03-10".to_string()));
+    assert_true!(
+        lines
+            .iter()
+            .any(|line| line.ends_with("00:00  late (overdue)"))
+    );
+}
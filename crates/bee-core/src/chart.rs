@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+/// A bar chart, with one bar per row stacking the values of each series
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+pub struct BarChart {
+    pub title: String,
+    /// The names of the series, in the order of the values of the rows
+    pub series: Vec<String>,
+    pub rows: Vec<BarChartRow>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct BarChartRow {
+    pub label: String,
+    pub values: Vec<usize>,
+}
+
+impl BarChart {
+    /// The highest total of a row, which takes the whole width of the chart
+    pub fn get_max_total(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| row.values.iter().sum())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The chart without bars, a row per line with the value of each series
+    pub fn to_plain_text(&self) -> Vec<String> {
+        let label_width = self
+            .rows
+            .iter()
+            .map(|row| row.label.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = vec![self.title.to_owned()];
+        for row in &self.rows {
+            let values: Vec<String> = self
+                .series
+                .iter()
+                .zip(&row.values)
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            lines.push(format!(
+                "{:<width$}  {}",
+                row.label,
+                values.join(", "),
+                width = label_width
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_plain_text() {
+        let chart = BarChart {
+            title: "Tasks".to_string(),
+            series: vec!["Added".to_string(), "Completed".to_string()],
+            rows: vec![
+                BarChartRow {
+                    label: "2025-03".to_string(),
+                    values: vec![3, 1],
+                },
+                BarChartRow {
+                    label: "2025-W11".to_string(),
+                    values: vec![0, 2],
+                },
+            ],
+        };
+        assert_eq!(chart.get_max_total(), 4);
+        assert_eq!(
+            chart.to_plain_text(),
+            vec![
+                "Tasks",
+                "2025-03   Added: 3, Completed: 1",
+                "2025-W11  Added: 0, Completed: 2",
+            ]
+        );
+    }
+}
@@ -1,13 +1,17 @@
 use log::{debug, info};
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
+use crate::BeeError;
+use crate::context::Context;
+use crate::task::GroupBy;
+
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct Config {
     #[serde(default = "default_report_name")]
@@ -20,6 +24,58 @@ pub struct Config {
     #[serde(default)]
     #[serde(rename = "coefficients")]
     pub coefficients: Vec<CoeffientField>,
+
+    #[serde(default)]
+    pub storage: StorageBackend,
+
+    /// How many actions can be undone
+    #[serde(default = "default_undo_depth")]
+    pub undo_depth: usize,
+
+    /// The contexts that can be activated with 'bee context <name>'
+    #[serde(default)]
+    #[serde(rename = "context")]
+    pub contexts: BTreeMap<String, Context>,
+
+    /// The directory of the scripts run before and after the tasks are changed
+    #[serde(default)]
+    hooks_dir: Option<String>,
+
+    /// The Jira server 'bee sync jira' synchronises the tasks with
+    #[serde(default)]
+    pub jira: Option<JiraConfig>,
+}
+
+/// The `[core.jira]` section of the configuration
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct JiraConfig {
+    /// The address of the server, e.g. 'https://jira.example.com'
+    pub url: String,
+
+    /// With a user, the token is sent as its password (Jira Cloud). Without one, it is
+    /// sent as a personal access token (Jira Server and Data Center).
+    #[serde(default)]
+    pub user: Option<String>,
+
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// The issues to synchronise
+    #[serde(default = "default_jira_query")]
+    pub jql: String,
+}
+
+fn default_jira_query() -> String {
+    "assignee = currentUser() AND statusCategory != Done".to_string()
+}
+
+/// Where the tasks and the undo log are stored
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Json,
+    Sqlite,
 }
 
 fn default_report_map() -> HashMap<String, ReportConfig> {
@@ -30,12 +86,21 @@ fn default_report_name() -> String {
     DEFAULT_REPORT_NAME.to_string()
 }
 
+fn default_undo_depth() -> usize {
+    10
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             default_report: default_report_name(),
             report_map: default_report_map(),
             coefficients: Vec::new(),
+            storage: StorageBackend::default(),
+            undo_depth: default_undo_depth(),
+            contexts: BTreeMap::new(),
+            hooks_dir: None,
+            jira: None,
         }
     }
 }
@@ -45,6 +110,13 @@ impl Config {
         self.report_map.get(name)
     }
 
+    /// The hooks are disabled unless a directory is configured
+    pub fn get_hooks_dir(&self) -> Option<PathBuf> {
+        self.hooks_dir
+            .as_ref()
+            .map(|dir| PathBuf::from(shellexpand::tilde(dir).into_owned()))
+    }
+
     pub fn get_default_report(&self) -> &ReportConfig {
         if let Some(report) = self.get_report(&self.default_report) {
             report
@@ -68,6 +140,48 @@ pub struct ReportConfig {
     pub columns: Vec<String>,
     pub column_names: Vec<String>,
     pub default: bool,
+
+    /// The keys the tasks are sorted on, e.g. `["due+", "project-"]`. Without any, the
+    /// most urgent tasks come first.
+    #[serde(default)]
+    pub sort: Vec<String>,
+
+    /// Group the tasks into sections, instead of the sections of the CLI configuration
+    #[serde(default)]
+    pub group_by: Option<GroupBy>,
+
+    /// How the tasks are printed, e.g. `format = "csv"`
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+/// The output of a report. Apart from the table, they have no colours and can be
+/// pasted in other documents.
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// The coloured table of the terminal
+    #[default]
+    Table,
+    /// A GitHub flavoured markdown table
+    Markdown,
+    Csv,
+    Tsv,
+}
+
+impl ReportFormat {
+    pub fn from_string(input: &str) -> Result<ReportFormat, BeeError> {
+        match input.to_lowercase().as_str() {
+            "table" => Ok(ReportFormat::Table),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "csv" => Ok(ReportFormat::Csv),
+            "tsv" => Ok(ReportFormat::Tsv),
+            _ => Err(BeeError::parse(format!(
+                "Invalid format '{}', expected table, markdown, csv or tsv",
+                input
+            ))),
+        }
+    }
 }
 
 impl Default for ReportConfig {
@@ -83,6 +197,9 @@ impl Default for ReportConfig {
                 .iter()
                 .map(|&s| s.to_string())
                 .collect(),
+            sort: Vec::new(),
+            group_by: None,
+            format: ReportFormat::Table,
         }
     }
 }
@@ -93,14 +210,14 @@ pub fn get_config() -> &'static Config {
 
 // The code is used as soon as it is first acces, thanks to the Lazy library
 #[allow(dead_code)]
-static CONFIG: Lazy<Result<Config, String>> = Lazy::new(|| match load_config() {
+static CONFIG: Lazy<Result<Config, BeeError>> = Lazy::new(|| match load_config() {
     Ok(config) => Ok(config),
     Err(e) => Err(e),
 });
 
 const DEFAULT_REPORT_NAME: &str = "__default";
 
-pub fn load_config() -> Result<Config, String> {
+pub fn load_config() -> Result<Config, BeeError> {
     match find_config_file() {
         Some(file) => {
             let content = match fs::read_to_string(file) {
@@ -116,18 +233,20 @@ pub fn load_config() -> Result<Config, String> {
         None => Ok(Config::default()),
     }
 }
-fn load_config_from_string(content: &str) -> Result<Config, String> {
-    let toml_value: toml::Value =
-        toml::from_str(content).map_err(|e| format!("Unable to read configuration file: {}", e))?;
+fn load_config_from_string(content: &str) -> Result<Config, BeeError> {
+    let toml_value: toml::Value = toml::from_str(content)
+        .map_err(|e| BeeError::Config(format!("Unable to read configuration file: {}", e)))?;
     let mut config: Config = if let Some(core_config) = toml_value.get("core") {
         core_config.clone().try_into().map_err(|e| {
-            format!(
+            BeeError::Config(format!(
                 "Unable to parse the [core] section of the configuration. {}",
                 e
-            )
+            ))
         })?
     } else {
-        return Err("Configuration file found but the [core] section is missing.".to_string());
+        return Err(BeeError::Config(
+            "Configuration file found but the [core] section is missing.".to_string(),
+        ));
     };
 
     for (name, report) in &config.report_map {
@@ -162,11 +281,11 @@ pub fn find_config_file() -> Option<PathBuf> {
             Cow::Owned(v) => v.to_string(),
         };
 
-        if let Ok(full_path) = Path::new(&expanded_path).canonicalize() {
-            if full_path.exists() {
-                debug!("Found config file {}", expanded_path);
-                return Some(full_path);
-            }
+        if let Ok(full_path) = Path::new(&expanded_path).canonicalize()
+            && full_path.exists()
+        {
+            debug!("Found config file {}", expanded_path);
+            return Some(full_path);
         }
     }
 
@@ -202,6 +321,129 @@ debug = true
         assert_true!(_result.is_ok());
     }
 
+    #[test]
+    fn test_load_storage_backend() {
+        let config = load_config_from_string("[core]\n").unwrap();
+        assert_eq!(config.storage, StorageBackend::Json);
+
+        let config = load_config_from_string("[core]\nstorage = \"sqlite\"\n").unwrap();
+        assert_eq!(config.storage, StorageBackend::Sqlite);
+
+        assert_true!(load_config_from_string("[core]\nstorage = \"csv\"\n").is_err());
+    }
+
+    #[test]
+    fn test_load_undo_depth() {
+        let config = load_config_from_string("[core]\n").unwrap();
+        assert_eq!(config.undo_depth, 10);
+
+        let config = load_config_from_string("[core]\nundo_depth = 3\n").unwrap();
+        assert_eq!(config.undo_depth, 3);
+    }
+
+    #[test]
+    fn test_load_hooks_dir() {
+        let config = load_config_from_string("[core]\n").unwrap();
+        assert_eq!(config.get_hooks_dir(), None);
+
+        let config = load_config_from_string("[core]\nhooks_dir = \"/etc/bee/hooks\"\n").unwrap();
+        assert_eq!(
+            config.get_hooks_dir(),
+            Some(PathBuf::from("/etc/bee/hooks"))
+        );
+    }
+
+    #[test]
+    fn test_load_jira() {
+        let config = load_config_from_string("[core]\n").unwrap();
+        assert_eq!(config.jira, None);
+
+        let config = load_config_from_string(
+            r#"
+[core]
+[core.jira]
+url = "http://jira.example.com"
+user = "me@example.com"
+token = "secret"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.jira,
+            Some(JiraConfig {
+                url: "http://jira.example.com".to_string(),
+                user: Some("me@example.com".to_string()),
+                token: Some("secret".to_string()),
+                jql: default_jira_query(),
+            })
+        );
+
+        assert_true!(load_config_from_string("[core]\n[core.jira]\nuser = \"me\"\n").is_err());
+    }
+
+    #[test]
+    fn test_load_contexts() {
+        let config = load_config_from_string(
+            r#"
+[core]
+[core.context.work]
+filter = "project:work or +office"
+project = "work"
+
+[core.context.home]
+filter = "+home"
+tags = ["home"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.contexts.len(), 2);
+        assert_eq!(
+            config.contexts["work"],
+            Context {
+                filter: "project:work or +office".to_string(),
+                tags: Vec::new(),
+                project: Some("work".to_string()),
+            }
+        );
+        assert_eq!(config.contexts["home"].tags, vec!["home".to_string()]);
+
+        assert_true!(load_config_from_string("[core]\n[core.context.work]\ntags = []\n").is_err());
+    }
+
+    #[test]
+    fn test_load_report_format() {
+        let report = r#"
+[core]
+[core.report.spreadsheet]
+filters = []
+columns = ["id", "summary"]
+column_names = ["ID", "Summary"]
+default = false
+"#;
+        let config = load_config_from_string(report).unwrap();
+        assert_eq!(
+            config.get_report("spreadsheet").unwrap().format,
+            ReportFormat::Table
+        );
+
+        let config = load_config_from_string(&format!("{}format = \"tsv\"\n", report)).unwrap();
+        assert_eq!(
+            config.get_report("spreadsheet").unwrap().format,
+            ReportFormat::Tsv
+        );
+        assert_true!(load_config_from_string(&format!("{}format = \"pdf\"\n", report)).is_err());
+
+        assert_eq!(
+            ReportFormat::from_string("Markdown").unwrap(),
+            ReportFormat::Markdown
+        );
+        assert_eq!(
+            ReportFormat::from_string("md").unwrap(),
+            ReportFormat::Markdown
+        );
+        assert_true!(ReportFormat::from_string("pdf").is_err());
+    }
+
     #[test]
     fn test_get_default_report_exists() {
         let config = Config::default();
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::BeeError;
+use crate::config::get_config;
+use crate::filters::{self, Filter};
+use crate::task::{Project, TaskProperties};
+
+/// The names that can't be used for a context, since they are the subcommands of the
+/// 'context' action
+pub const RESERVED_CONTEXT_NAMES: [&str; 5] = ["define", "delete", "list", "show", "none"];
+
+/// A named filter, added to the filters of the commands reading the tasks while the
+/// context is active
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct Context {
+    pub filter: String,
+    /// Added to the tasks created while the context is active
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Set on the tasks created while the context is active, unless they have a project
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+impl Context {
+    /// Create a context from a filter given on the command line. When the filter is only
+    /// made of tags and a project, e.g. 'project:work +office', they are used as the
+    /// defaults of the tasks created in the context.
+    pub fn from_filter(values: &[String]) -> Result<Context, BeeError> {
+        let filter = values.join(" ").trim().to_string();
+        if filter.is_empty() {
+            return Err(BeeError::InvalidCommand(
+                "A context needs a filter.".to_string(),
+            ));
+        }
+        filters::from(values)?;
+
+        let mut context = Context {
+            filter,
+            ..Context::default()
+        };
+        if let Ok(props) = TaskProperties::from(values)
+            && props.get_summary().is_none()
+        {
+            context.tags = props.get_tags_add().to_owned().unwrap_or_default();
+            context.project = props
+                .get_project()
+                .to_owned()
+                .flatten()
+                .map(|p| p.get_name().to_owned());
+        }
+        Ok(context)
+    }
+
+    pub fn get_filter(&self) -> Result<Box<dyn Filter>, BeeError> {
+        filters::from(std::slice::from_ref(&self.filter))
+            .map_err(|e| e.with_prefix("Invalid filter for the context: "))
+    }
+
+    /// Add the tags and the project of the context to the properties of a new task
+    pub fn apply_defaults(&self, props: &mut TaskProperties) {
+        let mut tags = props.get_tags_add().to_owned().unwrap_or_default();
+        for tag in &self.tags {
+            if !tags.contains(tag) {
+                tags.push(tag.to_owned());
+            }
+        }
+        if !tags.is_empty() {
+            props.set_tag_add(&tags);
+        }
+        if props.get_project().is_none()
+            && let Some(project) = &self.project
+        {
+            props.set_project(&Some(Project::from(project.to_owned())));
+        }
+    }
+}
+
+/// The contexts defined with the 'context' action, and the active one. The contexts
+/// defined in the configuration file are not part of it.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct Contexts {
+    #[serde(default)]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub definitions: BTreeMap<String, Context>,
+}
+
+impl Contexts {
+    /// Look for a context defined with the 'context' action, then in the configuration
+    pub fn get(&self, name: &str) -> Option<&Context> {
+        self.definitions
+            .get(name)
+            .or_else(|| get_config().contexts.get(name))
+    }
+
+    /// All the contexts, the ones defined with the 'context' action hiding the ones of
+    /// the configuration with the same name
+    pub fn get_all(&self) -> BTreeMap<&str, &Context> {
+        get_config()
+            .contexts
+            .iter()
+            .chain(self.definitions.iter())
+            .map(|(name, context)| (name.as_str(), context))
+            .collect()
+    }
+
+    /// The active context, if it still exists
+    pub fn get_active(&self) -> Option<(&str, &Context)> {
+        let name = self.active.as_ref()?;
+        self.get(name).map(|context| (name.as_str(), context))
+    }
+}
+
+#[cfg(test)]
+#[path = "context_test.rs"]
+mod context_test;
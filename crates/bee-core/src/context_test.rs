@@ -0,0 +1,75 @@
+use all_asserts::assert_true;
+
+use super::*;
+
+fn to_args(value: &str) -> Vec<String> {
+    value.split(' ').map(|s| s.to_owned()).collect()
+}
+
+#[test]
+fn test_from_filter() {
+    let context = Context::from_filter(&to_args("project:work +office")).unwrap();
+    assert_eq!(
+        context,
+        Context {
+            filter: "project:work +office".to_string(),
+            tags: vec!["office".to_string()],
+            project: Some("work".to_string()),
+        }
+    );
+
+    // The defaults can't be guessed from a filter with operators
+    let context = Context::from_filter(&to_args("project:work or +office")).unwrap();
+    assert_true!(context.tags.is_empty());
+    assert_eq!(context.project, None);
+
+    assert_true!(Context::from_filter(&[]).is_err());
+    assert_true!(Context::from_filter(&to_args("project:work.")).is_err());
+}
+
+#[test]
+fn test_apply_defaults() {
+    let context = Context {
+        filter: "+office".to_string(),
+        tags: vec!["office".to_string()],
+        project: Some("work".to_string()),
+    };
+
+    let mut props = TaskProperties::from(&to_args("call Bob +phone")).unwrap();
+    context.apply_defaults(&mut props);
+    assert_eq!(
+        props.get_tags_add(),
+        &Some(vec!["phone".to_string(), "office".to_string()])
+    );
+    assert_eq!(
+        props.get_project(),
+        &Some(Some(Project::from("work".to_string())))
+    );
+
+    // The project given on the command line is kept
+    let mut props = TaskProperties::from(&to_args("call Bob project:perso +office")).unwrap();
+    context.apply_defaults(&mut props);
+    assert_eq!(props.get_tags_add(), &Some(vec!["office".to_string()]));
+    assert_eq!(
+        props.get_project(),
+        &Some(Some(Project::from("perso".to_string())))
+    );
+}
+
+#[test]
+fn test_get_active() {
+    let mut contexts = Contexts::default();
+    assert_eq!(contexts.get_active(), None);
+
+    let context = Context::from_filter(&to_args("+home")).unwrap();
+    contexts
+        .definitions
+        .insert("home".to_string(), context.clone());
+    contexts.active = Some("home".to_string());
+    assert_eq!(contexts.get_active(), Some(("home", &context)));
+    assert_true!(contexts.get_all().contains_key("home"));
+
+    // A context that was deleted is not active anymore
+    contexts.definitions.clear();
+    assert_eq!(contexts.get_active(), None);
+}
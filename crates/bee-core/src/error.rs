@@ -0,0 +1,203 @@
+use std::fmt;
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Where a parse error happened: the text that was parsed and the graphemes at fault
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorLocation {
+    pub input: String,
+    pub span: Range<usize>,
+}
+
+/// The errors of Bee. The message is meant for the user, the kind lets the caller
+/// decide how to handle the error, e.g. to only warn about it or to pick an exit code.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BeeError {
+    /// The input could not be parsed: a filter, the properties of a task, a date...
+    Parse {
+        message: String,
+        location: Option<ErrorLocation>,
+    },
+    /// No task has the given ID or UUID
+    UnknownTask(String),
+    /// The task can't change to the requested status, e.g. starting a completed task
+    InvalidTransition(String),
+    /// The command or its arguments are not valid
+    InvalidCommand(String),
+    /// The tasks, the undo log or another file could not be read or written
+    Storage(String),
+    Config(String),
+    /// A hook rejected the change
+    Hook(String),
+    /// A service the tasks are synchronised with could not be reached or refused a request
+    Remote(String),
+    /// The tasks or a chart could not be written to the output, e.g. a closed pipe
+    Output(String),
+}
+
+impl BeeError {
+    /// A parse error whose position in the input is not known
+    pub fn parse(message: impl Into<String>) -> BeeError {
+        BeeError::Parse {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// A parse error caused by the graphemes `span` of `input`
+    pub fn parse_at(message: impl Into<String>, input: &str, span: Range<usize>) -> BeeError {
+        BeeError::Parse {
+            message: message.into(),
+            location: Some(ErrorLocation {
+                input: input.to_owned(),
+                span,
+            }),
+        }
+    }
+
+    /// The same error, located at `span` of `input` if it is a parse error that was
+    /// not located yet
+    pub fn located_at(self, input: &str, span: Range<usize>) -> BeeError {
+        match self {
+            BeeError::Parse {
+                message,
+                location: None,
+            } => BeeError::parse_at(message, input, span),
+            error => error,
+        }
+    }
+
+    /// The command line with a caret under the graphemes at fault, for the parse errors
+    /// whose location is known. The parsed input is usually part of the command line,
+    /// otherwise (e.g. the filter of a context) the input is shown on its own.
+    pub fn get_diagnostic(&self, command_line: &str) -> Option<String> {
+        let BeeError::Parse {
+            location: Some(location),
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let (line, offset) = match command_line.find(&location.input) {
+            Some(index) if !location.input.is_empty() => {
+                (command_line, command_line[..index].graphemes(true).count())
+            }
+            _ => (location.input.as_str(), 0),
+        };
+        let carets = "^".repeat(location.span.len().max(1));
+        Some(format!(
+            "    {}\n    {}{}",
+            line,
+            " ".repeat(offset + location.span.start),
+            carets
+        ))
+    }
+
+    pub fn get_message(&self) -> &str {
+        match self {
+            BeeError::Parse { message, .. } => message,
+            BeeError::UnknownTask(message)
+            | BeeError::InvalidTransition(message)
+            | BeeError::InvalidCommand(message)
+            | BeeError::Storage(message)
+            | BeeError::Config(message)
+            | BeeError::Hook(message)
+            | BeeError::Remote(message)
+            | BeeError::Output(message) => message,
+        }
+    }
+
+    /// The same error, with a message saying where it happened
+    pub fn with_prefix(self, prefix: &str) -> BeeError {
+        let prefixed = |message: String| format!("{}{}", prefix, message);
+        match self {
+            BeeError::Parse { message, location } => BeeError::Parse {
+                message: prefixed(message),
+                location,
+            },
+            BeeError::UnknownTask(message) => BeeError::UnknownTask(prefixed(message)),
+            BeeError::InvalidTransition(message) => BeeError::InvalidTransition(prefixed(message)),
+            BeeError::InvalidCommand(message) => BeeError::InvalidCommand(prefixed(message)),
+            BeeError::Storage(message) => BeeError::Storage(prefixed(message)),
+            BeeError::Config(message) => BeeError::Config(prefixed(message)),
+            BeeError::Hook(message) => BeeError::Hook(prefixed(message)),
+            BeeError::Remote(message) => BeeError::Remote(prefixed(message)),
+            BeeError::Output(message) => BeeError::Output(prefixed(message)),
+        }
+    }
+}
+
+impl fmt::Display for BeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_message())
+    }
+}
+
+impl std::error::Error for BeeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_prefix() {
+        let error = BeeError::parse_at("unexpected ')'", "a b)", 3..4);
+        assert_eq!(
+            error.with_prefix("Invalid filter: "),
+            BeeError::parse_at("Invalid filter: unexpected ')'", "a b)", 3..4)
+        );
+        assert_eq!(
+            BeeError::Storage("disk full".to_string())
+                .with_prefix("Unable to write the tasks: ")
+                .to_string(),
+            "Unable to write the tasks: disk full"
+        );
+    }
+
+    #[test]
+    fn test_located_at() {
+        assert_eq!(
+            BeeError::parse("unexpected ')'").located_at("a b)", 3..4),
+            BeeError::parse_at("unexpected ')'", "a b)", 3..4)
+        );
+        // The first location is the most precise one
+        assert_eq!(
+            BeeError::parse_at("unexpected ')'", "a b)", 3..4).located_at("a b)", 0..4),
+            BeeError::parse_at("unexpected ')'", "a b)", 3..4)
+        );
+        assert_eq!(
+            BeeError::Storage("disk full".to_string()).located_at("a", 0..1),
+            BeeError::Storage("disk full".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_diagnostic() {
+        let error = BeeError::parse_at("unknown keyword", "projct:work +home", 0..7);
+        assert_eq!(
+            error.get_diagnostic("projct:work +home list").unwrap(),
+            "    projct:work +home list\n    ^^^^^^^"
+        );
+        assert_eq!(
+            error.get_diagnostic("1 modify projct:work +home").unwrap(),
+            "    1 modify projct:work +home\n             ^^^^^^^"
+        );
+        // The input isn't part of the command line, e.g. the filter of a context
+        assert_eq!(
+            error.get_diagnostic("list").unwrap(),
+            "    projct:work +home\n    ^^^^^^^"
+        );
+        // The offsets count graphemes, not bytes
+        let error = BeeError::parse_at("unexpected ')'", "café)", 4..5);
+        assert_eq!(
+            error.get_diagnostic("café) list").unwrap(),
+            "    café) list\n        ^"
+        );
+        // The end of the input is pointed at with a single caret
+        let error = BeeError::parse_at("expected a tag", "+", 1..1);
+        assert_eq!(error.get_diagnostic("+").unwrap(), "    +\n     ^");
+
+        assert_eq!(BeeError::parse("invalid").get_diagnostic("list"), None);
+    }
+}
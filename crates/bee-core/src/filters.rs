@@ -2,8 +2,9 @@ mod filters_impl;
 
 mod parser;
 
+use crate::BeeError;
 use crate::lexer::Lexer;
-use crate::task::Task;
+use crate::task::{Task, TaskData, TaskStatus};
 use parser::FilterParser;
 
 use log::{debug, error};
@@ -15,9 +16,10 @@ use std::{
 use uuid::Uuid;
 
 use filters_impl::{
-    AndFilter, DateCreatedFilter, DateDueFilter, DateEndFilter, DependsOnFilter, FilterKind,
-    FilterKindGetter, OrFilter, ProjectFilter, RootFilter, StatusFilter, StringFilter, TagFilter,
-    TaskIdFilter, UuidFilter, XorFilter,
+    AndFilter, DateComparisonFilter, DateCreatedFilter, DateDueFilter, DateEndFilter,
+    DependsOnFilter, DescriptionFilter, FilterKind, FilterKindGetter, NumberComparisonFilter,
+    OrFilter, PriorityFilter, ProjectFilter, RegexFilter, RootFilter, StatusFilter, StringFilter,
+    TagFilter, TaskIdFilter, TextFilter, TransitiveLinkFilter, UuidFilter, XorFilter,
 };
 
 #[allow(private_bounds)]
@@ -28,6 +30,9 @@ pub trait Filter: CloneFilter + Any + Debug + Display + FilterKindGetter {
     fn as_any(&self) -> &dyn Any;
     fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_>;
     fn convert_id_to_uuid(&mut self, id_to_uuid: &HashMap<usize, Uuid>);
+    /// Compute what depends on the other tasks rather than on the validated task alone.
+    /// Only the filters following links between tasks need it.
+    fn resolve_links(&mut self, _tasks: &TaskData) {}
 }
 
 // Consume @lhs and @rhs to return a new Box<dyn Filter>
@@ -80,7 +85,7 @@ pub fn or(lhs: Box<dyn Filter>, rhs: Box<dyn Filter>) -> Box<dyn Filter> {
     })
 }
 
-pub fn from(values: &[String]) -> Result<Box<dyn Filter>, String> {
+pub fn from(values: &[String]) -> Result<Box<dyn Filter>, BeeError> {
     let lexer = Lexer::new(values.join(" "));
     let mut parser = FilterParser::new(lexer);
     let f = parser.parse_filter()?;
@@ -92,6 +97,102 @@ pub fn new_empty() -> Box<dyn Filter> {
     Default::default()
 }
 
+/// Simplified view of a filter, limited to the task properties a storage backend can
+/// look up without deserialising the tasks.
+///
+/// A condition is a superset of the filter it comes from: every task validated by the
+/// filter matches the condition, but the filter still needs to be applied to the tasks
+/// matching the condition.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterCondition {
+    /// Matches every task
+    Any,
+    Status(TaskStatus),
+    /// Matches the project and all its sub projects
+    Project(String),
+    Tag {
+        name: String,
+        include: bool,
+    },
+    Uuid(Uuid),
+    TaskId(usize),
+    And(Vec<FilterCondition>),
+    Or(Vec<FilterCondition>),
+}
+
+pub fn to_condition(filter: &dyn Filter) -> FilterCondition {
+    let any = filter.as_any();
+    match filter.get_kind() {
+        FilterKind::Status => {
+            let f = any.downcast_ref::<StatusFilter>().unwrap();
+            FilterCondition::Status(f.status.to_owned())
+        }
+        FilterKind::Project => {
+            let f = any.downcast_ref::<ProjectFilter>().unwrap();
+            FilterCondition::Project(f.name.get_name().to_owned())
+        }
+        FilterKind::Tag => {
+            let f = any.downcast_ref::<TagFilter>().unwrap();
+            FilterCondition::Tag {
+                name: f.tag_name.to_owned(),
+                include: f.include,
+            }
+        }
+        FilterKind::Uuid => {
+            let f = any.downcast_ref::<UuidFilter>().unwrap();
+            FilterCondition::Uuid(f.uuid)
+        }
+        FilterKind::TaskId => {
+            let f = any.downcast_ref::<TaskIdFilter>().unwrap();
+            FilterCondition::TaskId(f.id)
+        }
+        FilterKind::And => {
+            let f = any.downcast_ref::<AndFilter>().unwrap();
+            let children: Vec<_> = f
+                .children
+                .iter()
+                .map(|c| to_condition(c.as_ref()))
+                .filter(|c| *c != FilterCondition::Any)
+                .collect();
+            match children.len() {
+                0 => FilterCondition::Any,
+                1 => children.into_iter().next().unwrap(),
+                _ => FilterCondition::And(children),
+            }
+        }
+        FilterKind::Or => {
+            let f = any.downcast_ref::<OrFilter>().unwrap();
+            let children: Vec<_> = f
+                .children
+                .iter()
+                .filter(|c| c.get_kind() != FilterKind::Root)
+                .map(|c| to_condition(c.as_ref()))
+                .collect();
+            if children.is_empty() || children.contains(&FilterCondition::Any) {
+                return FilterCondition::Any;
+            }
+            if children.len() == 1 {
+                return children.into_iter().next().unwrap();
+            }
+            FilterCondition::Or(children)
+        }
+        FilterKind::Root
+        | FilterKind::Xor
+        | FilterKind::String
+        | FilterKind::Description
+        | FilterKind::Text
+        | FilterKind::Regex
+        | FilterKind::NumberComparison
+        | FilterKind::DateComparison
+        | FilterKind::Priority
+        | FilterKind::DependsOn
+        | FilterKind::TransitiveLink
+        | FilterKind::DateEnd
+        | FilterKind::DateCreated
+        | FilterKind::DateDue => FilterCondition::Any,
+    }
+}
+
 #[allow(clippy::borrowed_box)]
 fn downcast_and_compare<T: Filter + PartialEq>(
     self_filter: &Box<dyn Filter>,
@@ -148,12 +249,21 @@ impl PartialEq for Box<dyn Filter> {
             FilterKind::Or => downcast_and_compare::<OrFilter>(self, other),
             FilterKind::Xor => downcast_and_compare::<XorFilter>(self, other),
             FilterKind::String => downcast_and_compare::<StringFilter>(self, other),
+            FilterKind::Description => downcast_and_compare::<DescriptionFilter>(self, other),
+            FilterKind::Text => downcast_and_compare::<TextFilter>(self, other),
+            FilterKind::Regex => downcast_and_compare::<RegexFilter>(self, other),
+            FilterKind::NumberComparison => {
+                downcast_and_compare::<NumberComparisonFilter>(self, other)
+            }
+            FilterKind::DateComparison => downcast_and_compare::<DateComparisonFilter>(self, other),
             FilterKind::Status => downcast_and_compare::<StatusFilter>(self, other),
+            FilterKind::Priority => downcast_and_compare::<PriorityFilter>(self, other),
             FilterKind::Project => downcast_and_compare::<ProjectFilter>(self, other),
             FilterKind::Tag => downcast_and_compare::<TagFilter>(self, other),
             FilterKind::Uuid => downcast_and_compare::<UuidFilter>(self, other),
             FilterKind::TaskId => downcast_and_compare::<TaskIdFilter>(self, other),
             FilterKind::DependsOn => downcast_and_compare::<DependsOnFilter>(self, other),
+            FilterKind::TransitiveLink => downcast_and_compare::<TransitiveLinkFilter>(self, other),
             FilterKind::DateEnd => downcast_and_compare::<DateEndFilter>(self, other),
             FilterKind::DateCreated => downcast_and_compare::<DateCreatedFilter>(self, other),
             FilterKind::DateDue => downcast_and_compare::<DateDueFilter>(self, other),
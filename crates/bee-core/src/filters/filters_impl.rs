@@ -1,12 +1,13 @@
 use chrono::{DateTime, Local};
 use log::{debug, trace, warn};
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::{any::Any, fmt};
 use uuid::Uuid;
 
 use super::{CloneFilter, Filter};
-use crate::task::{Project, Task, TaskStatus};
+use crate::task::{Priority, Project, Task, TaskData, TaskStatus};
 
 #[derive(PartialEq, Debug)]
 pub enum FilterKind {
@@ -14,14 +15,21 @@ pub enum FilterKind {
     Or,
     Root,
     Status,
+    Priority,
     Project,
     DateEnd,
     DateCreated,
     DateDue,
     String,
+    Description,
+    Text,
+    Regex,
+    NumberComparison,
+    DateComparison,
     Tag,
     TaskId,
     DependsOn,
+    TransitiveLink,
     Uuid,
     Xor,
 }
@@ -33,14 +41,21 @@ impl fmt::Display for FilterKind {
             FilterKind::Or => write!(f, "Or"),
             FilterKind::Root => write!(f, "Root"),
             FilterKind::Status => write!(f, "Status"),
+            FilterKind::Priority => write!(f, "Priority"),
             FilterKind::Project => write!(f, "Project"),
             FilterKind::DateEnd => write!(f, "DateEnd"),
             FilterKind::DateCreated => write!(f, "DateCreated"),
             FilterKind::DateDue => write!(f, "DateDue"),
             FilterKind::String => write!(f, "String"),
+            FilterKind::Description => write!(f, "Description"),
+            FilterKind::Text => write!(f, "Text"),
+            FilterKind::Regex => write!(f, "Regex"),
+            FilterKind::NumberComparison => write!(f, "NumberComparison"),
+            FilterKind::DateComparison => write!(f, "DateComparison"),
             FilterKind::Tag => write!(f, "Tag"),
             FilterKind::TaskId => write!(f, "TaskId"),
             FilterKind::DependsOn => write!(f, "DependsOn"),
+            FilterKind::TransitiveLink => write!(f, "TransitiveLink"),
             FilterKind::Uuid => write!(f, "Uuid"),
             FilterKind::Xor => write!(f, "Xor"),
         }
@@ -74,13 +89,20 @@ impl_display_and_debug!(
     RootFilter,
     ProjectFilter,
     StatusFilter,
+    PriorityFilter,
     DateEndFilter,
     DateCreatedFilter,
     DateDueFilter,
     StringFilter,
+    DescriptionFilter,
+    TextFilter,
+    RegexFilter,
+    NumberComparisonFilter,
+    DateComparisonFilter,
     TagFilter,
     TaskIdFilter,
     DependsOnFilter,
+    TransitiveLinkFilter,
     UuidFilter,
     XorFilter
 );
@@ -169,6 +191,12 @@ impl Filter for AndFilter {
         }
     }
 
+    fn resolve_links(&mut self, tasks: &TaskData) {
+        for child in &mut self.children {
+            child.resolve_links(tasks);
+        }
+    }
+
     fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
         Box::new(
             std::iter::once(self as &dyn Filter)
@@ -248,6 +276,12 @@ impl Filter for XorFilter {
         }
     }
 
+    fn resolve_links(&mut self, tasks: &TaskData) {
+        for child in &mut self.children {
+            child.resolve_links(tasks);
+        }
+    }
+
     fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
         Box::new(
             std::iter::once(self as &dyn Filter)
@@ -323,6 +357,12 @@ impl Filter for OrFilter {
         }
     }
 
+    fn resolve_links(&mut self, tasks: &TaskData) {
+        for child in &mut self.children {
+            child.resolve_links(tasks);
+        }
+    }
+
     fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
         Box::new(
             std::iter::once(self as &dyn Filter)
@@ -644,6 +684,516 @@ impl CloneFilter for ProjectFilter {
     }
 }
 
+/// Matches the tasks whose description contains the value, ignoring the case
+#[derive(PartialEq, Deserialize, Serialize)]
+pub struct DescriptionFilter {
+    pub value: String,
+}
+
+#[typetag::serde]
+impl Filter for DescriptionFilter {
+    fn validate_task(&self, task: &Task) -> bool {
+        task.get_description()
+            .as_ref()
+            .is_some_and(|d| d.to_lowercase().contains(&self.value.to_lowercase()))
+    }
+
+    fn add_children(&mut self, _: Box<dyn Filter>) {
+        unreachable!("Trying to add a child to a DescriptionFilter");
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn convert_id_to_uuid(&mut self, _id_to_uuid: &HashMap<usize, Uuid>) {}
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
+        Box::new(std::iter::once(self as &dyn Filter))
+    }
+}
+
+impl FilterKindGetter for DescriptionFilter {
+    fn get_kind(&self) -> FilterKind {
+        FilterKind::Description
+    }
+}
+
+impl DescriptionFilter {
+    fn format_helper(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.get_kind(), &self.value)
+    }
+}
+
+impl CloneFilter for DescriptionFilter {
+    fn clone_box(&self) -> Box<dyn Filter> {
+        Box::new(DescriptionFilter {
+            value: self.value.to_owned(),
+        })
+    }
+}
+
+/// The text fields of a task that can be matched by a `TextFilter` or a `RegexFilter`
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum TextField {
+    Summary,
+    Annotation,
+    Description,
+    Tag,
+}
+
+impl TextField {
+    pub fn from_string(input: &str) -> Option<TextField> {
+        match input {
+            "summary" => Some(TextField::Summary),
+            "annotation" => Some(TextField::Annotation),
+            "description" => Some(TextField::Description),
+            "tag" => Some(TextField::Tag),
+            _ => None,
+        }
+    }
+
+    /// The values of the field for the task. A task has as many values as it has
+    /// annotations or tags.
+    fn get_values<'a>(&self, task: &'a Task) -> Vec<&'a str> {
+        match self {
+            TextField::Summary => vec![task.get_summary()],
+            TextField::Annotation => task
+                .get_annotations()
+                .iter()
+                .map(|a| a.get_value().as_str())
+                .collect(),
+            TextField::Description => task.get_description().as_deref().into_iter().collect(),
+            TextField::Tag => task.get_tags().iter().map(|t| t.as_str()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for TextField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextField::Summary => write!(f, "summary"),
+            TextField::Annotation => write!(f, "annotation"),
+            TextField::Description => write!(f, "description"),
+            TextField::Tag => write!(f, "tag"),
+        }
+    }
+}
+
+/// Whether `word` appears in `text` as a whole word, ignoring the case
+fn contains_word(text: &str, word: &str) -> bool {
+    let text = text.to_lowercase();
+    let word = word.to_lowercase();
+    if word.is_empty() {
+        return false;
+    }
+    let is_boundary = |c: Option<char>| c.is_none_or(|c| !c.is_alphanumeric());
+    text.match_indices(&word).any(|(start, _)| {
+        is_boundary(text[..start].chars().next_back())
+            && is_boundary(text[start + word.len()..].chars().next())
+    })
+}
+
+/// Matches the tasks where a value of the field contains the text, ignoring the case.
+/// With `word`, the text must be a whole word of the value, e.g. 'tag=team' only matches
+/// the tag 'team' and 'summary=fix' doesn't match 'prefix'.
+#[derive(PartialEq, Deserialize, Serialize)]
+pub struct TextFilter {
+    pub field: TextField,
+    pub value: String,
+    pub word: bool,
+}
+
+#[typetag::serde]
+impl Filter for TextFilter {
+    fn validate_task(&self, task: &Task) -> bool {
+        self.field.get_values(task).iter().any(|value| {
+            if self.word {
+                contains_word(value, &self.value)
+            } else {
+                value.to_lowercase().contains(&self.value.to_lowercase())
+            }
+        })
+    }
+
+    fn add_children(&mut self, _: Box<dyn Filter>) {
+        unreachable!("Trying to add a child to a TextFilter");
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn convert_id_to_uuid(&mut self, _id_to_uuid: &HashMap<usize, Uuid>) {}
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
+        Box::new(std::iter::once(self as &dyn Filter))
+    }
+}
+
+impl FilterKindGetter for TextFilter {
+    fn get_kind(&self) -> FilterKind {
+        FilterKind::Text
+    }
+}
+
+impl TextFilter {
+    fn format_helper(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = if self.word { "=" } else { "~" };
+        write!(
+            f,
+            "{}: {}{}{}",
+            self.get_kind(),
+            self.field,
+            operator,
+            &self.value
+        )
+    }
+}
+
+impl CloneFilter for TextFilter {
+    fn clone_box(&self) -> Box<dyn Filter> {
+        Box::new(TextFilter {
+            field: self.field.to_owned(),
+            value: self.value.to_owned(),
+            word: self.word,
+        })
+    }
+}
+
+/// The regular expressions are serialised as their pattern
+mod regex_serde {
+    use regex::Regex;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(regex: &Regex, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(regex.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        super::RegexFilter::build_regex(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Matches the tasks where a value of the field matches the regular expression,
+/// ignoring the case
+#[derive(Deserialize, Serialize)]
+pub struct RegexFilter {
+    pub field: TextField,
+    #[serde(with = "regex_serde")]
+    pub regex: Regex,
+}
+
+impl PartialEq for RegexFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.field == other.field && self.regex.as_str() == other.regex.as_str()
+    }
+}
+
+#[typetag::serde]
+impl Filter for RegexFilter {
+    fn validate_task(&self, task: &Task) -> bool {
+        self.field
+            .get_values(task)
+            .iter()
+            .any(|value| self.regex.is_match(value))
+    }
+
+    fn add_children(&mut self, _: Box<dyn Filter>) {
+        unreachable!("Trying to add a child to a RegexFilter");
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn convert_id_to_uuid(&mut self, _id_to_uuid: &HashMap<usize, Uuid>) {}
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
+        Box::new(std::iter::once(self as &dyn Filter))
+    }
+}
+
+impl FilterKindGetter for RegexFilter {
+    fn get_kind(&self) -> FilterKind {
+        FilterKind::Regex
+    }
+}
+
+impl RegexFilter {
+    pub fn build_regex(pattern: &str) -> Result<Regex, regex::Error> {
+        RegexBuilder::new(pattern).case_insensitive(true).build()
+    }
+
+    fn format_helper(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}~/{}/",
+            self.get_kind(),
+            self.field,
+            self.regex.as_str()
+        )
+    }
+}
+
+impl CloneFilter for RegexFilter {
+    fn clone_box(&self) -> Box<dyn Filter> {
+        Box::new(RegexFilter {
+            field: self.field.to_owned(),
+            regex: self.regex.to_owned(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum ComparisonOperator {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl ComparisonOperator {
+    pub fn from_string(input: &str) -> Option<ComparisonOperator> {
+        match input {
+            "<" => Some(ComparisonOperator::Less),
+            "<=" => Some(ComparisonOperator::LessOrEqual),
+            ">" => Some(ComparisonOperator::Greater),
+            ">=" => Some(ComparisonOperator::GreaterOrEqual),
+            "=" => Some(ComparisonOperator::Equal),
+            _ => None,
+        }
+    }
+
+    /// Whether `lhs <operator> rhs` holds
+    fn compare<T: PartialOrd>(&self, lhs: &T, rhs: &T) -> bool {
+        match self {
+            ComparisonOperator::Less => lhs < rhs,
+            ComparisonOperator::LessOrEqual => lhs <= rhs,
+            ComparisonOperator::Greater => lhs > rhs,
+            ComparisonOperator::GreaterOrEqual => lhs >= rhs,
+            ComparisonOperator::Equal => lhs == rhs,
+        }
+    }
+}
+
+impl fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComparisonOperator::Less => write!(f, "<"),
+            ComparisonOperator::LessOrEqual => write!(f, "<="),
+            ComparisonOperator::Greater => write!(f, ">"),
+            ComparisonOperator::GreaterOrEqual => write!(f, ">="),
+            ComparisonOperator::Equal => write!(f, "="),
+        }
+    }
+}
+
+/// The numeric properties of a task that can be compared to a value
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum NumberField {
+    Urgency,
+    Id,
+    /// The number of annotations
+    Annotations,
+}
+
+impl NumberField {
+    pub fn from_string(input: &str) -> Option<NumberField> {
+        match input {
+            "urgency" => Some(NumberField::Urgency),
+            "id" => Some(NumberField::Id),
+            "annotations" => Some(NumberField::Annotations),
+            _ => None,
+        }
+    }
+
+    fn get_value(&self, task: &Task) -> Option<i64> {
+        match self {
+            NumberField::Urgency => task
+                .get_computed_urgency()
+                .or_else(|| task.clone().get_urgency().ok()),
+            NumberField::Id => task.get_id().map(|id| id as i64),
+            NumberField::Annotations => Some(task.get_annotations().len() as i64),
+        }
+    }
+}
+
+impl fmt::Display for NumberField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberField::Urgency => write!(f, "urgency"),
+            NumberField::Id => write!(f, "id"),
+            NumberField::Annotations => write!(f, "annotations"),
+        }
+    }
+}
+
+/// Matches the tasks whose field compares to the value, e.g. 'urgency>5'. The tasks
+/// without an ID, i.e. completed or deleted, never match a comparison on the ID.
+#[derive(PartialEq, Deserialize, Serialize)]
+pub struct NumberComparisonFilter {
+    pub field: NumberField,
+    pub operator: ComparisonOperator,
+    pub value: i64,
+}
+
+#[typetag::serde]
+impl Filter for NumberComparisonFilter {
+    fn validate_task(&self, task: &Task) -> bool {
+        self.field
+            .get_value(task)
+            .is_some_and(|value| self.operator.compare(&value, &self.value))
+    }
+
+    fn add_children(&mut self, _: Box<dyn Filter>) {
+        unreachable!("Trying to add a child to a NumberComparisonFilter");
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn convert_id_to_uuid(&mut self, _id_to_uuid: &HashMap<usize, Uuid>) {}
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
+        Box::new(std::iter::once(self as &dyn Filter))
+    }
+}
+
+impl FilterKindGetter for NumberComparisonFilter {
+    fn get_kind(&self) -> FilterKind {
+        FilterKind::NumberComparison
+    }
+}
+
+impl NumberComparisonFilter {
+    fn format_helper(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}{}{}",
+            self.get_kind(),
+            self.field,
+            self.operator,
+            self.value
+        )
+    }
+}
+
+impl CloneFilter for NumberComparisonFilter {
+    fn clone_box(&self) -> Box<dyn Filter> {
+        Box::new(NumberComparisonFilter {
+            field: self.field.to_owned(),
+            operator: self.operator,
+            value: self.value,
+        })
+    }
+}
+
+/// The dates of a task that can be compared to a date
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum DateField {
+    Created,
+    Due,
+    End,
+}
+
+impl DateField {
+    pub fn from_string(input: &str) -> Option<DateField> {
+        match input {
+            "created" => Some(DateField::Created),
+            "due" => Some(DateField::Due),
+            "end" => Some(DateField::End),
+            _ => None,
+        }
+    }
+
+    fn get_value(&self, task: &Task) -> Option<DateTime<Local>> {
+        match self {
+            DateField::Created => Some(task.get_date_created().to_owned()),
+            DateField::Due => task.get_date_due().to_owned(),
+            DateField::End => task.get_date_completed().to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for DateField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateField::Created => write!(f, "created"),
+            DateField::Due => write!(f, "due"),
+            DateField::End => write!(f, "end"),
+        }
+    }
+}
+
+/// Matches the tasks whose date compares to the time, e.g. 'due<tomorrow'. An equality
+/// holds for any time of the same day, and the tasks without the date never match.
+#[derive(PartialEq, Deserialize, Serialize)]
+pub struct DateComparisonFilter {
+    pub field: DateField,
+    pub operator: ComparisonOperator,
+    pub time: DateTime<Local>,
+}
+
+#[typetag::serde]
+impl Filter for DateComparisonFilter {
+    fn validate_task(&self, task: &Task) -> bool {
+        let Some(date) = self.field.get_value(task) else {
+            return false;
+        };
+        if self.operator == ComparisonOperator::Equal {
+            return date.date_naive() == self.time.date_naive();
+        }
+        self.operator.compare(&date, &self.time)
+    }
+
+    fn add_children(&mut self, _: Box<dyn Filter>) {
+        unreachable!("Trying to add a child to a DateComparisonFilter");
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn convert_id_to_uuid(&mut self, _id_to_uuid: &HashMap<usize, Uuid>) {}
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
+        Box::new(std::iter::once(self as &dyn Filter))
+    }
+}
+
+impl FilterKindGetter for DateComparisonFilter {
+    fn get_kind(&self) -> FilterKind {
+        FilterKind::DateComparison
+    }
+}
+
+impl DateComparisonFilter {
+    fn format_helper(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}{}{}",
+            self.get_kind(),
+            self.field,
+            self.operator,
+            self.time
+        )
+    }
+}
+
+impl CloneFilter for DateComparisonFilter {
+    fn clone_box(&self) -> Box<dyn Filter> {
+        Box::new(DateComparisonFilter {
+            field: self.field.to_owned(),
+            operator: self.operator,
+            time: self.time.to_owned(),
+        })
+    }
+}
+
 #[derive(PartialEq, Deserialize, Serialize)]
 pub struct StatusFilter {
     pub status: TaskStatus,
@@ -690,6 +1240,68 @@ impl CloneFilter for StatusFilter {
     }
 }
 
+/// Matches the tasks with the given priority. With `above`, matches the tasks with a
+/// higher priority instead. A missing priority matches the tasks without one.
+#[derive(PartialEq, Deserialize, Serialize)]
+pub struct PriorityFilter {
+    pub priority: Option<Priority>,
+    pub above: bool,
+}
+
+#[typetag::serde]
+impl Filter for PriorityFilter {
+    fn validate_task(&self, task: &Task) -> bool {
+        if self.above {
+            // Any priority is higher than no priority
+            return task.get_priority() > &self.priority;
+        }
+        &self.priority == task.get_priority()
+    }
+
+    fn add_children(&mut self, _: Box<dyn Filter>) {
+        unreachable!("Trying to add a child to a PriorityFilter");
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn convert_id_to_uuid(&mut self, _id_to_uuid: &HashMap<usize, Uuid>) {}
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
+        Box::new(std::iter::once(self as &dyn Filter))
+    }
+}
+
+impl FilterKindGetter for PriorityFilter {
+    fn get_kind(&self) -> FilterKind {
+        FilterKind::Priority
+    }
+}
+
+impl PriorityFilter {
+    fn format_helper(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let priority = match &self.priority {
+            Some(p) => p.to_string(),
+            None => "none".to_string(),
+        };
+        if self.above {
+            write!(f, "{}: above {}", self.get_kind(), priority)
+        } else {
+            write!(f, "{}: {}", self.get_kind(), priority)
+        }
+    }
+}
+
+impl CloneFilter for PriorityFilter {
+    fn clone_box(&self) -> Box<dyn Filter> {
+        Box::new(PriorityFilter {
+            priority: self.priority,
+            above: self.above,
+        })
+    }
+}
+
 #[derive(PartialEq, Deserialize, Serialize)]
 pub struct TagFilter {
     pub include: bool,
@@ -850,7 +1462,8 @@ impl Filter for DependsOnFilter {
             return task.depends_on(uuid);
         }
 
-        unreachable!("Trying to validate a DependsOn filter without a UUID");
+        // The ID didn't match any task, so no task depends on it
+        false
     }
 
     fn add_children(&mut self, _: Box<dyn Filter>) {
@@ -921,3 +1534,122 @@ impl CloneFilter for DependsOnFilter {
         })
     }
 }
+
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub enum TransitiveLinkType {
+    /// Matches the tasks depending on the task, directly or through other tasks
+    DependsOn,
+    /// Matches the tasks blocking the task, directly or through other tasks
+    Blocks,
+}
+
+#[derive(PartialEq, Deserialize, Serialize)]
+pub struct TransitiveLinkFilter {
+    pub id: Option<usize>,
+    pub uuid: Option<Uuid>,
+    pub link_type: TransitiveLinkType,
+    /// The tasks linked to `uuid`, only known once the filter is resolved against the
+    /// loaded tasks
+    #[serde(default)]
+    pub linked_uuids: Option<HashSet<Uuid>>,
+}
+
+#[typetag::serde]
+impl Filter for TransitiveLinkFilter {
+    fn validate_task(&self, task: &Task) -> bool {
+        if let Some(linked_uuids) = &self.linked_uuids {
+            return linked_uuids.contains(task.get_uuid());
+        }
+
+        // Without the other tasks, only the direct links can be checked
+        if let Some(uuid) = &self.uuid {
+            return match self.link_type {
+                TransitiveLinkType::DependsOn => task.depends_on(uuid),
+                TransitiveLinkType::Blocks => task.blocks(uuid),
+            };
+        }
+
+        // The ID didn't match any task, so no task is linked to it
+        false
+    }
+
+    fn add_children(&mut self, _: Box<dyn Filter>) {
+        unreachable!("Trying to add a child to a TransitiveLinkFilter");
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn convert_id_to_uuid(&mut self, id_to_uuid: &HashMap<usize, Uuid>) {
+        if self.uuid.is_some() {
+            debug!("TransitiveLinkFilter already has a UUID, no need to update it.");
+            return;
+        }
+
+        if let Some(id) = &self.id {
+            if let Some(uuid) = id_to_uuid.get(id) {
+                self.uuid = Some(uuid.to_owned());
+            } else {
+                warn!(
+                    "Trying to map id {} in TransitiveLinkFilter but couldn't find a matching UUID",
+                    id
+                );
+            }
+        }
+    }
+
+    fn resolve_links(&mut self, tasks: &TaskData) {
+        if let Some(uuid) = &self.uuid {
+            self.linked_uuids = Some(match self.link_type {
+                TransitiveLinkType::DependsOn => tasks.get_transitive_dependents(uuid),
+                TransitiveLinkType::Blocks => tasks.get_transitive_dependencies(uuid),
+            });
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Filter> + '_> {
+        Box::new(std::iter::once(self as &dyn Filter))
+    }
+}
+
+impl FilterKindGetter for TransitiveLinkFilter {
+    fn get_kind(&self) -> FilterKind {
+        FilterKind::TransitiveLink
+    }
+}
+
+impl TransitiveLinkFilter {
+    fn format_helper(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let uuid_string = if let Some(uuid) = &self.uuid {
+            uuid.to_string()
+        } else {
+            "None".to_string()
+        };
+
+        let id_string = if let Some(id) = &self.id {
+            id.to_string()
+        } else {
+            "None".to_string()
+        };
+        write!(
+            f,
+            "{}: {:?} id({}), uuid({})",
+            self.get_kind(),
+            self.link_type,
+            id_string,
+            uuid_string
+        )
+    }
+}
+
+impl CloneFilter for TransitiveLinkFilter {
+    fn clone_box(&self) -> Box<dyn Filter> {
+        Box::new(TransitiveLinkFilter {
+            id: self.id.to_owned(),
+            uuid: self.uuid.to_owned(),
+            link_type: self.link_type.to_owned(),
+            linked_uuids: self.linked_uuids.to_owned(),
+        })
+    }
+}
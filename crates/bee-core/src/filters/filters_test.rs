@@ -1,8 +1,10 @@
 use super::*;
-use crate::task::{Project, TaskData, TaskProperties, TaskStatus};
+use crate::task::{Priority, Project, TaskData, TaskProperties, TaskStatus};
 use all_asserts::{assert_false, assert_true};
-use chrono::{Duration, Local, NaiveTime, TimeZone};
-use filters_test::filters_impl::DateDueFilterType;
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use filters_test::filters_impl::{
+    ComparisonOperator, DateDueFilterType, DateField, NumberField, TextField, TransitiveLinkType,
+};
 
 fn init() {
     let _ = env_logger::builder().is_test(true).try_init();
@@ -257,6 +259,243 @@ fn test_task_matches_status_filter() {
     assert_false!(other_filter.validate_task(&task));
 }
 
+#[test]
+fn test_task_matches_priority_filter() {
+    let mut task_data = TaskData::default();
+    let high = task_data
+        .add_task(
+            &TaskProperties::from(&["foo".to_owned(), "priority:H".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .clone();
+    let low = task_data
+        .add_task(
+            &TaskProperties::from(&["bar".to_owned(), "priority:L".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .clone();
+    let none = task_data
+        .add_task(
+            &TaskProperties::from(&["baz".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .clone();
+
+    let high_filter = PriorityFilter {
+        priority: Some(Priority::High),
+        above: false,
+    };
+    assert_true!(high_filter.validate_task(&high));
+    assert_false!(high_filter.validate_task(&low));
+    assert_false!(high_filter.validate_task(&none));
+
+    let above_low_filter = PriorityFilter {
+        priority: Some(Priority::Low),
+        above: true,
+    };
+    assert_true!(above_low_filter.validate_task(&high));
+    assert_false!(above_low_filter.validate_task(&low));
+    assert_false!(above_low_filter.validate_task(&none));
+
+    let no_priority_filter = PriorityFilter {
+        priority: None,
+        above: false,
+    };
+    assert_false!(no_priority_filter.validate_task(&high));
+    assert_true!(no_priority_filter.validate_task(&none));
+
+    let any_priority_filter = PriorityFilter {
+        priority: None,
+        above: true,
+    };
+    assert_true!(any_priority_filter.validate_task(&low));
+    assert_false!(any_priority_filter.validate_task(&none));
+}
+
+#[test]
+fn test_task_matches_description_filter() {
+    let mut task_data = TaskData::default();
+    let mut props = TaskProperties::from(&["foo".to_owned()]).unwrap();
+    props.set_description(&Some("Ask Bob for the\nQuarterly figures".to_owned()));
+    let described = task_data
+        .add_task(&props, TaskStatus::Pending)
+        .unwrap()
+        .clone();
+    let other = task_data
+        .add_task(
+            &TaskProperties::from(&["figures".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .clone();
+
+    let filter = DescriptionFilter {
+        value: "quarterly".to_owned(),
+    };
+    assert_true!(filter.validate_task(&described));
+    // The summary is not part of the description
+    let filter = DescriptionFilter {
+        value: "figures".to_owned(),
+    };
+    assert_true!(filter.validate_task(&described));
+    assert_false!(filter.validate_task(&other));
+}
+
+#[test]
+fn test_task_matches_text_filter() {
+    let mut task_data = TaskData::default();
+    let mut task = task_data
+        .add_task(
+            &TaskProperties::from(&["fix the prefix".to_owned(), "+teaminfra".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .clone();
+    let mut annotate = TaskProperties::default();
+    annotate.set_annotate("Waiting on Alice".to_owned());
+    task.apply(&annotate).unwrap();
+
+    let matches = |field: TextField, value: &str, word: bool| {
+        let filter = TextFilter {
+            field,
+            value: value.to_owned(),
+            word,
+        };
+        filter.validate_task(&task)
+    };
+    assert_true!(matches(TextField::Summary, "PREFIX", false));
+    assert_true!(matches(TextField::Summary, "fix", true));
+    assert_false!(matches(TextField::Summary, "pre", true));
+    assert_true!(matches(TextField::Tag, "team", false));
+    assert_false!(matches(TextField::Tag, "team", true));
+    assert_true!(matches(TextField::Tag, "teaminfra", true));
+    assert_true!(matches(TextField::Annotation, "alice", true));
+    assert_false!(matches(TextField::Description, "alice", false));
+}
+
+#[test]
+fn test_task_matches_regex_filter() {
+    let mut task_data = TaskData::default();
+    let task = task_data
+        .add_task(
+            &TaskProperties::from(&["Fix bug 42".to_owned(), "+teaminfra".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .clone();
+
+    let matches = |field: TextField, pattern: &str| {
+        let filter = RegexFilter {
+            field,
+            regex: RegexFilter::build_regex(pattern).unwrap(),
+        };
+        filter.validate_task(&task)
+    };
+    assert_true!(matches(TextField::Summary, "^fix bug [0-9]+$"));
+    assert_false!(matches(TextField::Summary, "^bug"));
+    assert_true!(matches(TextField::Tag, "^team"));
+    assert_false!(matches(TextField::Annotation, ".*"));
+
+    let filter: Box<dyn Filter> = Box::new(RegexFilter {
+        field: TextField::Tag,
+        regex: RegexFilter::build_regex("^team-").unwrap(),
+    });
+    let serialised = serde_json::to_string(&filter).unwrap();
+    assert_eq!(
+        serialised,
+        "{\"type\":\"RegexFilter\",\"value\":{\"field\":\"Tag\",\"regex\":\"^team-\"}}"
+    );
+    let deserialised: Box<dyn Filter> = serde_json::from_str(&serialised).unwrap();
+    assert_eq!(&deserialised, &filter);
+    assert_eq!(filter.to_string(), "Regex: tag~/^team-/");
+}
+
+#[test]
+fn test_task_matches_comparison_filter() {
+    let mut task_data = TaskData::default();
+    task_data
+        .add_task(
+            &TaskProperties::from(&["first".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap();
+    let mut task = task_data
+        .add_task(
+            &TaskProperties::from(&["second due:tomorrow".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .clone();
+    let mut annotate = TaskProperties::default();
+    annotate.set_annotate("a note".to_owned());
+    task.apply(&annotate).unwrap();
+
+    let number = |field: NumberField, operator: ComparisonOperator, value: i64| {
+        NumberComparisonFilter {
+            field,
+            operator,
+            value,
+        }
+        .validate_task(&task)
+    };
+    assert_true!(number(NumberField::Id, ComparisonOperator::Equal, 2));
+    assert_true!(number(
+        NumberField::Id,
+        ComparisonOperator::GreaterOrEqual,
+        2
+    ));
+    assert_false!(number(NumberField::Id, ComparisonOperator::Less, 2));
+    assert_true!(number(
+        NumberField::Annotations,
+        ComparisonOperator::Greater,
+        0
+    ));
+    assert_false!(number(
+        NumberField::Annotations,
+        ComparisonOperator::Greater,
+        1
+    ));
+    let urgency = task.clone().get_urgency().unwrap();
+    assert_true!(number(
+        NumberField::Urgency,
+        ComparisonOperator::LessOrEqual,
+        urgency
+    ));
+    assert_false!(number(
+        NumberField::Urgency,
+        ComparisonOperator::Greater,
+        urgency
+    ));
+
+    let date = |field: DateField, operator: ComparisonOperator, time: DateTime<Local>| {
+        DateComparisonFilter {
+            field,
+            operator,
+            time,
+        }
+        .validate_task(&task)
+    };
+    let now = Local::now();
+    assert_true!(date(DateField::Due, ComparisonOperator::Greater, now));
+    assert_false!(date(DateField::Due, ComparisonOperator::Less, now));
+    // An equality holds for the whole day
+    assert_true!(date(
+        DateField::Due,
+        ComparisonOperator::Equal,
+        now + Duration::days(1)
+    ));
+    assert_true!(date(
+        DateField::Created,
+        ComparisonOperator::LessOrEqual,
+        now
+    ));
+    // The task isn't completed, it has no end date to compare
+    assert_false!(date(DateField::End, ComparisonOperator::Less, now));
+}
+
 #[test]
 fn test_filter_depends_on() {
     let depends_uuid = Uuid::new_v4();
@@ -336,6 +575,17 @@ fn test_depends_on_convert_id_to_uuid_id_not_found() {
     filter.convert_id_to_uuid(&id_to_uuid);
 
     assert_eq!(filter.uuid, None);
+    assert_false!(filter.validate_task(&Task::default()));
+
+    let mut filter = TransitiveLinkFilter {
+        id: Some(id),
+        uuid: None,
+        link_type: TransitiveLinkType::DependsOn,
+        linked_uuids: None,
+    };
+    filter.convert_id_to_uuid(&id_to_uuid);
+    filter.resolve_links(&TaskData::default());
+    assert_false!(filter.validate_task(&Task::default()));
 }
 
 #[test]
@@ -456,6 +706,46 @@ fn test_validate_task() {
     let f_id = TaskIdFilter { id: 0 };
     assert_false!(f_id.validate_task(&t));
 
-    t.done();
+    t.done().unwrap();
     assert_false!(f_id.validate_task(&t));
 }
+
+#[test]
+fn test_to_condition() {
+    let f = from(&["status:pending".to_owned()]).unwrap();
+    assert_eq!(
+        to_condition(f.as_ref()),
+        FilterCondition::Status(TaskStatus::Pending)
+    );
+
+    let f = from(&["(status:pending or status:active) and +home proj:work".to_owned()]).unwrap();
+    assert_eq!(
+        to_condition(f.as_ref()),
+        FilterCondition::And(vec![
+            FilterCondition::Or(vec![
+                FilterCondition::Status(TaskStatus::Pending),
+                FilterCondition::Status(TaskStatus::Active),
+            ]),
+            FilterCondition::And(vec![
+                FilterCondition::Tag {
+                    name: "home".to_owned(),
+                    include: true
+                },
+                FilterCondition::Project("work".to_owned()),
+            ]),
+        ])
+    );
+
+    // Filters that can't be expressed as a condition are ignored in an 'and'...
+    let f = from(&["status:pending and foo".to_owned()]).unwrap();
+    assert_eq!(
+        to_condition(f.as_ref()),
+        FilterCondition::Status(TaskStatus::Pending)
+    );
+
+    // ...but make the whole 'or' match anything
+    let f = from(&["status:pending or foo".to_owned()]).unwrap();
+    assert_eq!(to_condition(f.as_ref()), FilterCondition::Any);
+
+    assert_eq!(to_condition(new_empty().as_ref()), FilterCondition::Any);
+}
@@ -1,12 +1,15 @@
 use uuid::Uuid;
 
+use crate::BeeError;
 use crate::lexer::Lexer;
-use crate::task::{Project, TaskStatus};
+use crate::task::{Priority, Project, TaskStatus};
 
 use super::filters_impl::{
-    AndFilter, DateCreatedFilter, DateDueFilter, DateDueFilterType, DateEndFilter, DependsOnFilter,
-    FilterKind, OrFilter, ProjectFilter, StatusFilter, StringFilter, TagFilter, TaskIdFilter,
-    UuidFilter, XorFilter,
+    AndFilter, ComparisonOperator, DateComparisonFilter, DateCreatedFilter, DateDueFilter,
+    DateDueFilterType, DateEndFilter, DateField, DependsOnFilter, DescriptionFilter, FilterKind,
+    NumberComparisonFilter, NumberField, OrFilter, PriorityFilter, ProjectFilter, RegexFilter,
+    StatusFilter, StringFilter, TagFilter, TaskIdFilter, TextField, TextFilter,
+    TransitiveLinkFilter, TransitiveLinkType, UuidFilter, XorFilter,
 };
 use super::{Filter, new_empty};
 
@@ -102,9 +105,61 @@ impl FilterParser {
         parser
     }
 
-    pub fn parse_filter(&mut self) -> Result<Box<dyn Filter>, String> {
+    /// Build the filter of the current '/regex/' token, matched against the field
+    fn read_regex_filter(&mut self, field: TextField) -> Result<Box<dyn Filter>, BeeError> {
+        let literal = self.current_token.literal.to_owned();
+        let pattern = literal[1..literal.len() - 1].replace("\\/", "/");
+        match RegexFilter::build_regex(&pattern) {
+            Ok(regex) => {
+                self.next_token();
+                Ok(Box::new(RegexFilter { field, regex }))
+            }
+            Err(_) => Err(self.error_at(
+                self.current_token.span.to_owned(),
+                format!(
+                    "Invalid regular expression {}",
+                    self.current_token.describe()
+                ),
+            )),
+        }
+    }
+
+    /// Read a number, that can be negative, e.g. the value of 'urgency>-2'
+    fn read_number(&mut self) -> Option<i64> {
+        let negative = self.current_token.token_type == TokenType::TagMinusPrefix
+            && self.peek_token.token_type == TokenType::Int;
+        if negative {
+            self.next_token();
+        }
+        if self.current_token.token_type != TokenType::Int {
+            return None;
+        }
+        let value = self.current_token.literal.parse::<i64>().ok()?;
+        self.next_token();
+        Some(if negative { -value } else { value })
+    }
+
+    /// Read the text following a '<field>~' or '<field>=' keyword, up to the next blank
+    fn read_text_value(&mut self) -> String {
+        let mut value = String::default();
+        while !matches!(
+            self.current_token.token_type,
+            TokenType::Blank
+                | TokenType::Eof
+                | TokenType::LeftParenthesis
+                | TokenType::RightParenthesis
+        ) {
+            value.push_str(&self.current_token.literal);
+            self.next_token();
+        }
+        value
+    }
+
+    pub fn parse_filter(&mut self) -> Result<Box<dyn Filter>, BeeError> {
         let mut has_only_ids = true;
-        let filter = self.parse_filter_impl(&0, ScopeOperator::None, &mut has_only_ids)?;
+        let filter = self
+            .parse_filter_impl(&0, ScopeOperator::None, &mut has_only_ids)
+            .map_err(|e| self.suggest_keyword_in_error(e))?;
 
         if has_only_ids {
             let values: Vec<Box<dyn Filter>> = filter
@@ -126,7 +181,7 @@ impl FilterParser {
         parenthesis_scope: &usize,
         scope_operator: ScopeOperator,
         has_only_ids: &mut bool,
-    ) -> Result<Box<dyn Filter>, String> {
+    ) -> Result<Box<dyn Filter>, BeeError> {
         let mut filter: Box<dyn Filter> = new_empty();
         let err_msg_prefix: String = "could not parse the filter expression. ".to_string();
 
@@ -144,7 +199,17 @@ impl FilterParser {
                     *has_only_ids = false;
                     match self.peek_token.token_type {
                         TokenType::OperatorOr | TokenType::OperatorAnd | TokenType::OperatorXor => {
-                            return Err(err_msg_prefix + "Found two operators one after the other");
+                            let (current, peek) =
+                                (self.current_token.to_owned(), self.peek_token.to_owned());
+                            return Err(self.error_at(
+                                peek.span.to_owned(),
+                                err_msg_prefix
+                                    + &format!(
+                                        "Expected a filter after {}, found {}",
+                                        current.describe(),
+                                        peek.describe()
+                                    ),
+                            ));
                         }
                         _ => {}
                     }
@@ -167,7 +232,17 @@ impl FilterParser {
                     *has_only_ids = false;
                     match self.peek_token.token_type {
                         TokenType::OperatorOr | TokenType::OperatorAnd | TokenType::OperatorXor => {
-                            return Err(err_msg_prefix + "Found two operators one after the other");
+                            let (current, peek) =
+                                (self.current_token.to_owned(), self.peek_token.to_owned());
+                            return Err(self.error_at(
+                                peek.span.to_owned(),
+                                err_msg_prefix
+                                    + &format!(
+                                        "Expected a filter after {}, found {}",
+                                        current.describe(),
+                                        peek.describe()
+                                    ),
+                            ));
                         }
                         _ => {}
                     }
@@ -187,7 +262,17 @@ impl FilterParser {
                     *has_only_ids = false;
                     match self.peek_token.token_type {
                         TokenType::OperatorOr | TokenType::OperatorAnd | TokenType::OperatorXor => {
-                            return Err(err_msg_prefix + "Found two operators one after the other");
+                            let (current, peek) =
+                                (self.current_token.to_owned(), self.peek_token.to_owned());
+                            return Err(self.error_at(
+                                peek.span.to_owned(),
+                                err_msg_prefix
+                                    + &format!(
+                                        "Expected a filter after {}, found {}",
+                                        current.describe(),
+                                        peek.describe()
+                                    ),
+                            ));
                         }
                         _ => {}
                     }
@@ -211,7 +296,10 @@ impl FilterParser {
                 }
                 TokenType::RightParenthesis => {
                     if *parenthesis_scope == 0 {
-                        return Err(err_msg_prefix + "Encountered ')' before encountering a '('");
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix + "Found ')' without a matching '('",
+                        ));
                     }
                     return Ok(filter);
                 }
@@ -236,11 +324,14 @@ impl FilterParser {
                         });
                     }
                     if self.current_token.token_type != TokenType::RightParenthesis {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "Expected right parenthesis, found '{}'",
-                                self.current_token.literal
-                            ));
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected ')' to close the '(', found {}",
+                                    self.current_token.describe()
+                                ),
+                        ));
                     }
                     self.next_token();
                 }
@@ -249,17 +340,24 @@ impl FilterParser {
                     self.next_token();
                     self.skip_whitespace();
                     if self.current_token.token_type != TokenType::WordString {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "Expected a token of type String following a TokenTypeFilterStatus, found '{}' (value: '{}')",
-                                self.current_token.token_type, self.current_token.literal
-                            ));
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a status after 'status:', found {}",
+                                    self.current_token.describe()
+                                ),
+                        ));
                     }
 
-                    let status_filter = Box::new(StatusFilter {
-                        status: TaskStatus::from_string(&self.current_token.literal)
-                            .map_err(|err| err_msg_prefix.to_string() + &err)?,
-                    });
+                    let status = match TaskStatus::from_string(&self.current_token.literal) {
+                        Ok(status) => status,
+                        Err(err) => {
+                            let err = err.with_prefix(&err_msg_prefix);
+                            return Err(self.locate_error(err, self.current_token.span.to_owned()));
+                        }
+                    };
+                    let status_filter = Box::new(StatusFilter { status });
                     filter = add_to_current_filter(filter, status_filter, &ScopeOperator::And);
 
                     self.next_token();
@@ -269,13 +367,17 @@ impl FilterParser {
                     self.next_token();
                     self.skip_whitespace();
                     if self.current_token.token_type != TokenType::WordString {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "Expected a token of type String following a TokenTypeProjectPrefix, found '{}' (value: '{}')",
-                                self.current_token.token_type, self.current_token.literal
-                            ));
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a project name after 'project:', found {}",
+                                    self.current_token.describe()
+                                ),
+                        ));
                     }
 
+                    let project_start = self.current_token.span.start;
                     let mut project_name = self.current_token.literal.to_string();
 
                     while self.peek_token.token_type == TokenType::TagMinusPrefix
@@ -285,20 +387,27 @@ impl FilterParser {
                         self.next_token();
                     }
 
+                    let project_span = project_start..self.current_token.span.end;
                     if project_name.ends_with('.') {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "A project name cannot end with a '.' (name: '{}')",
-                                project_name
-                            ));
+                        return Err(self.error_at(
+                            project_span,
+                            err_msg_prefix
+                                + &format!(
+                                    "A project name cannot end with a '.' (name: '{}')",
+                                    project_name
+                                ),
+                        ));
                     }
 
                     if project_name.ends_with('-') {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "A project name cannot end with a '-' (name: '{}')",
-                                project_name
-                            ));
+                        return Err(self.error_at(
+                            project_span,
+                            err_msg_prefix
+                                + &format!(
+                                    "A project name cannot end with a '-' (name: '{}')",
+                                    project_name
+                                ),
+                        ));
                     }
 
                     let project_filter = Box::new(ProjectFilter {
@@ -308,7 +417,7 @@ impl FilterParser {
 
                     self.next_token();
                 }
-                TokenType::String | TokenType::WordString => {
+                TokenType::String | TokenType::WordString | TokenType::Date => {
                     *has_only_ids = false;
                     filter = add_to_current_filter(
                         filter,
@@ -319,6 +428,93 @@ impl FilterParser {
                     );
                     self.next_token();
                 }
+                TokenType::Regex => {
+                    *has_only_ids = false;
+                    let regex_filter = self.read_regex_filter(TextField::Summary)?;
+                    filter = add_to_current_filter(filter, regex_filter, &ScopeOperator::And);
+                }
+                TokenType::TextMatch | TokenType::WordMatch => {
+                    *has_only_ids = false;
+                    let word = self.current_token.token_type == TokenType::WordMatch;
+                    let keyword = self.current_token.literal.to_owned();
+                    let field = TextField::from_string(&keyword[..keyword.len() - 1]).unwrap();
+                    self.next_token();
+                    self.skip_whitespace();
+
+                    let text_filter: Box<dyn Filter> =
+                        if !word && self.current_token.token_type == TokenType::Regex {
+                            self.read_regex_filter(field)?
+                        } else {
+                            let span = self.current_token.span.to_owned();
+                            let value = self.read_text_value();
+                            if value.is_empty() {
+                                return Err(self.error_at(
+                                    span,
+                                    err_msg_prefix
+                                        + &format!(
+                                            "Expected a text after '{}', found {}",
+                                            keyword,
+                                            self.current_token.describe()
+                                        ),
+                                ));
+                            }
+                            Box::new(TextFilter { field, value, word })
+                        };
+                    filter = add_to_current_filter(filter, text_filter, &ScopeOperator::And);
+                }
+                TokenType::Comparison => {
+                    *has_only_ids = false;
+                    let keyword = self.current_token.literal.to_owned();
+                    let field_end = keyword.find(['<', '>', '=']).unwrap();
+                    let operator = ComparisonOperator::from_string(&keyword[field_end..]).unwrap();
+                    let field = &keyword[..field_end];
+                    self.next_token();
+                    self.skip_whitespace();
+
+                    let comparison_filter: Box<dyn Filter> =
+                        if let Some(field) = NumberField::from_string(field) {
+                            let value = self.read_number().ok_or_else(|| {
+                                self.error_at(
+                                    self.current_token.span.to_owned(),
+                                    err_msg_prefix.to_owned()
+                                        + &format!(
+                                            "Expected a number after '{}', found {}",
+                                            keyword,
+                                            self.current_token.describe()
+                                        ),
+                                )
+                            })?;
+                            Box::new(NumberComparisonFilter {
+                                field,
+                                operator,
+                                value,
+                            })
+                        } else {
+                            if self.current_token.token_type != TokenType::WordString
+                                && self.current_token.token_type != TokenType::Int
+                                && self.current_token.token_type != TokenType::Date
+                            {
+                                return Err(self.error_at(
+                                    self.current_token.span.to_owned(),
+                                    err_msg_prefix
+                                        + &format!(
+                                            "Expected a date after '{}', found {}",
+                                            keyword,
+                                            self.current_token.describe()
+                                        ),
+                                ));
+                            }
+                            let time = self
+                                .read_date_expr()
+                                .map_err(|err| err.with_prefix(&err_msg_prefix))?;
+                            Box::new(DateComparisonFilter {
+                                field: DateField::from_string(field).unwrap(),
+                                operator,
+                                time,
+                            })
+                        };
+                    filter = add_to_current_filter(filter, comparison_filter, &ScopeOperator::And);
+                }
                 TokenType::Uuid => {
                     *has_only_ids = false;
                     filter = add_to_current_filter(
@@ -348,11 +544,15 @@ impl FilterParser {
                 TokenType::TagMinusPrefix => {
                     *has_only_ids = false;
                     if self.peek_token.token_type != TokenType::WordString {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "Expected a token of type String following a TokenType::TagMinusPrefix, found '{}' (value: '{}')",
-                                self.peek_token.token_type, self.peek_token.literal
-                            ));
+                        return Err(self.error_at(
+                            self.peek_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a tag name after '{}', found {}",
+                                    self.current_token.literal,
+                                    self.peek_token.describe()
+                                ),
+                        ));
                     }
 
                     let tag_filter = Box::new(TagFilter {
@@ -366,11 +566,15 @@ impl FilterParser {
                 TokenType::TagPlusPrefix => {
                     *has_only_ids = false;
                     if self.peek_token.token_type != TokenType::WordString {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "Expected a token of type String following a TokenType::TagMinusPrefix, found '{}' (value: '{}')",
-                                self.peek_token.token_type, self.peek_token.literal
-                            ));
+                        return Err(self.error_at(
+                            self.peek_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a tag name after '{}', found {}",
+                                    self.current_token.literal,
+                                    self.peek_token.describe()
+                                ),
+                        ));
                     }
                     let tag_filter = Box::new(TagFilter {
                         include: true,
@@ -406,17 +610,135 @@ impl FilterParser {
                             id: None,
                         }),
                         _ => {
-                            return Err(err_msg_prefix
-                                + &format!(
-                                    "Expected a token of type Int or UUID following a TokenType::DependsOn, found '{}' (value: '{}')",
-                                    self.current_token.token_type, self.current_token.literal
-                                ));
+                            return Err(self.error_at(
+                                self.current_token.span.to_owned(),
+                                err_msg_prefix
+                                    + &format!(
+                                        "Expected a task ID or UUID after 'depends:', found {}",
+                                        self.current_token.describe()
+                                    ),
+                            ));
                         }
                     };
                     filter = add_to_current_filter(filter, depends_on_filter, &ScopeOperator::And);
 
                     self.next_token();
                 }
+                TokenType::DependsOnAny | TokenType::BlocksAny => {
+                    let link_type = if self.current_token.token_type == TokenType::DependsOnAny {
+                        TransitiveLinkType::DependsOn
+                    } else {
+                        TransitiveLinkType::Blocks
+                    };
+                    let keyword = self.current_token.literal.to_owned();
+                    self.next_token();
+                    self.skip_whitespace();
+
+                    let (id, uuid) = match self.current_token.token_type {
+                        TokenType::Int => (
+                            Some(self.current_token.literal.parse::<usize>().unwrap()),
+                            None,
+                        ),
+                        TokenType::Uuid => (
+                            None,
+                            Some(self.current_token.literal.parse::<Uuid>().unwrap()),
+                        ),
+                        _ => {
+                            return Err(self.error_at(
+                                self.current_token.span.to_owned(),
+                                err_msg_prefix
+                                    + &format!(
+                                        "Expected a task ID or UUID after '{}', found {}",
+                                        keyword,
+                                        self.current_token.describe()
+                                    ),
+                            ));
+                        }
+                    };
+                    let transitive_filter = Box::new(TransitiveLinkFilter {
+                        id,
+                        uuid,
+                        link_type,
+                        linked_uuids: None,
+                    });
+                    filter = add_to_current_filter(filter, transitive_filter, &ScopeOperator::And);
+
+                    self.next_token();
+                }
+                TokenType::Description => {
+                    *has_only_ids = false;
+                    self.next_token();
+                    self.skip_whitespace();
+                    if self.current_token.token_type != TokenType::WordString
+                        && self.current_token.token_type != TokenType::String
+                        && self.current_token.token_type != TokenType::Int
+                        && self.current_token.token_type != TokenType::Date
+                    {
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a text after 'description:', found {}",
+                                    self.current_token.describe()
+                                ),
+                        ));
+                    }
+
+                    let description_filter = Box::new(DescriptionFilter {
+                        value: self.current_token.literal.to_owned(),
+                    });
+                    filter = add_to_current_filter(filter, description_filter, &ScopeOperator::And);
+
+                    self.next_token();
+                }
+                TokenType::Priority | TokenType::PriorityAbove => {
+                    *has_only_ids = false;
+                    let above = self.current_token.token_type == TokenType::PriorityAbove;
+                    let keyword = self.current_token.literal.to_owned();
+                    self.next_token();
+                    self.skip_whitespace();
+                    if self.current_token.token_type != TokenType::WordString
+                        && self.current_token.token_type != TokenType::Int
+                    {
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a priority after '{}', found {}",
+                                    keyword,
+                                    self.current_token.describe()
+                                ),
+                        ));
+                    }
+
+                    let priority = if self.current_token.literal.to_lowercase() == "none" {
+                        None
+                    } else {
+                        match Priority::from_string(&self.current_token.literal) {
+                            Ok(priority) => Some(priority),
+                            Err(err) => {
+                                let err = err.with_prefix(&err_msg_prefix);
+                                return Err(
+                                    self.locate_error(err, self.current_token.span.to_owned())
+                                );
+                            }
+                        }
+                    };
+                    let priority_filter = Box::new(PriorityFilter { priority, above });
+                    filter = add_to_current_filter(filter, priority_filter, &ScopeOperator::And);
+
+                    self.next_token();
+                }
+                TokenType::Recur => {
+                    return Err(self.error_at(
+                        self.current_token.span.to_owned(),
+                        err_msg_prefix
+                            + &format!(
+                                "'{}' can only be used to modify a task, not to filter tasks",
+                                self.current_token.literal
+                            ),
+                    ));
+                }
                 TokenType::FilterTokDateEndBefore
                 | TokenType::FilterTokDateEndAfter
                 | TokenType::FilterTokDateCreatedBefore
@@ -428,20 +750,28 @@ impl FilterParser {
                     let before = self.current_token.token_type == TokenType::FilterTokDateEndBefore
                         || self.current_token.token_type == TokenType::FilterTokDateCreatedBefore;
                     let tok_type = self.current_token.token_type.clone();
+                    let keyword = self.current_token.literal.to_owned();
 
                     self.next_token();
                     self.skip_whitespace();
                     if self.current_token.token_type != TokenType::WordString
                         && self.current_token.token_type != TokenType::Int
+                        && self.current_token.token_type != TokenType::Date
                     {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "Expected a token of type String or Int following a TokenTypeFilterDateEnd, found '{}' (value: '{}')",
-                                self.peek_token.token_type, self.peek_token.literal
-                            ));
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a date after '{}', found {}",
+                                    keyword,
+                                    self.current_token.describe()
+                                ),
+                        ));
                     }
 
-                    let time = self.read_date_expr()?;
+                    let time = self
+                        .read_date_expr()
+                        .map_err(|err| err.with_prefix(&err_msg_prefix))?;
                     let new_filter: Box<dyn Filter> = match tok_type {
                         TokenType::FilterTokDateEndBefore | TokenType::FilterTokDateEndAfter => {
                             Box::new(DateEndFilter { time, before })
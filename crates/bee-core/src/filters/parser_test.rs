@@ -1,8 +1,9 @@
 use crate::filters;
 use all_asserts::assert_true;
 use chrono::{Duration, Local, NaiveTime, TimeZone};
+use std::ops::Range;
 
-use crate::task::{Project, TaskStatus};
+use crate::task::{Priority, Project, TaskStatus};
 
 use super::*;
 
@@ -153,6 +154,195 @@ fn test_parse_depends_on_filter() {
     assert_true!(p.parse_filter().is_err())
 }
 
+#[test]
+fn test_parse_transitive_link_filter() {
+    let lexer = Lexer::new("depends.any:1".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(TransitiveLinkFilter {
+        id: Some(1),
+        uuid: None,
+        link_type: TransitiveLinkType::DependsOn,
+        linked_uuids: None,
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let new_uuid = Uuid::new_v4();
+    let lexer = Lexer::new(format!("blocks.any: {}", new_uuid));
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(TransitiveLinkFilter {
+        id: None,
+        uuid: Some(new_uuid),
+        link_type: TransitiveLinkType::Blocks,
+        linked_uuids: None,
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("blocks.any:abc".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_err())
+}
+
+#[test]
+fn test_parse_description_filter() {
+    let lexer = Lexer::new("description:figures +work".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(AndFilter {
+        children: vec![
+            Box::new(DescriptionFilter {
+                value: "figures".to_owned(),
+            }),
+            Box::new(TagFilter {
+                include: true,
+                tag_name: "work".to_owned(),
+            }),
+        ],
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("description:".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_err())
+}
+
+#[test]
+fn test_parse_text_filter() {
+    let lexer = Lexer::new("/^fix\\/ed [0-9]+/ tag~team- summary=bug".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(AndFilter {
+        children: vec![
+            Box::new(AndFilter {
+                children: vec![
+                    Box::new(RegexFilter {
+                        field: TextField::Summary,
+                        regex: RegexFilter::build_regex("^fix/ed [0-9]+").unwrap(),
+                    }),
+                    Box::new(TextFilter {
+                        field: TextField::Tag,
+                        value: "team-".to_owned(),
+                        word: false,
+                    }),
+                ],
+            }),
+            Box::new(TextFilter {
+                field: TextField::Summary,
+                value: "bug".to_owned(),
+                word: true,
+            }),
+        ],
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("annotation~/alice|bob/".to_string());
+    let mut p = FilterParser::new(lexer);
+    let expected_filter: Box<dyn Filter> = Box::new(RegexFilter {
+        field: TextField::Annotation,
+        regex: RegexFilter::build_regex("alice|bob").unwrap(),
+    });
+    assert_eq!(&p.parse_filter().unwrap(), &expected_filter);
+
+    let lexer = Lexer::new("tag~".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_err());
+
+    let lexer = Lexer::new("/fix(/".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_err());
+}
+
+#[test]
+fn test_parse_comparison_filter() {
+    let lexer = Lexer::new("urgency>=-2 or id<20".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(OrFilter {
+        children: vec![
+            Box::new(NumberComparisonFilter {
+                field: NumberField::Urgency,
+                operator: ComparisonOperator::GreaterOrEqual,
+                value: -2,
+            }),
+            Box::new(NumberComparisonFilter {
+                field: NumberField::Id,
+                operator: ComparisonOperator::Less,
+                value: 20,
+            }),
+        ],
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("due<today".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+    let today = Local
+        .from_local_datetime(
+            &Local::now()
+                .date_naive()
+                .and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap()),
+        )
+        .single()
+        .unwrap();
+    let expected_filter: Box<dyn Filter> = Box::new(DateComparisonFilter {
+        field: DateField::Due,
+        operator: ComparisonOperator::Less,
+        time: today,
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("annotations>many".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_err());
+
+    let lexer = Lexer::new("created>=".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_err());
+}
+
+#[test]
+fn test_parse_priority_filter() {
+    let lexer = Lexer::new("priority:h".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(PriorityFilter {
+        priority: Some(Priority::High),
+        above: false,
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("priority.above: 1".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(PriorityFilter {
+        priority: Some(Priority::Low),
+        above: true,
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("priority:none".to_string());
+    let mut p = FilterParser::new(lexer);
+    let f = p.parse_filter().unwrap();
+
+    let expected_filter: Box<dyn Filter> = Box::new(PriorityFilter {
+        priority: None,
+        above: false,
+    });
+    assert_eq!(&f, &expected_filter);
+
+    let lexer = Lexer::new("priority:urgent".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_err())
+}
+
 #[test]
 fn test_parse_project_filter() {
     let lexer = Lexer::new("project:ABC".to_string());
@@ -507,3 +697,48 @@ fn test_build_filter_dates() {
     });
     assert_eq!(&expected, &actual);
 }
+
+fn get_error_span(input: &str) -> Range<usize> {
+    let lexer = Lexer::new(input.to_string());
+    let mut p = FilterParser::new(lexer);
+    match p.parse_filter() {
+        Err(BeeError::Parse {
+            location: Some(location),
+            ..
+        }) => {
+            assert_eq!(location.input, input);
+            location.span
+        }
+        other => panic!(
+            "Expected a located parse error for {}, got {:?}",
+            input,
+            other.err()
+        ),
+    }
+}
+
+#[test]
+fn test_parse_filter_error_location() {
+    assert_eq!(get_error_span("+home and status:pendng"), 17..23);
+    assert_eq!(get_error_span("+home - +work"), 7..8);
+    assert_eq!(get_error_span("(+home or +work"), 15..15);
+    assert_eq!(get_error_span("+home )"), 6..7);
+    assert_eq!(get_error_span("project:a.b."), 8..12);
+    assert_eq!(get_error_span("due:tomorow"), 4..11);
+
+    // A misspelled keyword is text, it is pointed out when the parsing fails
+    let lexer = Lexer::new("projct:work and Dues:".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(p.parse_filter().is_ok());
+
+    assert_eq!(get_error_span("projct:work due:tomorow"), 16..23);
+    let lexer = Lexer::new("projct:work due:tomorow".to_string());
+    let mut p = FilterParser::new(lexer);
+    assert_true!(
+        p.parse_filter()
+            .err()
+            .unwrap()
+            .get_message()
+            .ends_with("'projct:' is not a keyword, did you mean 'project:'?")
+    );
+}
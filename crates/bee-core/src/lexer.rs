@@ -1,8 +1,13 @@
+use std::ops::Range;
+
+use chrono::{NaiveDate, NaiveDateTime};
 use log::trace;
 use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
 
+use crate::BeeError;
+
 #[derive(Debug, PartialEq, Default, Clone)]
 pub enum TokenType {
     FilterTokDateDue,
@@ -13,6 +18,17 @@ pub enum TokenType {
     FilterTokDateEndBefore,
     FilterTokDateEndAfter,
     DependsOn,
+    DependsOnAny,
+    BlocksAny,
+    Recur,
+    Priority,
+    PriorityAbove,
+    Description,
+    TextMatch,
+    WordMatch,
+    Regex,
+    Comparison,
+    Date,
     String,
     WordString,
     TagPlusPrefix,
@@ -37,6 +53,17 @@ impl std::fmt::Display for TokenType {
             TokenType::FilterTokDateDueBefore => "FilterTokDateDueBefore",
             TokenType::FilterTokDateDueAfter => "FilterTokDateDueAfter",
             TokenType::DependsOn => "DependsOn",
+            TokenType::DependsOnAny => "DependsOnAny",
+            TokenType::BlocksAny => "BlocksAny",
+            TokenType::Recur => "Recur",
+            TokenType::Priority => "Priority",
+            TokenType::PriorityAbove => "PriorityAbove",
+            TokenType::Description => "Description",
+            TokenType::TextMatch => "TextMatch",
+            TokenType::WordMatch => "WordMatch",
+            TokenType::Regex => "Regex",
+            TokenType::Comparison => "Comparison",
+            TokenType::Date => "Date",
             TokenType::String => "String",
             TokenType::ProjectPrefix => "ProjectPrefix",
             TokenType::WordString => "WordString",
@@ -64,10 +91,123 @@ impl std::fmt::Display for TokenType {
 #[path = "lexer_test.rs"]
 mod lexer_test;
 
+/// The keywords of the filters and of the task properties
+pub const KEYWORDS: &[&str] = &[
+    "status:",
+    "created.after:",
+    "created.before:",
+    "end.after:",
+    "end.before:",
+    "project:",
+    "due:",
+    "due.before:",
+    "due.after:",
+    "proj:",
+    "depends:",
+    "depends.any:",
+    "blocks.any:",
+    "recur:",
+    "priority:",
+    "priority.above:",
+    "description:",
+    "summary~",
+    "annotation~",
+    "description~",
+    "tag~",
+    "summary=",
+    "annotation=",
+    "description=",
+    "tag=",
+];
+
+/// The fields that can be matched against a text with '<field>~' or '<field>='
+pub const TEXT_FIELDS: &[&str] = &["summary", "annotation", "description", "tag"];
+
+/// The fields that can be compared to a value, e.g. 'urgency>5' or 'due<tomorrow'
+pub const COMPARISON_FIELDS: &[&str] = &["urgency", "id", "annotations", "created", "due", "end"];
+
+/// The comparison operators, the longest first so that '>=' isn't read as '>'
+pub const COMPARISON_OPERATORS: &[&str] = &[">=", "<=", ">", "<", "="];
+
 #[derive(Debug, Default, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub literal: String,
+    /// The graphemes of the input the token was read from
+    pub span: Range<usize>,
+}
+
+impl Token {
+    /// How the token is shown in the error messages
+    pub fn describe(&self) -> String {
+        match self.token_type {
+            TokenType::Eof => "the end of the expression".to_string(),
+            TokenType::Blank => "a blank".to_string(),
+            _ => format!("'{}'", self.literal),
+        }
+    }
+}
+
+/// The number of single-character edits needed to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The keyword `word` likely misspells, e.g. 'project:' for 'projct:work'.
+/// Only the words made of a name, a ':' and a value are considered, as a word ending
+/// with ':' is usually text (e.g. 'Note:'), and only the keywords ending with ':' a
+/// couple of edits away are suggested.
+pub fn suggest_keyword(word: &str) -> Option<&'static str> {
+    let (name, value) = word.split_once(':')?;
+    if value.is_empty() {
+        return None;
+    }
+    let name = format!("{}:", name.to_lowercase());
+    if name.len() < 3 || KEYWORDS.contains(&name.as_str()) {
+        return None;
+    }
+    let max_distance = if name.len() <= 5 { 1 } else { 2 };
+    KEYWORDS
+        .iter()
+        .filter(|keyword| keyword.ends_with(':'))
+        .map(|keyword| (edit_distance(&name, keyword), *keyword))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, keyword)| keyword)
+}
+
+/// Parse an ISO 8601 date, e.g. '2025-03-14', '2025-03-14T09:00' or '2025-03-14T09:00:30'
+pub(crate) fn parse_iso_date(input: &str) -> Option<NaiveDateTime> {
+    let bytes = input.as_bytes();
+    let is_date_shape = bytes.len() >= 10
+        && bytes[..10].iter().enumerate().all(|(i, b)| {
+            if i == 4 || i == 7 {
+                *b == b'-'
+            } else {
+                b.is_ascii_digit()
+            }
+        });
+    if !is_date_shape {
+        return None;
+    }
+    match input.len() {
+        10 => NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0)),
+        16 => NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M").ok(),
+        19 => NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S").ok(),
+        _ => None,
+    }
 }
 
 fn is_segment_character(ch: &char) -> bool {
@@ -98,6 +238,10 @@ impl Lexer {
         self.input.graphemes(true).collect::<Vec<_>>().len()
     }
 
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+
     pub fn new(input: String) -> Lexer {
         let mut lexer = Lexer {
             input,
@@ -160,11 +304,37 @@ impl Lexer {
         Uuid::parse_str(&potential_uuid_str).is_ok()
     }
 
+    // Number of graphemes of the ISO 8601 date at the current position, if there is one
+    fn get_date_len(&self) -> Option<usize> {
+        let rest: String = self
+            .input
+            .graphemes(true)
+            .skip(self.position)
+            .take(19)
+            .collect();
+        [19, 16, 10].into_iter().find(|len| {
+            rest.get(..*len)
+                .is_some_and(|d| parse_iso_date(d).is_some())
+        })
+    }
+
+    // Read the given number of graphemes
+    fn read_graphemes(&mut self, count: usize) -> String {
+        let mut output_str = String::default();
+        for _ in 0..count {
+            if let Some(c) = &self.ch {
+                output_str.push_str(c);
+            }
+            self.read_char();
+        }
+        output_str
+    }
+
     // Method to check and read a UUID
-    fn read_uuid(&mut self) -> Result<String, String> {
+    fn read_uuid(&mut self) -> Result<String, BeeError> {
         let end_pos = self.position + 36;
         if end_pos > self.get_input_len() {
-            return Err("Not a valid UUID string".to_string());
+            return Err(BeeError::parse("Not a valid UUID string"));
         }
 
         let uuid_str = self
@@ -176,7 +346,7 @@ impl Lexer {
 
         if Uuid::parse_str(&uuid_str).is_ok() {
             self.position = end_pos;
-            self.read_position = end_pos;
+            self.read_position = end_pos + 1;
             self.ch = self
                 .input
                 .graphemes(true)
@@ -184,7 +354,7 @@ impl Lexer {
                 .map(|s| s.to_string());
             Ok(uuid_str.to_string())
         } else {
-            Err("Not a valid UUID string".to_string())
+            Err(BeeError::parse("Not a valid UUID string"))
         }
     }
 
@@ -229,6 +399,67 @@ impl Lexer {
         output_str
     }
 
+    // Method to check if the current character opens a regular expression, i.e. a '/'
+    // that is closed by another '/' further in the input
+    fn is_regex(&self) -> bool {
+        self.ch.as_deref() == Some("/") && self.find_regex_end().is_some()
+    }
+
+    // Position of the '/' closing the regular expression starting at the current
+    // position. A '/' preceded by a '\' is part of the expression.
+    fn find_regex_end(&self) -> Option<usize> {
+        let mut escaped = false;
+        for (i, grapheme) in self
+            .input
+            .graphemes(true)
+            .enumerate()
+            .skip(self.position + 1)
+        {
+            match grapheme {
+                "/" if !escaped => return Some(i),
+                "\\" => escaped = !escaped,
+                _ => escaped = false,
+            }
+        }
+        None
+    }
+
+    // Read the regular expression, including its surrounding '/'
+    fn read_regex(&mut self) -> String {
+        let end = self.find_regex_end().unwrap();
+        let mut output_str = String::default();
+        while self.position <= end {
+            if let Some(c) = &self.ch {
+                output_str.push_str(c);
+            }
+            self.read_char();
+        }
+        output_str
+    }
+
+    // Read a text field keyword, e.g. 'tag~' or 'summary='
+    fn match_text_field(&self) -> Option<(TokenType, String)> {
+        TEXT_FIELDS.iter().find_map(|field| {
+            if self.match_keyword(&format!("{}~", field)) {
+                Some((TokenType::TextMatch, format!("{}~", field)))
+            } else if self.match_keyword(&format!("{}=", field)) {
+                Some((TokenType::WordMatch, format!("{}=", field)))
+            } else {
+                None
+            }
+        })
+    }
+
+    // Read a comparison keyword, e.g. 'urgency>=' or 'due<'
+    fn match_comparison(&self) -> Option<String> {
+        COMPARISON_FIELDS.iter().find_map(|field| {
+            COMPARISON_OPERATORS
+                .iter()
+                .map(|operator| format!("{}{}", field, operator))
+                .find(|keyword| self.match_keyword(keyword))
+        })
+    }
+
     // This reads the word given as parameter. If the next characters do not
     // correspond to the word given, this will PANIC
     fn read_word(&mut self, word: &str) -> String {
@@ -247,7 +478,17 @@ impl Lexer {
         output_str
     }
 
-    pub fn next_token(&mut self) -> Result<Token, String> {
+    pub fn next_token(&mut self) -> Result<Token, BeeError> {
+        let start = self.position;
+        let (token_type, literal) = self.read_token()?;
+        Ok(Token {
+            token_type,
+            literal,
+            span: start..self.position.min(self.get_input_len()),
+        })
+    }
+
+    fn read_token(&mut self) -> Result<(TokenType, String), BeeError> {
         let mut whitespaces = String::default();
         while matches!(&self.ch, Some(ch) if ch
                 .nfc()
@@ -259,47 +500,34 @@ impl Lexer {
             self.read_char();
         }
         if !whitespaces.is_empty() {
-            return Ok(Token {
-                literal: whitespaces,
-                token_type: TokenType::Blank,
-            });
+            return Ok((TokenType::Blank, whitespaces));
         }
 
         let token = match &self.ch {
-            None => Token {
-                token_type: TokenType::Eof,
-                literal: String::new(),
-            },
+            None => (TokenType::Eof, String::new()),
             Some(ch) => match ch {
                 _ if self.is_uuid() => {
                     trace!("Token '{}' is a UUID", ch);
-                    Token {
-                        literal: self.read_uuid()?,
-                        token_type: TokenType::Uuid,
-                    }
+                    (TokenType::Uuid, self.read_uuid()?)
+                }
+                _ if let Some(len) = self.get_date_len() => {
+                    let date = self.read_graphemes(len);
+                    trace!("Token '{}' is a Date", date);
+                    (TokenType::Date, date)
                 }
                 _ if self.is_digit() => {
                     trace!("Token '{}' is a digit", ch);
-                    Token {
-                        token_type: TokenType::Int,
-                        literal: self.read_int(),
-                    }
+                    (TokenType::Int, self.read_int())
                 }
                 _ if ch == "+" => {
                     trace!("Token '{}' is a TagPlusPrefix", ch);
                     self.read_char();
-                    Token {
-                        token_type: TokenType::TagPlusPrefix,
-                        literal: "+".to_owned(),
-                    }
+                    (TokenType::TagPlusPrefix, "+".to_owned())
                 }
                 _ if ch == "-" => {
                     trace!("Token '{}' is a TagMinusPrefix", ch);
                     self.read_char();
-                    Token {
-                        token_type: TokenType::TagMinusPrefix,
-                        literal: "-".to_owned(),
-                    }
+                    (TokenType::TagMinusPrefix, "-".to_owned())
                 }
                 _ if self.match_keyword("and") => {
                     let mut literal_value = self.read_word("and");
@@ -313,10 +541,7 @@ impl Lexer {
                     };
 
                     trace!("Token '{}' is a {}", literal_value, token_type);
-                    Token {
-                        literal: literal_value,
-                        token_type,
-                    }
+                    (token_type, literal_value)
                 }
                 _ if self.match_keyword("or") => {
                     let mut literal_value = self.read_word("or");
@@ -330,10 +555,7 @@ impl Lexer {
                     };
 
                     trace!("Token '{}' is a {}", literal_value, token_type);
-                    Token {
-                        literal: literal_value,
-                        token_type,
-                    }
+                    (token_type, literal_value)
                 }
                 _ if self.match_keyword("xor") => {
                     let mut literal_value = self.read_word("xor");
@@ -347,84 +569,91 @@ impl Lexer {
                     };
 
                     trace!("Token '{}' is a {}", literal_value, token_type);
-                    Token {
-                        literal: literal_value,
-                        token_type,
-                    }
+                    (token_type, literal_value)
+                }
+                _ if self.match_keyword("status:") => {
+                    (TokenType::FilterStatus, self.read_word("status:"))
+                }
+                _ if self.match_keyword("created.after:") => (
+                    TokenType::FilterTokDateCreatedAfter,
+                    self.read_word("created.after:"),
+                ),
+                _ if self.match_keyword("created.before:") => (
+                    TokenType::FilterTokDateCreatedBefore,
+                    self.read_word("created.before:"),
+                ),
+                _ if self.match_keyword("end.after:") => (
+                    TokenType::FilterTokDateEndAfter,
+                    self.read_word("end.after:"),
+                ),
+                _ if self.match_keyword("end.before:") => (
+                    TokenType::FilterTokDateEndBefore,
+                    self.read_word("end.before:"),
+                ),
+                _ if self.match_keyword("project:") => {
+                    (TokenType::ProjectPrefix, self.read_word("project:"))
+                }
+                _ if self.match_keyword("due:") => {
+                    (TokenType::FilterTokDateDue, self.read_word("due:"))
+                }
+                _ if self.match_keyword("due.before:") => (
+                    TokenType::FilterTokDateDueBefore,
+                    self.read_word("due.before:"),
+                ),
+                _ if self.match_keyword("due.after:") => (
+                    TokenType::FilterTokDateDueAfter,
+                    self.read_word("due.after:"),
+                ),
+                _ if self.match_keyword("proj:") => {
+                    (TokenType::ProjectPrefix, self.read_word("proj:"))
+                }
+                _ if self.match_keyword("depends:") => {
+                    (TokenType::DependsOn, self.read_word("depends:"))
+                }
+                _ if self.match_keyword("depends.any:") => {
+                    (TokenType::DependsOnAny, self.read_word("depends.any:"))
+                }
+                _ if self.match_keyword("blocks.any:") => {
+                    (TokenType::BlocksAny, self.read_word("blocks.any:"))
+                }
+                _ if self.match_keyword("recur:") => (TokenType::Recur, self.read_word("recur:")),
+                _ if self.match_keyword("priority:") => {
+                    (TokenType::Priority, self.read_word("priority:"))
+                }
+                _ if self.match_keyword("priority.above:") => {
+                    (TokenType::PriorityAbove, self.read_word("priority.above:"))
+                }
+                _ if self.match_keyword("description:") => {
+                    (TokenType::Description, self.read_word("description:"))
+                }
+                _ if let Some((token_type, keyword)) = self.match_text_field() => {
+                    (token_type, self.read_word(&keyword))
+                }
+                _ if let Some(keyword) = self.match_comparison() => {
+                    (TokenType::Comparison, self.read_word(&keyword))
+                }
+                _ if self.is_regex() => {
+                    let regex = self.read_regex();
+                    trace!("Token '{}' is a Regex", regex);
+                    (TokenType::Regex, regex)
                 }
-                _ if self.match_keyword("status:") => Token {
-                    literal: self.read_word("status:"),
-                    token_type: TokenType::FilterStatus,
-                },
-                _ if self.match_keyword("created.after:") => Token {
-                    literal: self.read_word("created.after:"),
-                    token_type: TokenType::FilterTokDateCreatedAfter,
-                },
-                _ if self.match_keyword("created.before:") => Token {
-                    literal: self.read_word("created.before:"),
-                    token_type: TokenType::FilterTokDateCreatedBefore,
-                },
-                _ if self.match_keyword("end.after:") => Token {
-                    literal: self.read_word("end.after:"),
-                    token_type: TokenType::FilterTokDateEndAfter,
-                },
-                _ if self.match_keyword("end.before:") => Token {
-                    literal: self.read_word("end.before:"),
-                    token_type: TokenType::FilterTokDateEndBefore,
-                },
-                _ if self.match_keyword("project:") => Token {
-                    literal: self.read_word("project:"),
-                    token_type: TokenType::ProjectPrefix,
-                },
-                _ if self.match_keyword("due:") => Token {
-                    literal: self.read_word("due:"),
-                    token_type: TokenType::FilterTokDateDue,
-                },
-                _ if self.match_keyword("due.before:") => Token {
-                    literal: self.read_word("due.before:"),
-                    token_type: TokenType::FilterTokDateDueBefore,
-                },
-                _ if self.match_keyword("due.after:") => Token {
-                    literal: self.read_word("due.after:"),
-                    token_type: TokenType::FilterTokDateDueAfter,
-                },
-                _ if self.match_keyword("proj:") => Token {
-                    literal: self.read_word("proj:"),
-                    token_type: TokenType::ProjectPrefix,
-                },
-                _ if self.match_keyword("depends:") => Token {
-                    literal: self.read_word("depends:"),
-                    token_type: TokenType::DependsOn,
-                },
                 _ if ch == ")" => {
                     self.read_char();
-                    Token {
-                        literal: ")".to_string(),
-                        token_type: TokenType::RightParenthesis,
-                    }
+                    (TokenType::RightParenthesis, ")".to_string())
                 }
                 _ if ch == "(" => {
                     self.read_char();
-                    Token {
-                        literal: "(".to_string(),
-                        token_type: TokenType::LeftParenthesis,
-                    }
+                    (TokenType::LeftParenthesis, "(".to_string())
                 }
                 _ if self.is_word_character() => {
                     let next_word = self.read_next_word();
                     trace!("Token '{}' is a WordString", next_word);
-                    Token {
-                        literal: next_word,
-                        token_type: TokenType::WordString,
-                    }
+                    (TokenType::WordString, next_word)
                 }
                 _ => {
                     let next_word = self.read_next_word();
                     trace!("Token '{}' is a WordString", next_word);
-                    Token {
-                        literal: next_word,
-                        token_type: TokenType::String,
-                    }
+                    (TokenType::String, next_word)
                 }
             },
         };
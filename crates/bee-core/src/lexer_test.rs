@@ -137,6 +137,31 @@ fn test_lexer() {
     let tok = lexer.next_token().unwrap();
     assert_eq!(tok.literal, "depends:");
     assert_eq!(tok.token_type, TokenType::DependsOn);
+
+    let mut lexer = Lexer::new("depends.any:".to_string());
+    let tok = lexer.next_token().unwrap();
+    assert_eq!(tok.literal, "depends.any:");
+    assert_eq!(tok.token_type, TokenType::DependsOnAny);
+
+    let mut lexer = Lexer::new("blocks.any:".to_string());
+    let tok = lexer.next_token().unwrap();
+    assert_eq!(tok.literal, "blocks.any:");
+    assert_eq!(tok.token_type, TokenType::BlocksAny);
+
+    let mut lexer = Lexer::new("priority:".to_string());
+    let tok = lexer.next_token().unwrap();
+    assert_eq!(tok.literal, "priority:");
+    assert_eq!(tok.token_type, TokenType::Priority);
+
+    let mut lexer = Lexer::new("priority.above:".to_string());
+    let tok = lexer.next_token().unwrap();
+    assert_eq!(tok.literal, "priority.above:");
+    assert_eq!(tok.token_type, TokenType::PriorityAbove);
+
+    let mut lexer = Lexer::new("description:".to_string());
+    let tok = lexer.next_token().unwrap();
+    assert_eq!(tok.literal, "description:");
+    assert_eq!(tok.token_type, TokenType::Description);
 }
 
 #[test]
@@ -225,3 +250,104 @@ fn test_lexer_with_spaces() {
     assert_eq!(tok.literal, "\n");
     assert_eq!(tok.token_type, TokenType::Blank);
 }
+
+#[test]
+fn test_lexer_token_spans() {
+    let mut lexer = Lexer::new("é +tag project:work".to_string());
+    let expected = [
+        (TokenType::WordString, 0..1),
+        (TokenType::Blank, 1..2),
+        (TokenType::TagPlusPrefix, 2..3),
+        (TokenType::WordString, 3..6),
+        (TokenType::Blank, 6..7),
+        (TokenType::ProjectPrefix, 7..15),
+        (TokenType::WordString, 15..19),
+        (TokenType::Eof, 19..19),
+    ];
+    for (token_type, span) in expected {
+        let tok = lexer.next_token().unwrap();
+        assert_eq!(tok.token_type, token_type);
+        assert_eq!(tok.span, span);
+    }
+}
+
+#[test]
+fn test_suggest_keyword() {
+    assert_eq!(suggest_keyword("projct:work"), Some("project:"));
+    assert_eq!(suggest_keyword("Priorty:H"), Some("priority:"));
+    assert_eq!(suggest_keyword("stats:pending"), Some("status:"));
+    assert_eq!(suggest_keyword("project:work"), None);
+    assert_eq!(suggest_keyword("projct"), None);
+    assert_eq!(suggest_keyword("a:b"), None);
+    assert_eq!(suggest_keyword("meeting:noon"), None);
+    // A word ending with ':' is text, and 'tag~' is not a keyword ending with ':'
+    assert_eq!(suggest_keyword("Dues:"), None);
+    assert_eq!(suggest_keyword("Tag:"), None);
+    assert_eq!(suggest_keyword("Tag:me"), None);
+    assert_eq!(suggest_keyword("Dues:friday"), Some("due:"));
+}
+
+#[test]
+fn test_lexer_regex_and_text_fields() {
+    let mut lexer = Lexer::new("/a\\/b c/ tag~x summary=y 1/2".to_string());
+    let expected = [
+        (TokenType::Regex, "/a\\/b c/"),
+        (TokenType::Blank, " "),
+        (TokenType::TextMatch, "tag~"),
+        (TokenType::WordString, "x"),
+        (TokenType::Blank, " "),
+        (TokenType::WordMatch, "summary="),
+        (TokenType::WordString, "y"),
+        (TokenType::Blank, " "),
+        (TokenType::Int, "1"),
+        // Without a closing '/', this is not a regular expression
+        (TokenType::String, "/2"),
+        (TokenType::Eof, ""),
+    ];
+    for (token_type, literal) in expected {
+        let tok = lexer.next_token().unwrap();
+        assert_eq!(tok.token_type, token_type);
+        assert_eq!(tok.literal, literal);
+    }
+}
+
+#[test]
+fn test_lexer_comparison() {
+    let mut lexer = Lexer::new("urgency>=5 due<eod identity".to_string());
+    let expected = [
+        (TokenType::Comparison, "urgency>="),
+        (TokenType::Int, "5"),
+        (TokenType::Blank, " "),
+        (TokenType::Comparison, "due<"),
+        (TokenType::WordString, "eod"),
+        (TokenType::Blank, " "),
+        (TokenType::WordString, "identity"),
+        (TokenType::Eof, ""),
+    ];
+    for (token_type, literal) in expected {
+        let tok = lexer.next_token().unwrap();
+        assert_eq!(tok.token_type, token_type);
+        assert_eq!(tok.literal, literal);
+    }
+}
+
+#[test]
+fn test_lexer_date() {
+    let mut lexer = Lexer::new("2025-03-14 2025-03-14T09:30+1d 2025-13-01".to_string());
+    let expected = [
+        (TokenType::Date, "2025-03-14"),
+        (TokenType::Blank, " "),
+        (TokenType::Date, "2025-03-14T09:30"),
+        (TokenType::TagPlusPrefix, "+"),
+        (TokenType::Int, "1"),
+        (TokenType::WordString, "d"),
+        (TokenType::Blank, " "),
+        // Not a valid date
+        (TokenType::Int, "2025"),
+    ];
+    for (token_type, literal) in expected {
+        let tok = lexer.next_token().unwrap();
+        assert_eq!(tok.token_type, token_type);
+        assert_eq!(tok.literal, literal);
+    }
+}
@@ -1,12 +1,21 @@
+pub mod calendar;
+pub mod chart;
 pub mod config;
+pub mod context;
+pub mod error;
 pub mod filters;
 pub mod task;
 
 mod lexer;
 mod parser;
 
+pub use error::{BeeError, ErrorLocation};
+pub use parser::parse_date;
+
 use std::collections::HashMap;
 
+use calendar::Calendar;
+use chart::BarChart;
 use config::ReportConfig;
 use task::Task;
 
@@ -15,18 +24,41 @@ pub trait Printer {
         &self,
         tasks: Vec<&Task>,
         report_kind: &ReportConfig,
-    ) -> Result<(), String>;
-    fn print_task_info(&self, task: &Task) -> Result<(), String>;
+    ) -> Result<(), BeeError>;
+    fn print_task_info(&self, task: &Task) -> Result<(), BeeError>;
 
     /// Print the help for all the possible actions. This can also have a couple more named sections.
     ///
     /// @help_section_description: This is a map containing a mapping of Action name to
     /// action description, as it is implemented by them. It may also contain a section
     /// name to its content.
-    fn show_help(&self, help_section_description: &HashMap<String, String>) -> Result<(), String>;
+    fn show_help(&self, help_section_description: &HashMap<String, String>)
+    -> Result<(), BeeError>;
     fn show_information_message(&self, message: &str);
     fn error(&self, message: &str);
 
+    /// Report an error that doesn't stop the action, e.g. one of the tasks it targets
+    /// can't be changed
+    fn warning(&self, error: &BeeError) {
+        self.show_information_message(&format!("Warning: {}", error));
+    }
+
+    /// Print a chart. Without a graphical rendering, the values are printed as text.
+    fn print_chart(&self, chart: &BarChart) -> Result<(), BeeError> {
+        for line in chart.to_plain_text() {
+            self.print_raw(&line);
+        }
+        Ok(())
+    }
+
+    /// Print the months of a calendar, with the days that have tasks due marked
+    fn print_calendar(&self, calendar: &Calendar) -> Result<(), BeeError> {
+        for line in calendar.to_plain_text() {
+            self.print_raw(&line);
+        }
+        Ok(())
+    }
+
     /// This function is for developer purposes only. It might be used so the program outputs
     /// information to stdout or console.log, depending on the implementation
     fn print_raw(&self, message: &str);
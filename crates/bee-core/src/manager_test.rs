@@ -249,7 +249,7 @@ fn test_upkeep() {
     // Make sure that the delete and done functions correctly erase the blocking / done status
     // and that upkeep also checks that we need to update when task is complete
     let tmp_task2 = data.tasks.get_mut(&task2_uuid).unwrap().clone();
-    data.tasks.get_mut(&task2_uuid).unwrap().done();
+    data.tasks.get_mut(&task2_uuid).unwrap().done().unwrap();
 
     let _ = data.upkeep();
     assert_true!(
@@ -333,3 +333,152 @@ fn test_upkeep_dependency_links() {
     assert_eq!(t2.get_blocking()[0], &task1_uuid,);
     assert_true!(t2.get_depends_on().is_empty());
 }
+
+fn insert_task_with_dependencies(data: &mut TaskData, summary: &str, depends_on: &[Uuid]) -> Uuid {
+    let uuid = Uuid::new_v4();
+    let id = data.max_id + 1;
+    let task = Task {
+        id: Some(id),
+        uuid,
+        summary: summary.to_owned(),
+        status: TaskStatus::Pending,
+        links: depends_on
+            .iter()
+            .map(|dep| Link {
+                from: uuid,
+                to: *dep,
+                link_type: LinkType::DependsOn,
+            })
+            .collect(),
+        ..Task::default()
+    };
+    data.tasks.insert(uuid, task);
+    data.insert_id_to_uuid(id, uuid);
+    uuid
+}
+
+#[test]
+fn test_apply_circular_dependency() {
+    let mut data = TaskData::default();
+    let a = insert_task_with_dependencies(&mut data, "a", &[]);
+    let b = insert_task_with_dependencies(&mut data, "b", &[a]);
+    let c = insert_task_with_dependencies(&mut data, "c", &[b]);
+
+    let props = TaskProperties {
+        depends_on: Some(vec![DependsOnIdentifier::Usize(3)]),
+        ..TaskProperties::default()
+    };
+    assert_eq!(
+        data.apply(&a, &props),
+        Err(BeeError::InvalidCommand(
+            "Unable to add the dependency, it would create a circular dependency: \
+            1 'a' -> 3 'c' -> 2 'b' -> 1 'a'"
+                .to_string()
+        ))
+    );
+    assert_true!(
+        data.get_task_map()
+            .get(&a)
+            .unwrap()
+            .get_depends_on()
+            .is_empty()
+    );
+
+    // A task can't depend on itself
+    let props = TaskProperties {
+        depends_on: Some(vec![DependsOnIdentifier::Uuid(c)]),
+        ..TaskProperties::default()
+    };
+    assert_eq!(
+        data.apply(&c, &props),
+        Err(BeeError::InvalidCommand(
+            "Unable to add the dependency, it would create a circular dependency: \
+            3 'c' -> 3 'c'"
+                .to_string()
+        ))
+    );
+
+    // Dependencies that don't create cycles are still accepted
+    let props = TaskProperties {
+        depends_on: Some(vec![DependsOnIdentifier::Uuid(a)]),
+        ..TaskProperties::default()
+    };
+    assert_true!(data.apply(&c, &props).is_ok());
+    assert_true!(data.get_task_map().get(&c).unwrap().depends_on(&a));
+}
+
+#[test]
+fn test_find_dependency_cycles() {
+    let mut data = TaskData::default();
+    let a = insert_task_with_dependencies(&mut data, "a", &[]);
+    let b = insert_task_with_dependencies(&mut data, "b", &[a]);
+    insert_task_with_dependencies(&mut data, "c", &[b]);
+    assert_true!(data.find_dependency_cycles().is_empty());
+
+    // Create the cycles a -> b -> a and d -> d, as found in a corrupted data file
+    data.tasks.get_mut(&a).unwrap().links.push(Link {
+        from: a,
+        to: b,
+        link_type: LinkType::DependsOn,
+    });
+    let d = insert_task_with_dependencies(&mut data, "d", &[]);
+    data.tasks.get_mut(&d).unwrap().links.push(Link {
+        from: d,
+        to: d,
+        link_type: LinkType::DependsOn,
+    });
+
+    let cycles = data.find_dependency_cycles();
+    assert_eq!(cycles.len(), 2);
+    assert_true!(cycles.contains(&vec![d, d]));
+    let expected = if a < b { vec![a, b, a] } else { vec![b, a, b] };
+    assert_true!(cycles.contains(&expected));
+    assert_eq!(data.describe_dependency_path(&[d, d]), "4 'd' -> 4 'd'");
+}
+
+#[test]
+fn test_filter_taskdata_transitive() {
+    let mut data = TaskData::default();
+    let a = insert_task_with_dependencies(&mut data, "a", &[]);
+    let b = insert_task_with_dependencies(&mut data, "b", &[a]);
+    let c = insert_task_with_dependencies(&mut data, "c", &[b]);
+    let d = insert_task_with_dependencies(&mut data, "d", &[c]);
+    let other = insert_task_with_dependencies(&mut data, "other", &[]);
+    data.upkeep_links().unwrap();
+
+    // Every task reachable from the filtered task is loaded, not only its neighbours
+    let new_data = data.filter(&filters::from(&[d.to_string()]).unwrap());
+    assert_eq!(new_data.get_task_map().len(), 1);
+    let extra: HashSet<Uuid> = new_data.get_extra_tasks().keys().cloned().collect();
+    assert_eq!(extra, HashSet::from([a, b, c]));
+
+    let matching = |filter: &str| -> HashSet<Uuid> {
+        let mut filter = filters::from(&[filter.to_owned()]).unwrap();
+        filter.convert_id_to_uuid(data.get_id_to_uuid());
+        data.filter(&filter)
+            .get_task_map()
+            .keys()
+            .cloned()
+            .collect()
+    };
+    assert_eq!(matching("depends.any:1"), HashSet::from([b, c, d]));
+    assert_eq!(matching("depends:1"), HashSet::from([b]));
+    assert_eq!(matching("blocks.any:4"), HashSet::from([a, b, c]));
+    assert_eq!(matching("blocks.any:2 or summary"), HashSet::from([a]));
+    assert_eq!(
+        matching(&format!("depends.any:{} and not_matching", b)),
+        HashSet::new()
+    );
+    assert_true!(matching("depends.any:5").is_empty());
+    assert_true!(!matching("depends.any:1").contains(&other));
+
+    // The IDs that match no task match no link either
+    for filter in [
+        "depends.any:999",
+        "blocks.any:999",
+        "depends:999",
+        "blocks:999",
+    ] {
+        assert_true!(matching(filter).is_empty());
+    }
+}
@@ -1,23 +1,27 @@
 use log::debug;
 use std::fmt::Debug;
+use std::ops::Range;
 
-use chrono::{DateTime, Duration, Local, NaiveTime, TimeDelta, TimeZone};
+use chrono::{
+    DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveTime, TimeDelta, TimeZone, Weekday,
+};
 
-use crate::lexer::{Lexer, Token, TokenType};
+use crate::BeeError;
+use crate::lexer::{Lexer, Token, TokenType, parse_iso_date, suggest_keyword};
 
-fn matches_year_string(input: &str) -> bool {
+pub(crate) fn matches_year_string(input: &str) -> bool {
     input == "y" || input == "year" || input == "years"
 }
 
-fn matches_month_string(input: &str) -> bool {
+pub(crate) fn matches_month_string(input: &str) -> bool {
     input == "mo" || input == "month" || input == "months"
 }
 
-fn matches_week_string(input: &str) -> bool {
+pub(crate) fn matches_week_string(input: &str) -> bool {
     input == "w" || input == "week" || input == "weeks"
 }
 
-fn matches_day_string(input: &str) -> bool {
+pub(crate) fn matches_day_string(input: &str) -> bool {
     input == "d" || input == "day" || input == "days"
 }
 
@@ -37,6 +41,44 @@ fn get_day_duration_from_string(number: i64, value: &str) -> TimeDelta {
     Duration::try_days(number * value.parse::<i64>().unwrap().to_owned()).unwrap()
 }
 
+/// An amount of time to move a date by. Months and years follow the calendar, e.g. a
+/// month after January 31st is the last day of February.
+enum DateOffset {
+    Months(u32),
+    Duration(TimeDelta),
+}
+
+impl DateOffset {
+    fn shift(&self, time: DateTime<Local>, forward: bool) -> Option<DateTime<Local>> {
+        match (self, forward) {
+            (DateOffset::Months(months), true) => time.checked_add_months(Months::new(*months)),
+            (DateOffset::Months(months), false) => time.checked_sub_months(Months::new(*months)),
+            (DateOffset::Duration(duration), true) => time.checked_add_signed(*duration),
+            (DateOffset::Duration(duration), false) => time.checked_sub_signed(*duration),
+        }
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Local> {
+    Local
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .unwrap()
+}
+
+/// Midnight on the Monday of the week of the date
+fn start_of_week(date: NaiveDate) -> DateTime<Local> {
+    start_of_day(date - Duration::days(date.weekday().num_days_from_monday() as i64))
+}
+
+fn start_of_month(date: NaiveDate) -> DateTime<Local> {
+    start_of_day(date.with_day(1).unwrap())
+}
+
+fn start_of_year(date: NaiveDate) -> DateTime<Local> {
+    start_of_day(date.with_ordinal(1).unwrap())
+}
+
 pub trait BaseParser: Debug {
     fn get_buffer_index(&self) -> usize;
     fn set_buffer_index(&mut self, value: usize);
@@ -105,6 +147,44 @@ pub trait BaseParser: Debug {
         self.set_buffer_index(self.get_buffer_index() + 1);
     }
 
+    /// A parse error pointing at the graphemes `span` of the input
+    fn error_at(&mut self, span: Range<usize>, message: String) -> BeeError {
+        BeeError::parse_at(message, self.get_mut_lexer().get_input(), span)
+    }
+
+    /// Point the parse error at the graphemes `span` of the input, unless it already
+    /// points somewhere
+    fn locate_error(&mut self, error: BeeError, span: Range<usize>) -> BeeError {
+        error.located_at(self.get_mut_lexer().get_input(), span)
+    }
+
+    /// Point out the first word of the input that looks like a misspelled keyword in
+    /// a parse error, e.g. 'projct:work'. These words are valid text, so they are only
+    /// pointed out once the parsing failed.
+    fn suggest_keyword_in_error(&mut self, error: BeeError) -> BeeError {
+        let BeeError::Parse { message, location } = error else {
+            return error;
+        };
+        let suggestion = self
+            .get_mut_lexer()
+            .get_input()
+            .split_whitespace()
+            .find_map(|word| suggest_keyword(word).map(|keyword| (word.to_owned(), keyword)));
+        let message = match suggestion {
+            Some((word, keyword)) => {
+                let name = &word[..word.find(':').map_or(word.len(), |i| i + 1)];
+                format!(
+                    "{}. '{}' is not a keyword, did you mean '{}'?",
+                    message.trim_end_matches('.'),
+                    name,
+                    keyword
+                )
+            }
+            None => message,
+        };
+        BeeError::Parse { message, location }
+    }
+
     /// skip whitespace and return the number of whitespace characters skipped
     fn skip_whitespace(&mut self) -> usize {
         let mut blank_count = 0;
@@ -115,8 +195,9 @@ pub trait BaseParser: Debug {
         blank_count
     }
 
-    fn read_date_expr(&mut self) -> Result<DateTime<Local>, String> {
+    fn read_date_expr(&mut self) -> Result<DateTime<Local>, BeeError> {
         debug!("Reading date expression");
+        let start_span = self.get_current_token().span.to_owned();
         let mut time = None;
         let mut try_time = Local::now();
         let mut first = true;
@@ -144,53 +225,68 @@ pub trait BaseParser: Debug {
                     backtrace_tokens += self.skip_whitespace();
                     let unit_token = self.get_current_token().to_owned();
                     // expect a duration here
-                    let duration = match unit_token.literal {
+                    let offset = match unit_token.literal {
                         _ if matches_year_string(unit_token.literal.as_str()) => {
-                            get_day_duration_from_string(365, number_token.literal.as_str())
+                            let years = number_token.literal.parse::<u32>().unwrap_or(u32::MAX);
+                            DateOffset::Months(years.saturating_mul(12))
                         }
                         _ if matches_month_string(unit_token.literal.as_str()) => {
-                            get_day_duration_from_string(30, number_token.literal.as_str())
+                            DateOffset::Months(
+                                number_token.literal.parse::<u32>().unwrap_or(u32::MAX),
+                            )
                         }
                         _ if matches_week_string(unit_token.literal.as_str()) => {
-                            get_day_duration_from_string(7, number_token.literal.as_str())
+                            DateOffset::Duration(get_day_duration_from_string(
+                                7,
+                                number_token.literal.as_str(),
+                            ))
                         }
                         _ if matches_day_string(unit_token.literal.as_str()) => {
-                            get_day_duration_from_string(1, number_token.literal.as_str())
+                            DateOffset::Duration(get_day_duration_from_string(
+                                1,
+                                number_token.literal.as_str(),
+                            ))
                         }
                         _ if matches_hour_string(unit_token.literal.as_str()) => {
-                            Duration::try_hours(
-                                number_token.literal.parse::<i64>().unwrap().to_owned(),
+                            DateOffset::Duration(
+                                Duration::try_hours(
+                                    number_token.literal.parse::<i64>().unwrap().to_owned(),
+                                )
+                                .unwrap(),
                             )
-                            .unwrap()
                         }
                         _ if matches_minute_string(unit_token.literal.as_str()) => {
-                            Duration::try_minutes(
-                                number_token.literal.parse::<i64>().unwrap().to_owned(),
+                            DateOffset::Duration(
+                                Duration::try_minutes(
+                                    number_token.literal.parse::<i64>().unwrap().to_owned(),
+                                )
+                                .unwrap(),
                             )
-                            .unwrap()
                         }
                         _ if matches_second_string(unit_token.literal.as_str()) => {
-                            Duration::try_seconds(
-                                number_token.literal.parse::<i64>().unwrap().to_owned(),
+                            DateOffset::Duration(
+                                Duration::try_seconds(
+                                    number_token.literal.parse::<i64>().unwrap().to_owned(),
+                                )
+                                .unwrap(),
                             )
-                            .unwrap()
                         }
                         _ => {
                             break;
                         }
                     };
-                    if first {
-                        try_time = Local::now() - duration;
+                    let shifted = if first {
+                        offset.shift(Local::now(), false)
                     } else {
-                        match cur_scope {
-                            Scope::Minus => {
-                                try_time -= duration;
-                            }
-                            Scope::Plus => {
-                                try_time += duration;
-                            }
+                        offset.shift(try_time, cur_scope == Scope::Plus)
+                    };
+                    try_time = match shifted {
+                        Some(shifted) => shifted,
+                        None => {
+                            let span = number_token.span.start..unit_token.span.end;
+                            return Err(self.error_at(span, "The date is out of range".to_string()));
                         }
-                    }
+                    };
 
                     expect_duration = false;
                     time = Some(try_time.to_owned());
@@ -211,18 +307,20 @@ pub trait BaseParser: Debug {
                 }
                 TokenType::TagPlusPrefix | TokenType::TagMinusPrefix => {
                     if first {
-                        return Err(format!(
-                            "unexpected token '{}' found in invalid date expression",
-                            self.get_current_token().literal
+                        let token = self.get_current_token().to_owned();
+                        return Err(self.error_at(
+                            token.span.to_owned(),
+                            format!("Unexpected {} in the date expression", token.describe()),
                         ));
                     }
                     if expect_duration {
                         if time.is_some() {
                             break;
                         }
-                        return Err(format!(
-                            "unexpected token '{}' found in invalid date expression",
-                            self.get_current_token().literal
+                        let token = self.get_current_token().to_owned();
+                        return Err(self.error_at(
+                            token.span.to_owned(),
+                            format!("Unexpected {} in the date expression", token.describe()),
                         ));
                     }
                     debug!("Read plus token '{}'", self.get_current_token().literal);
@@ -254,7 +352,8 @@ pub trait BaseParser: Debug {
                         )
                         .single()
                         .unwrap();
-                    match self.get_current_token().literal.as_str() {
+                    let literal = self.get_current_token().literal.to_owned();
+                    match literal.as_str() {
                         "now" => {
                             try_time = now;
                         }
@@ -270,8 +369,49 @@ pub trait BaseParser: Debug {
                         "eod" => {
                             try_time = today_start + Duration::try_hours(18).unwrap();
                         }
+                        "sow" => try_time = start_of_week(now.date_naive()),
+                        "eow" => {
+                            let next_week = start_of_week(now.date_naive()) + Duration::days(7);
+                            try_time = next_week - Duration::seconds(1);
+                        }
+                        "som" => try_time = start_of_month(now.date_naive()),
+                        "eom" => {
+                            let next_month = start_of_month(now.date_naive()) + Months::new(1);
+                            try_time = next_month - Duration::seconds(1);
+                        }
+                        "soy" => try_time = start_of_year(now.date_naive()),
+                        "eoy" => {
+                            let next_year = start_of_year(now.date_naive()) + Months::new(12);
+                            try_time = next_year - Duration::seconds(1);
+                        }
+                        "next" => {
+                            // The day of the next week, e.g. 'next friday'
+                            self.next_token();
+                            self.skip_whitespace();
+                            let token = self.get_current_token().to_owned();
+                            let Ok(weekday) = token.literal.parse::<Weekday>() else {
+                                return Err(self.error_at(
+                                    token.span.to_owned(),
+                                    format!(
+                                        "Expected a day of the week after 'next', found {}",
+                                        token.describe()
+                                    ),
+                                ));
+                            };
+                            try_time = start_of_week(now.date_naive())
+                                + Duration::days(7 + weekday.num_days_from_monday() as i64);
+                        }
+                        _ if let Ok(weekday) = literal.parse::<Weekday>() => {
+                            // The next one, never today
+                            let today = now.weekday().num_days_from_monday() as i64;
+                            let days =
+                                (weekday.num_days_from_monday() as i64 - today).rem_euclid(7);
+                            let days = if days == 0 { 7 } else { days };
+                            try_time = today_start + Duration::days(days);
+                        }
                         "in" => {
                             expect_duration = true;
+                            cur_scope = Scope::Plus;
                             self.next_token();
                             backtrace_tokens += 1 + self.skip_whitespace();
                             in_keyword = true;
@@ -279,9 +419,10 @@ pub trait BaseParser: Debug {
                         }
                         // last week
                         _ => {
-                            return Err(format!(
-                                "unexpected token '{}' found in invalid date expression",
-                                self.get_current_token().literal
+                            let token = self.get_current_token().to_owned();
+                            return Err(self.error_at(
+                                token.span.to_owned(),
+                                format!("Unexpected {} in the date expression", token.describe()),
                             ));
                         }
                     }
@@ -290,6 +431,28 @@ pub trait BaseParser: Debug {
                     backtrace_tokens = 0;
                     self.next_token();
                 }
+                TokenType::Date => {
+                    if !first {
+                        break;
+                    }
+                    first = false;
+
+                    let token = self.get_current_token().to_owned();
+                    debug!("Read Date token '{}'", token.literal);
+                    let date = parse_iso_date(&token.literal).unwrap();
+                    try_time = match Local.from_local_datetime(&date).earliest() {
+                        Some(time) => time,
+                        None => {
+                            return Err(self.error_at(
+                                token.span.to_owned(),
+                                format!("The time {} doesn't exist locally", token.describe()),
+                            ));
+                        }
+                    };
+                    time = Some(try_time.to_owned());
+                    backtrace_tokens = 0;
+                    self.next_token();
+                }
                 TokenType::Blank => {
                     self.next_token();
                 }
@@ -302,13 +465,86 @@ pub trait BaseParser: Debug {
         }
         self.back_n_tokens(backtrace_tokens);
         if time.is_none() {
-            return Err("invalid date expression".to_string());
+            return Err(self.error_at(start_span, "Invalid date expression".to_string()));
         }
         debug!("Parsed date expression. Time: {:?}", time);
         Ok(time.unwrap())
     }
 }
 
+/// Parser reading a single date expression
+#[derive(Debug)]
+struct DateParser {
+    lexer: Lexer,
+    current_token: Token,
+    peek_token: Token,
+    buffer_tokens: Vec<Token>,
+    buffer_index: usize,
+}
+
+impl BaseParser for DateParser {
+    fn get_buffer_index(&self) -> usize {
+        self.buffer_index
+    }
+    fn set_buffer_index(&mut self, value: usize) {
+        self.buffer_index = value;
+    }
+
+    fn get_current_token(&self) -> &Token {
+        &self.current_token
+    }
+    fn set_current_token(&mut self, tok: Token) {
+        self.current_token = tok;
+    }
+
+    fn get_buffer_tokens(&self) -> &Vec<Token> {
+        &self.buffer_tokens
+    }
+    fn get_mut_buffer_tokens(&mut self) -> &mut Vec<Token> {
+        &mut self.buffer_tokens
+    }
+
+    fn get_peek_token(&self) -> &Token {
+        &self.peek_token
+    }
+    fn set_peek_token(&mut self, tok: Token) {
+        self.peek_token = tok;
+    }
+
+    fn get_mut_lexer(&mut self) -> &mut Lexer {
+        &mut self.lexer
+    }
+}
+
+/// Parse a date expression given on its own, e.g. 'yesterday' or '2 weeks ago'
+pub fn parse_date(input: &str) -> Result<DateTime<Local>, BeeError> {
+    let mut parser = DateParser {
+        lexer: Lexer::new(input.to_owned()),
+        current_token: Token::default(),
+        peek_token: Token::default(),
+        buffer_tokens: Vec::default(),
+        buffer_index: 0,
+    };
+    parser.next_token();
+    parser.next_token();
+    parser.buffer_index = 0;
+
+    parser.skip_whitespace();
+    let time = parser
+        .read_date_expr()
+        .map_err(|e| e.with_prefix(&format!("Invalid date '{}': ", input)))?;
+    parser.next_token();
+    parser.skip_whitespace();
+    if parser.current_token.token_type != TokenType::Eof {
+        let token = parser.current_token.to_owned();
+        return Err(parser.error_at(
+            token.span.to_owned(),
+            format!("Invalid date '{}': unexpected {}", input, token.describe()),
+        ));
+    }
+    Ok(time)
+}
+
 #[cfg(test)]
 #[path = "parser_test.rs"]
 mod parser_test;
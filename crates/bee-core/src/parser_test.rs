@@ -189,7 +189,7 @@ fn test_read_date_expr() {
     // This format doesn't print smaller units than seconds
     assert_eq!(
         res.to_rfc2822(),
-        (today_start - Duration::try_days(90).unwrap()).to_rfc2822()
+        (today_start - Months::new(3)).to_rfc2822()
     );
 
     let lexer = Lexer::new("in 3 days ago".to_string());
@@ -199,7 +199,7 @@ fn test_read_date_expr() {
     // This format doesn't print smaller units than seconds
     assert_eq!(
         res.to_rfc2822(),
-        (now - Duration::try_days(3).unwrap()).to_rfc2822()
+        (now + Duration::try_days(3).unwrap()).to_rfc2822()
     );
     assert_eq!(p.current_token.token_type, TokenType::Blank);
     p.next_token();
@@ -213,7 +213,7 @@ fn test_read_date_expr() {
     // This format doesn't print smaller units than seconds
     assert_eq!(
         res.to_rfc2822(),
-        (today_start - Duration::try_days(365 * 3).unwrap()).to_rfc2822()
+        (today_start - Months::new(36)).to_rfc2822()
     );
 
     // Ensure we stop after seeing 'ago'
@@ -222,10 +222,7 @@ fn test_read_date_expr() {
 
     let res = p.read_date_expr().unwrap();
     // This format doesn't print smaller units than seconds
-    assert_eq!(
-        res.to_rfc2822(),
-        (now - Duration::try_days(365 * 3).unwrap()).to_rfc2822()
-    );
+    assert_eq!(res.to_rfc2822(), (now - Months::new(36)).to_rfc2822());
     p.skip_whitespace();
     assert_eq!(p.current_token.token_type, TokenType::WordString);
     assert_eq!(p.current_token.literal, "today".to_owned());
@@ -242,3 +239,123 @@ fn test_read_date_expr() {
     assert_eq!(p.peek_token.token_type, TokenType::WordString);
     assert_eq!(p.peek_token.literal, "foo".to_owned());
 }
+
+#[test]
+fn test_parse_date() {
+    init();
+    let now = Local::now();
+    let today_start = Local
+        .from_local_datetime(
+            &now.date_naive()
+                .and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap()),
+        )
+        .single()
+        .unwrap();
+
+    assert_eq!(
+        parse_date("yesterday").unwrap(),
+        today_start - Duration::days(1)
+    );
+    assert_eq!(parse_date(" today ").unwrap(), today_start);
+
+    let two_weeks_ago = parse_date("2 weeks ago").unwrap();
+    let expected = now - Duration::days(14);
+    assert!((two_weeks_ago - expected).num_seconds().abs() < 5);
+
+    assert!(parse_date("").is_err());
+    assert!(parse_date("whenever").is_err());
+    assert!(parse_date("today +work").is_err());
+}
+
+#[test]
+fn test_parse_absolute_date() {
+    init();
+    let at = |y, m, d, h, min| {
+        Local
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(y, m, d)
+                    .unwrap()
+                    .and_hms_opt(h, min, 0)
+                    .unwrap(),
+            )
+            .single()
+            .unwrap()
+    };
+    assert_eq!(parse_date("2025-03-14").unwrap(), at(2025, 3, 14, 0, 0));
+    assert_eq!(
+        parse_date("2025-03-14T09:30").unwrap(),
+        at(2025, 3, 14, 9, 30)
+    );
+    assert_eq!(
+        parse_date("2025-03-14T09:30:15").unwrap(),
+        at(2025, 3, 14, 9, 30) + Duration::seconds(15)
+    );
+    // The months and years follow the calendar
+    assert_eq!(
+        parse_date("2025-01-31 + 1 month").unwrap(),
+        at(2025, 2, 28, 0, 0)
+    );
+    assert_eq!(
+        parse_date("2024-02-29 + 1 year").unwrap(),
+        at(2025, 2, 28, 0, 0)
+    );
+    assert_eq!(
+        parse_date("2025-03-14 - 2 months").unwrap(),
+        at(2025, 1, 14, 0, 0)
+    );
+    assert!(parse_date("2025-02-30").is_err());
+    assert!(parse_date("2025-03-14 + 99999999999 years").is_err());
+}
+
+#[test]
+fn test_parse_calendar_date() {
+    init();
+    let today = Local::now().date_naive();
+    let midnight = |date: NaiveDate| {
+        Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+            .single()
+            .unwrap()
+    };
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+
+    assert_eq!(parse_date("sow").unwrap(), midnight(monday));
+    assert_eq!(
+        parse_date("eow").unwrap(),
+        midnight(monday + Duration::days(7)) - Duration::seconds(1)
+    );
+    assert_eq!(
+        parse_date("som").unwrap(),
+        midnight(today.with_day(1).unwrap())
+    );
+    assert_eq!(
+        parse_date("eom").unwrap(),
+        midnight(today.with_day(1).unwrap() + Months::new(1)) - Duration::seconds(1)
+    );
+    assert_eq!(
+        parse_date("soy").unwrap(),
+        midnight(today.with_ordinal(1).unwrap())
+    );
+    assert_eq!(
+        parse_date("eoy").unwrap(),
+        midnight(today.with_ordinal(1).unwrap() + Months::new(12)) - Duration::seconds(1)
+    );
+
+    // A day of the week is the next one, never today
+    let friday = parse_date("friday").unwrap().date_naive();
+    assert_eq!(friday.weekday(), Weekday::Fri);
+    assert!(friday > today && friday <= today + Duration::days(7));
+    assert_eq!(
+        parse_date("next friday").unwrap(),
+        midnight(monday + Duration::days(11))
+    );
+    assert!(parse_date("next week").is_err());
+
+    let in_two_months = parse_date("in 2 months").unwrap();
+    assert!(
+        (in_two_months - (Local::now() + Months::new(2)))
+            .num_seconds()
+            .abs()
+            < 5
+    );
+}
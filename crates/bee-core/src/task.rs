@@ -1,8 +1,27 @@
+mod grouping;
+mod ical;
+mod jira;
+mod recurrence;
+mod sort;
 mod task_prop_parser;
+mod taskwarrior;
+mod throughput;
+mod time_tracking;
 
 use log::trace;
 use task_prop_parser::TaskPropertyParser;
 
+pub use grouping::{GroupAggregate, GroupBy, ProjectSummary};
+pub use ical::{ICalTodo, parse_ical, to_ical};
+pub use jira::{JIRA_FIELDS, JiraIssue, JiraSearchResult, JiraStatusCategory, JiraSync};
+pub use recurrence::{Recurrence, RecurrenceUnit};
+pub use sort::{SortKey, compare_tasks, parse_sort_keys};
+pub use taskwarrior::{
+    ImportStatus, TaskImport, TaskwarriorAnnotation, TaskwarriorTask, parse_taskwarrior_export,
+};
+pub use throughput::{Throughput, ThroughputBucket, ThroughputPeriod};
+pub use time_tracking::{TimeInterval, Timesheet, TimesheetTask, format_duration};
+
 use std::{cmp::Ordering, collections::HashSet, fmt};
 
 use chrono::prelude::DateTime;
@@ -13,6 +32,8 @@ use chrono::Local;
 use serde::{Deserialize, Deserializer, Serialize, ser::Serializer};
 use std::collections::HashMap;
 
+use crate::BeeError;
+use crate::config::CoeffientField;
 use crate::filters::Filter;
 use crate::lexer::Lexer;
 
@@ -41,13 +62,16 @@ pub enum TaskStatus {
 }
 
 impl TaskStatus {
-    pub fn from_string(input: &str) -> Result<TaskStatus, String> {
+    pub fn from_string(input: &str) -> Result<TaskStatus, BeeError> {
         match input.to_lowercase().as_str() {
             "active" => Ok(TaskStatus::Active),
             "pending" => Ok(TaskStatus::Pending),
             "completed" => Ok(TaskStatus::Completed),
             "deleted" => Ok(TaskStatus::Deleted),
-            _ => Err("Invalid task status name".to_string()),
+            _ => Err(BeeError::parse(format!(
+                "Invalid status '{}', expected active, pending, completed or deleted",
+                input
+            ))),
         }
     }
 }
@@ -63,6 +87,43 @@ impl fmt::Display for TaskStatus {
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Priority {
+    #[serde(rename = "L")]
+    Low,
+    #[serde(rename = "M")]
+    Medium,
+    #[serde(rename = "H")]
+    High,
+}
+
+impl Priority {
+    /// Accepts H, M and L (or their full name), and the levels 1 (low) to 3 (high)
+    pub fn from_string(input: &str) -> Result<Priority, BeeError> {
+        match input.to_lowercase().as_str() {
+            "l" | "low" | "1" => Ok(Priority::Low),
+            "m" | "medium" | "2" => Ok(Priority::Medium),
+            "h" | "high" | "3" => Ok(Priority::High),
+            _ => Err(BeeError::parse(format!(
+                "Invalid priority '{}', expected H, M, L or a level from 1 to 3",
+                input
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Priority::Low => write!(f, "L"),
+            Priority::Medium => write!(f, "M"),
+            Priority::High => write!(f, "H"),
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum DependsOnIdentifier {
     Usize(usize),
@@ -84,6 +145,9 @@ pub struct TaskProperties {
     annotation: Option<String>,
     /// Replace all of this task's annotations with the given vector
     annotations: Option<Vec<TaskAnnotation>>,
+    /// If present, sets the task's description to the given Option<String>
+    #[serde(default)]
+    description: Option<Option<String>>,
     active_status: Option<bool>,
     /// If presents, sets the task's project to the given
     /// Option<Project>
@@ -91,6 +155,13 @@ pub struct TaskProperties {
     #[serde(default)]
     date_due: Option<DateTime<chrono::Local>>,
     depends_on: Option<Vec<DependsOnIdentifier>>,
+    /// If present, sets the task's recurrence to the given
+    /// Option<Recurrence>
+    #[serde(default)]
+    recurrence: Option<Option<Recurrence>>,
+    /// If present, sets the task's priority to the given Option<Priority>
+    #[serde(default)]
+    priority: Option<Option<Priority>>,
 }
 
 // We implement a specific function for annotate because we cannot know how to differenciate
@@ -108,6 +179,10 @@ impl TaskProperties {
         self.summary = Some(summary.to_string());
     }
 
+    pub fn set_description(&mut self, description: &Option<String>) {
+        self.description = Some(description.to_owned());
+    }
+
     pub fn set_project(&mut self, project: &Option<Project>) {
         self.project = Some(project.clone());
     }
@@ -138,12 +213,24 @@ impl TaskProperties {
         self.active_status = Some(status);
     }
 
-    pub fn from(values: &[String]) -> Result<TaskProperties, String> {
+    pub fn from(values: &[String]) -> Result<TaskProperties, BeeError> {
         let lexer = Lexer::new(values.join(" "));
         let mut parser = TaskPropertyParser::new(lexer);
         parser.parse_task_properties()
     }
 
+    pub fn get_summary(&self) -> &Option<String> {
+        &self.summary
+    }
+
+    pub fn get_tags_add(&self) -> &Option<Vec<String>> {
+        &self.tags_add
+    }
+
+    pub fn get_project(&self) -> &Option<Option<Project>> {
+        &self.project
+    }
+
     pub fn get_referenced_tasks(&self) -> Vec<DependsOnIdentifier> {
         match &self.depends_on {
             Some(deps) => deps.to_owned(),
@@ -187,6 +274,9 @@ pub struct Link {
     link_type: LinkType,
 }
 
+/// The event added to the history of a task when it is deleted
+const DELETED_HISTORY_VALUE: &str = "Deleted task.";
+
 /// This struct contains a description of what happened to a task,
 /// and when that event happened as well.
 #[derive(
@@ -204,6 +294,10 @@ pub struct Task {
     uuid: Uuid,
     summary: String,
 
+    /// Notes about the task, in markdown. Unlike the summary it can span multiple lines.
+    #[serde(default)]
+    description: Option<String>,
+
     #[serde(default)]
     annotations: Vec<TaskAnnotation>,
 
@@ -231,6 +325,21 @@ pub struct Task {
     /// All the events that have happened to a task after its creation
     #[serde(default)]
     history: Vec<TaskHistory>,
+
+    /// When set, completing this task creates its next occurrence
+    #[serde(default)]
+    recurrence: Option<Recurrence>,
+
+    #[serde(default)]
+    priority: Option<Priority>,
+
+    /// The periods during which the task was active
+    #[serde(default)]
+    intervals: Vec<TimeInterval>,
+
+    /// The key of the Jira issue the task is synchronised with
+    #[serde(default)]
+    external_id: Option<String>,
 }
 
 impl PartialOrd for Task {
@@ -288,7 +397,12 @@ impl Task {
         self.id
     }
 
-    pub fn get_urgency(&mut self) -> Result<i64, String> {
+    /// The urgency of the task if it has been computed already
+    pub fn get_computed_urgency(&self) -> Option<i64> {
+        self.urgency
+    }
+
+    pub fn get_urgency(&mut self) -> Result<i64, BeeError> {
         if let Some(urgency) = self.urgency {
             return Ok(urgency);
         }
@@ -296,14 +410,23 @@ impl Task {
         self.compute_urgency()
     }
 
-    fn compute_urgency(&mut self) -> Result<i64, String> {
+    fn compute_urgency(&mut self) -> Result<i64, BeeError> {
+        let conf = crate::config::get_config();
+        self.compute_urgency_with(&conf.coefficients)
+    }
+
+    fn compute_urgency_with(&mut self, coefficients: &[CoeffientField]) -> Result<i64, BeeError> {
         let mut urgency: i64 = 0;
         let mut blocking_coef = 1;
         let mut depends_coef = -1;
         let mut active_status_coef = 10;
+        let mut priority_coefs: HashMap<Priority, i64> = HashMap::from([
+            (Priority::High, 6),
+            (Priority::Medium, 4),
+            (Priority::Low, 2),
+        ]);
 
-        let conf = crate::config::get_config();
-        for coef_field in conf.coefficients.iter() {
+        for coef_field in coefficients.iter() {
             match coef_field.field.as_str() {
                 "tag" => {
                     if let Some(tag_value) = &coef_field.value {
@@ -323,12 +446,29 @@ impl Task {
                 "active_status" => {
                     active_status_coef = coef_field.coefficient;
                 }
+                "priority" => {
+                    // Without a value, the coefficient applies to every priority
+                    if let Some(priority_value) = &coef_field.value {
+                        let priority = Priority::from_string(priority_value).map_err(|e| {
+                            BeeError::Config(format!(
+                                "Error parsing the coefficient field in the configuration file. {}",
+                                e
+                            ))
+                        })?;
+                        priority_coefs.insert(priority, coef_field.coefficient);
+                    } else {
+                        priority_coefs
+                            .values_mut()
+                            .for_each(|c| *c = coef_field.coefficient);
+                    }
+                }
                 _ => {
-                    return Err(format!(
+                    return Err(BeeError::Config(format!(
                         "Error parsing the coefficient field in the configuration file. \
-                            '{}' is not a valid 'field' name. Valid field names are: 'tag', 'depends', 'blocking'",
+                            '{}' is not a valid 'field' name. Valid field names are: 'tag', 'depends', 'blocking', \
+                            'active_status', 'priority'",
                         coef_field.field
-                    ));
+                    )));
                 }
             }
         }
@@ -340,6 +480,10 @@ impl Task {
             urgency += active_status_coef;
         }
 
+        if let Some(priority) = &self.priority {
+            urgency += priority_coefs[priority];
+        }
+
         // Compute number of days remaining until the due date
         if let Some(date_due) = self.date_due {
             let now = Local::now();
@@ -359,6 +503,10 @@ impl Task {
         &self.annotations
     }
 
+    pub fn get_description(&self) -> &Option<String> {
+        &self.description
+    }
+
     pub fn get_summary(&self) -> &str {
         &self.summary
     }
@@ -392,10 +540,81 @@ impl Task {
         &self.date_due
     }
 
+    /// When a deleted task was deleted, according to its history. None for the tasks
+    /// that are not deleted, or were deleted without leaving a trace, e.g. imported.
+    pub fn get_date_deleted(&self) -> Option<DateTime<Local>> {
+        if self.status != TaskStatus::Deleted {
+            return None;
+        }
+        self.history
+            .iter()
+            .rev()
+            .find(|event| {
+                event.value == DELETED_HISTORY_VALUE || event.value.ends_with("to 'deleted'")
+            })
+            .map(|event| event.time)
+    }
+
     pub fn get_uuid(&self) -> &Uuid {
         &self.uuid
     }
 
+    pub fn get_recurrence(&self) -> &Option<Recurrence> {
+        &self.recurrence
+    }
+
+    pub fn get_priority(&self) -> &Option<Priority> {
+        &self.priority
+    }
+
+    pub fn get_intervals(&self) -> &Vec<TimeInterval> {
+        &self.intervals
+    }
+
+    pub fn get_external_id(&self) -> &Option<String> {
+        &self.external_id
+    }
+
+    /// The total time the task was active, up to `now` if it still is
+    pub fn get_time_tracked(&self, now: DateTime<Local>) -> chrono::Duration {
+        self.intervals
+            .iter()
+            .map(|i| i.end.unwrap_or(now) - i.start)
+            .sum()
+    }
+
+    /// The time the task was active between `from` and `to`
+    pub fn get_time_tracked_between(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+        now: DateTime<Local>,
+    ) -> chrono::Duration {
+        self.intervals
+            .iter()
+            .map(|i| i.duration_between(from, to, now))
+            .sum()
+    }
+
+    fn start_interval(&mut self, time: DateTime<Local>) {
+        if self.intervals.last().is_some_and(|i| i.end.is_none()) {
+            return;
+        }
+        self.intervals.push(TimeInterval {
+            start: time,
+            end: None,
+        });
+    }
+
+    /// Close the interval that is still open, if any
+    fn stop_interval(&mut self, time: DateTime<Local>) {
+        if let Some(interval) = self.intervals.last_mut()
+            && interval.end.is_none()
+        {
+            interval.end = Some(time);
+        }
+    }
+
     /// Send back a list of the UUID that this task knows about or refers to
     pub fn get_extra_uuid(&self) -> Vec<Uuid> {
         let mut uuids = [
@@ -411,7 +630,7 @@ impl Task {
         uuids
     }
 
-    pub fn apply(&mut self, props: &TaskProperties) -> Result<(), String> {
+    pub fn apply(&mut self, props: &TaskProperties) -> Result<(), BeeError> {
         if let Some(summary) = &props.summary {
             self.history.push(TaskHistory {
                 time: Local::now(),
@@ -431,24 +650,26 @@ impl Task {
         if let Some(active) = &props.active_status {
             if *active {
                 if self.status != TaskStatus::Pending {
-                    return Err(format!(
+                    return Err(BeeError::InvalidTransition(format!(
                         "Task '{}' status cannot be set to 'ACTIVE' because its status is already 'ACTIVE'",
                         self.summary
-                    ));
+                    )));
                 }
                 self.status = TaskStatus::Active;
+                self.start_interval(Local::now());
                 self.history.push(TaskHistory {
                     time: Local::now(),
                     value: "Status changed from 'PENDING' to 'ACTIVE'".to_string(),
                 });
             } else {
                 if self.status != TaskStatus::Active {
-                    return Err(format!(
+                    return Err(BeeError::InvalidTransition(format!(
                         "Task '{}' status cannot be 'stopped' because its status is not 'ACTIVE'",
                         self.summary
-                    ));
+                    )));
                 }
                 self.status = TaskStatus::Pending;
+                self.stop_interval(Local::now());
                 self.history.push(TaskHistory {
                     time: Local::now(),
                     value: "Status changed from 'ACTIVE' to 'PENDING'".to_string(),
@@ -462,6 +683,11 @@ impl Task {
                     time: Local::now(),
                     value: format!("Status changed from '{}' to '{}'", self.status, status),
                 });
+                if status == &TaskStatus::Active {
+                    self.start_interval(Local::now());
+                } else {
+                    self.stop_interval(Local::now());
+                }
             }
             self.status = status.to_owned();
         }
@@ -482,6 +708,30 @@ impl Task {
             }
         }
 
+        if let Some(recurrence) = &props.recurrence {
+            let value = match recurrence {
+                Some(r) => format!("Recurrence set to '{}'", r),
+                None => "Recurrence has been unset".to_string(),
+            };
+            self.history.push(TaskHistory {
+                time: Local::now(),
+                value,
+            });
+            self.recurrence = recurrence.to_owned();
+        }
+
+        if let Some(priority) = &props.priority {
+            let value = match priority {
+                Some(p) => format!("Priority set to '{}'", p),
+                None => "Priority has been unset".to_string(),
+            };
+            self.history.push(TaskHistory {
+                time: Local::now(),
+                value,
+            });
+            self.priority = priority.to_owned();
+        }
+
         if let Some(tags) = &props.tags_remove {
             let s: HashSet<String> = tags.iter().cloned().collect();
             let mut removed_tags: Vec<String> = Vec::new();
@@ -543,6 +793,18 @@ impl Task {
             self.annotations = annotations.to_owned();
         }
 
+        if let Some(description) = &props.description {
+            let value = match description {
+                Some(_) => "Description updated".to_string(),
+                None => "Description removed".to_string(),
+            };
+            self.history.push(TaskHistory {
+                time: Local::now(),
+                value,
+            });
+            self.description = description.to_owned();
+        }
+
         if let Some(depends_on) = &props.depends_on {
             let mut deps_set = HashSet::<Uuid>::new();
 
@@ -602,23 +864,66 @@ impl Task {
     pub fn delete(&mut self) {
         self.history.push(TaskHistory {
             time: Local::now(),
-            value: "Deleted task.".to_string(),
+            value: DELETED_HISTORY_VALUE.to_string(),
         });
         self.status = TaskStatus::Deleted;
+        self.stop_interval(Local::now());
         self.id = None;
         self.urgency = None;
     }
 
-    pub fn done(&mut self) {
+    /// Mark the task as completed.
+    ///
+    /// If the task is recurring and was not already completed or deleted, this returns
+    /// its next occurrence. It is up to the caller to store it. The task is left
+    /// unchanged when its next occurrence can't be computed.
+    pub fn done(&mut self) -> Result<Option<Task>, BeeError> {
         let current_time = Local::now();
+        let next_task = match self.status {
+            TaskStatus::Pending | TaskStatus::Active => self.next_occurrence(current_time)?,
+            _ => None,
+        };
         self.history.push(TaskHistory {
             time: current_time,
             value: "Marked task as done".to_string(),
         });
         self.status = TaskStatus::Completed;
+        self.stop_interval(current_time);
         self.date_completed = Some(current_time);
         self.id = None;
         self.urgency = None;
+
+        if let Some(next_task) = &next_task {
+            self.history.push(TaskHistory {
+                time: current_time,
+                value: format!("Created the next occurrence '{}'", next_task.uuid),
+            });
+        }
+        Ok(next_task)
+    }
+
+    fn next_occurrence(&self, completed: DateTime<Local>) -> Result<Option<Task>, BeeError> {
+        let Some(recurrence) = &self.recurrence else {
+            return Ok(None);
+        };
+        let date_due = recurrence.next_due(self.date_due, completed)?;
+        Ok(Some(Task {
+            uuid: Uuid::new_v4(),
+            status: TaskStatus::Pending,
+            summary: self.summary.to_owned(),
+            tags: self.tags.to_owned(),
+            project: self.project.to_owned(),
+            date_created: Local::now(),
+            date_due: Some(date_due),
+            recurrence: Some(recurrence.to_owned()),
+            priority: self.priority,
+            description: self.description.to_owned(),
+            history: vec![TaskHistory {
+                time: completed,
+                value: format!("Created as the next occurrence of '{}'", self.uuid),
+            }],
+            ..Task::default()
+        }))
     }
 }
 
@@ -673,6 +978,7 @@ impl TaskData {
 
     pub fn insert_id_to_uuid(&mut self, id: usize, uuid: Uuid) {
         self.id_to_uuid.insert(id, uuid);
+        self.max_id = self.max_id.max(id);
     }
 
     pub fn insert_extra_task(&mut self, task: Task) {
@@ -687,15 +993,161 @@ impl TaskData {
         self.tasks.values().collect()
     }
 
-    pub fn apply(&mut self, task_uuid: &Uuid, props: &TaskProperties) -> Result<(), String> {
+    pub fn iter(&self) -> impl Iterator<Item = &Task> {
+        self.tasks.values()
+    }
+
+    pub fn apply(&mut self, task_uuid: &Uuid, props: &TaskProperties) -> Result<(), BeeError> {
         if props.depends_on.is_none() {
             return self.tasks.get_mut(task_uuid).unwrap().apply(props);
         }
 
         let my_props = self.update_task_property_depends_on(props)?;
+        let new_depends_on: Vec<Uuid> = my_props
+            .depends_on
+            .iter()
+            .flatten()
+            .filter_map(|dep| match dep {
+                DependsOnIdentifier::Uuid(uuid) => Some(*uuid),
+                DependsOnIdentifier::Usize(_) => None,
+            })
+            .collect();
+        self.check_circular_dependency(task_uuid, &new_depends_on)?;
         self.tasks.get_mut(task_uuid).unwrap().apply(&my_props)
     }
 
+    fn get_any_task(&self, uuid: &Uuid) -> Option<&Task> {
+        self.tasks.get(uuid).or_else(|| self.extra_tasks.get(uuid))
+    }
+
+    /// Find a chain of dependencies going from `from` to `to`, using the loaded tasks.
+    /// The returned path starts with `from` and ends with `to`.
+    fn find_dependency_path(&self, from: &Uuid, to: &Uuid) -> Option<Vec<Uuid>> {
+        let mut parents = HashMap::<Uuid, Uuid>::default();
+        let mut visited = HashSet::from([*from]);
+        let mut stack = vec![*from];
+
+        while let Some(current) = stack.pop() {
+            if current == *to {
+                let mut path = vec![current];
+                while let Some(parent) = parents.get(path.last().unwrap()) {
+                    path.push(*parent);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            if let Some(task) = self.get_any_task(&current) {
+                for dep in task.get_depends_on() {
+                    if visited.insert(*dep) {
+                        parents.insert(*dep, current);
+                        stack.push(*dep);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns an error naming the whole cycle if making `task_uuid` depend on
+    /// `depends_on` would create a circular dependency.
+    fn check_circular_dependency(
+        &self,
+        task_uuid: &Uuid,
+        depends_on: &[Uuid],
+    ) -> Result<(), BeeError> {
+        for dep_uuid in depends_on {
+            if let Some(path) = self.find_dependency_path(dep_uuid, task_uuid) {
+                let cycle: Vec<Uuid> = std::iter::once(*task_uuid).chain(path).collect();
+                return Err(BeeError::InvalidCommand(format!(
+                    "Unable to add the dependency, it would create a circular dependency: {}",
+                    self.describe_dependency_path(&cycle)
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Format a list of tasks linked by their dependencies, e.g. "1 'foo' -> 2 'bar'"
+    pub fn describe_dependency_path(&self, path: &[Uuid]) -> String {
+        path.iter()
+            .map(|uuid| match self.get_any_task(uuid) {
+                Some(task) => match task.get_id() {
+                    Some(id) => format!("{} '{}'", id, task.get_summary()),
+                    None => format!("'{}'", task.get_summary()),
+                },
+                None => uuid.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+
+    /// Find the circular dependencies in between the loaded tasks. Each cycle starts
+    /// and ends with the same task.
+    pub fn find_dependency_cycles(&self) -> Vec<Vec<Uuid>> {
+        fn visit(
+            data: &TaskData,
+            uuid: Uuid,
+            stack: &mut Vec<Uuid>,
+            done: &mut HashSet<Uuid>,
+            cycles: &mut Vec<Vec<Uuid>>,
+        ) {
+            if let Some(pos) = stack.iter().position(|u| *u == uuid) {
+                let mut cycle = stack[pos..].to_vec();
+                // Start from the smallest UUID, so that a cycle is only reported once
+                let min_pos = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+                cycle.rotate_left(min_pos);
+                cycle.push(cycle[0]);
+                if !cycles.contains(&cycle) {
+                    cycles.push(cycle);
+                }
+                return;
+            }
+            if done.contains(&uuid) {
+                return;
+            }
+
+            stack.push(uuid);
+            if let Some(task) = data.get_any_task(&uuid) {
+                for dep in task.get_depends_on() {
+                    visit(data, *dep, stack, done, cycles);
+                }
+            }
+            stack.pop();
+            done.insert(uuid);
+        }
+
+        let mut uuids: Vec<Uuid> = self
+            .tasks
+            .keys()
+            .chain(self.extra_tasks.keys())
+            .cloned()
+            .collect();
+        uuids.sort_unstable();
+        uuids.dedup();
+
+        let mut cycles = Vec::new();
+        let mut done = HashSet::default();
+        for uuid in uuids {
+            visit(self, uuid, &mut Vec::new(), &mut done, &mut cycles);
+        }
+        cycles
+    }
+
+    /// Give an ID to a task imported as pending or active, and remove the one of a task
+    /// that was completed or deleted
+    fn update_id(&mut self, task: &mut Task) {
+        if task.id.is_none()
+            && (task.status == TaskStatus::Pending || task.status == TaskStatus::Active)
+        {
+            self.max_id += 1;
+            task.id = Some(self.max_id);
+        }
+        if task.status == TaskStatus::Completed || task.status == TaskStatus::Deleted {
+            task.id = None;
+        }
+    }
+
     pub fn get_owned(&self, uuid: &Uuid) -> Option<Task> {
         self.tasks.get(uuid).cloned()
     }
@@ -715,8 +1167,18 @@ impl TaskData {
         &self.undos
     }
 
-    pub fn task_done(&mut self, uuid: &Uuid) {
-        self.tasks.get_mut(uuid).unwrap().done();
+    /// Complete the task, and add its next occurrence if it is a recurring task.
+    /// The next occurrence is returned when one was created.
+    pub fn task_done(&mut self, uuid: &Uuid) -> Result<Option<&Task>, BeeError> {
+        let Some(mut next_task) = self.tasks.get_mut(uuid).unwrap().done()? else {
+            return Ok(None);
+        };
+        self.max_id += 1;
+        next_task.id = Some(self.max_id);
+
+        let next_uuid = next_task.uuid;
+        self.tasks.insert(next_uuid, next_task);
+        Ok(self.tasks.get(&next_uuid))
     }
 
     pub fn task_delete(&mut self, uuid: &Uuid) {
@@ -728,7 +1190,7 @@ impl TaskData {
     fn update_task_property_depends_on(
         &self,
         props: &TaskProperties,
-    ) -> Result<TaskProperties, String> {
+    ) -> Result<TaskProperties, BeeError> {
         if props.depends_on.is_none() {
             return Ok(props.clone());
         }
@@ -747,10 +1209,10 @@ impl TaskData {
                         new_depends_on.push(DependsOnIdentifier::Uuid(
                             self.id_to_uuid
                                 .get(id)
-                                .ok_or(format!(
+                                .ok_or(BeeError::UnknownTask(format!(
                                     "The given id {} doesn't correspond to any known task.",
                                     &id
-                                ))?
+                                )))?
                                 .to_owned(),
                         ));
                     }
@@ -761,11 +1223,11 @@ impl TaskData {
         Ok(my_props)
     }
 
-    pub fn upkeep(&mut self) -> Result<(), String> {
+    pub fn upkeep(&mut self) -> Result<(), BeeError> {
         let mut vec: Vec<_> = self.tasks.values().by_ref().collect();
 
         // Set the ID of the tasks by sorting them by date_created
-        vec.sort_by(|lhs, rhs| lhs.date_created.cmp(&rhs.date_created));
+        vec.sort_by_key(|lhs| lhs.date_created);
         let uuids: Vec<Uuid> = vec.iter().map(|t| t.uuid).collect();
         let mut i = 1;
         for cur_uuid in uuids {
@@ -781,6 +1243,13 @@ impl TaskData {
             }
         }
 
+        self.upkeep_links()
+    }
+
+    /// Update the urgency and the dependency links of the loaded tasks, without
+    /// changing their IDs. Unlike `upkeep`, this does not require all the tasks to be
+    /// loaded, only the ones linked to the loaded tasks.
+    pub fn upkeep_links(&mut self) -> Result<(), BeeError> {
         for t in self.tasks.values_mut() {
             t.compute_urgency()?;
         }
@@ -918,31 +1387,80 @@ impl TaskData {
             ..TaskData::clone(self)
         };
 
-        let mut extra_tasks = Vec::new();
+        let mut filter = filter.clone();
+        filter.resolve_links(self);
+
         for (key, task) in &self.tasks {
             if filter.validate_task(task) {
                 new_data.tasks.insert(key.to_owned(), task.to_owned());
-                for uuid_dep in &task.get_depends_on() {
-                    extra_tasks.push(self.tasks.get(uuid_dep).unwrap());
-                }
-                for uuid_dep in task.get_blocking() {
-                    extra_tasks.push(self.tasks.get(uuid_dep).unwrap());
-                }
             }
         }
 
-        for task in extra_tasks {
-            new_data.extra_tasks.insert(task.uuid, task.to_owned());
+        // Every task reachable from the filtered tasks is needed to keep their links
+        // up to date
+        let filtered_uuids: Vec<Uuid> = new_data.tasks.keys().cloned().collect();
+        for uuid in self.get_linked_tasks(&filtered_uuids) {
+            if let Some(task) = self.get_any_task(&uuid) {
+                new_data.extra_tasks.insert(uuid, task.to_owned());
+            }
         }
 
         new_data
     }
 
+    /// Follow the links given by `neighbours`, starting from the tasks in `start`.
+    /// The tasks in `start` are only part of the result if they can be reached from
+    /// another task.
+    fn collect_reachable<F>(&self, start: &[Uuid], neighbours: F) -> HashSet<Uuid>
+    where
+        F: Fn(&Task) -> Vec<Uuid>,
+    {
+        let mut reachable = HashSet::default();
+        let mut stack: Vec<Uuid> = start
+            .iter()
+            .filter_map(|uuid| self.get_any_task(uuid))
+            .flat_map(&neighbours)
+            .collect();
+
+        while let Some(uuid) = stack.pop() {
+            if reachable.insert(uuid)
+                && let Some(task) = self.get_any_task(&uuid)
+            {
+                stack.extend(neighbours(task));
+            }
+        }
+        reachable
+    }
+
+    /// All the tasks that can be reached from `uuids` by following the dependencies, in
+    /// both directions. The tasks of `uuids` are not part of the result.
+    pub fn get_linked_tasks(&self, uuids: &[Uuid]) -> HashSet<Uuid> {
+        let mut linked = self.collect_reachable(uuids, |task| task.get_extra_uuid());
+        for uuid in uuids {
+            linked.remove(uuid);
+        }
+        linked
+    }
+
+    /// The tasks `uuid` depends on, directly or through other tasks
+    pub fn get_transitive_dependencies(&self, uuid: &Uuid) -> HashSet<Uuid> {
+        self.collect_reachable(&[*uuid], |task| {
+            task.get_depends_on().into_iter().cloned().collect()
+        })
+    }
+
+    /// The tasks depending on `uuid`, directly or through other tasks
+    pub fn get_transitive_dependents(&self, uuid: &Uuid) -> HashSet<Uuid> {
+        self.collect_reachable(&[*uuid], |task| {
+            task.get_blocking().into_iter().cloned().collect()
+        })
+    }
+
     pub fn add_task(
         &mut self,
         props: &TaskProperties,
         status: TaskStatus,
-    ) -> Result<&Task, String> {
+    ) -> Result<&Task, BeeError> {
         // This allows the user to override the default status of the task being
         // created (defined by the caller of this function, usually Pending)
         let status = match &props.status {
@@ -974,7 +1492,11 @@ impl TaskData {
 
         let summary = match &props.summary {
             Some(summary) => summary.to_owned(),
-            None => return Err("A task must have a summary".to_owned()),
+            None => {
+                return Err(BeeError::InvalidCommand(
+                    "A task must have a summary".to_owned(),
+                ));
+            }
         };
 
         let tags = match &props.tags_add {
@@ -982,6 +1504,10 @@ impl TaskData {
             None => Vec::default(),
         };
 
+        let recurrence = props.recurrence.to_owned().unwrap_or_default();
+        let priority = props.priority.to_owned().unwrap_or_default();
+        let description = props.description.to_owned().unwrap_or_default();
+
         let links = match &props.depends_on {
             Some(_) => {
                 let my_props = self.update_task_property_depends_on(props)?;
@@ -1021,6 +1547,9 @@ impl TaskData {
             date_due,
             project,
             links,
+            recurrence,
+            priority,
+            description,
             ..Task::default()
         };
         let owned_uuid = t.get_uuid().to_owned();
@@ -1035,7 +1564,7 @@ impl Serialize for TaskData {
         S: Serializer,
     {
         let mut tasks: Vec<&Task> = self.tasks.values().collect();
-        tasks.sort_by(|lhs, rhs| lhs.date_created.cmp(&rhs.date_created));
+        tasks.sort_by_key(|lhs| lhs.date_created);
         tasks.serialize(serializer)
     }
 }
@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Duration, Local};
+use serde::Deserialize;
+
+use super::{Task, TaskStatus, format_duration};
+
+/// What the tasks of a report are grouped on, e.g. `group_by = "due_week"`
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    Tag,
+    Status,
+    /// The ISO week of the due date, e.g. '2025-W11'
+    DueWeek,
+    /// The month of the creation date, e.g. '2025-03'
+    CreatedMonth,
+    /// The first part of the project, 'work' for 'work.release'
+    Project,
+}
+
+impl GroupBy {
+    /// The names of the groups the task belongs to. A task with several tags is in
+    /// the group of each of them, and a task without a value in none.
+    pub fn get_group_names(&self, task: &Task) -> Vec<String> {
+        match self {
+            GroupBy::Tag => task.get_tags().to_owned(),
+            GroupBy::Status => vec![task.get_status().to_string()],
+            GroupBy::DueWeek => task
+                .get_date_due()
+                .iter()
+                .map(|due| {
+                    let week = due.iso_week();
+                    format!("{}-W{:02}", week.year(), week.week())
+                })
+                .collect(),
+            GroupBy::CreatedMonth => vec![task.get_date_created().format("%Y-%m").to_string()],
+            GroupBy::Project => task
+                .get_project()
+                .iter()
+                .filter_map(|project| project.get_name().split('.').next())
+                .map(|name| name.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// The totals of a group of tasks, shown in the header of the group
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupAggregate {
+    pub count: usize,
+    pub time_tracked: Duration,
+    pub earliest_due: Option<DateTime<Local>>,
+}
+
+impl GroupAggregate {
+    pub fn new<'a>(tasks: impl Iterator<Item = &'a Task>, now: DateTime<Local>) -> GroupAggregate {
+        let mut aggregate = GroupAggregate {
+            count: 0,
+            time_tracked: Duration::zero(),
+            earliest_due: None,
+        };
+        for task in tasks {
+            aggregate.count += 1;
+            aggregate.time_tracked += task.get_time_tracked(now);
+            if let Some(due) = task.get_date_due()
+                && aggregate
+                    .earliest_due
+                    .is_none_or(|earliest| *due < earliest)
+            {
+                aggregate.earliest_due = Some(*due);
+            }
+        }
+        aggregate
+    }
+}
+
+impl fmt::Display for GroupAggregate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} task{}",
+            self.count,
+            if self.count == 1 { "" } else { "s" }
+        )?;
+        if self.time_tracked.num_minutes() > 0 {
+            write!(f, ", {} tracked", format_duration(self.time_tracked))?;
+        }
+        if let Some(due) = self.earliest_due {
+            write!(f, ", first due {}", due.format("%Y-%m-%d"))?;
+        }
+        Ok(())
+    }
+}
+
+/// The completion of a project, as shown by 'summary'
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSummary {
+    /// The full name of the project, empty for the tasks without one
+    pub name: String,
+    /// 0 for a top-level project, 1 for its sub-projects, and so on
+    pub depth: usize,
+    /// The pending and active tasks
+    pub remaining: usize,
+    pub completed: usize,
+}
+
+impl ProjectSummary {
+    /// The percentage of the tasks that are completed, rounded down
+    pub fn get_completion(&self) -> usize {
+        let total = self.remaining + self.completed;
+        if total == 0 {
+            return 0;
+        }
+        self.completed * 100 / total
+    }
+
+    /// Summarise the projects of the tasks, each one followed by its sub-projects. The
+    /// tasks of a sub-project count for its parents as well, and the deleted tasks are
+    /// ignored. Like Taskwarrior, the projects without remaining tasks are left out.
+    pub fn from_tasks<'a>(tasks: impl Iterator<Item = &'a Task>) -> Vec<ProjectSummary> {
+        let mut counts: BTreeMap<Vec<String>, (usize, usize)> = BTreeMap::new();
+        for task in tasks {
+            let (remaining, completed) = match task.get_status() {
+                TaskStatus::Pending | TaskStatus::Active => (1, 0),
+                TaskStatus::Completed => (0, 1),
+                TaskStatus::Deleted => continue,
+            };
+
+            let parts: Vec<String> = match task.get_project() {
+                Some(project) => project
+                    .get_name()
+                    .split('.')
+                    .map(|part| part.to_string())
+                    .collect(),
+                None => vec![String::new()],
+            };
+            for depth in 1..=parts.len() {
+                let entry = counts.entry(parts[..depth].to_vec()).or_insert((0, 0));
+                entry.0 += remaining;
+                entry.1 += completed;
+            }
+        }
+
+        counts
+            .into_iter()
+            .filter(|(_, (remaining, _))| *remaining > 0)
+            .map(|(parts, (remaining, completed))| ProjectSummary {
+                name: parts.join("."),
+                depth: parts.len() - 1,
+                remaining,
+                completed,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "grouping_test.rs"]
+mod grouping_test;
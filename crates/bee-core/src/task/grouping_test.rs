@@ -0,0 +1,103 @@
+use chrono::TimeZone;
+
+use super::*;
+use crate::task::{TaskData, TaskProperties};
+
+fn add_task(data: &mut TaskData, arguments: &str, status: TaskStatus) -> Task {
+    data.add_task(
+        &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+        status,
+    )
+    .unwrap()
+    .clone()
+}
+
+#[test]
+fn test_get_group_names() {
+    let mut data = TaskData::default();
+    let task = add_task(
+        &mut data,
+        "release +team +urgent project:work.release due:2025-03-13",
+        TaskStatus::Pending,
+    );
+
+    assert_eq!(GroupBy::Tag.get_group_names(&task), vec!["team", "urgent"]);
+    assert_eq!(GroupBy::Status.get_group_names(&task), vec!["pending"]);
+    assert_eq!(GroupBy::DueWeek.get_group_names(&task), vec!["2025-W11"]);
+    assert_eq!(GroupBy::Project.get_group_names(&task), vec!["work"]);
+    assert_eq!(
+        GroupBy::CreatedMonth.get_group_names(&task),
+        vec![task.get_date_created().format("%Y-%m").to_string()]
+    );
+
+    let task = add_task(&mut data, "nothing", TaskStatus::Pending);
+    assert!(GroupBy::Tag.get_group_names(&task).is_empty());
+    assert!(GroupBy::DueWeek.get_group_names(&task).is_empty());
+    assert!(GroupBy::Project.get_group_names(&task).is_empty());
+}
+
+#[test]
+fn test_group_aggregate() {
+    let mut data = TaskData::default();
+    let tasks = [
+        add_task(&mut data, "a due:2025-03-20", TaskStatus::Pending),
+        add_task(&mut data, "b due:2025-03-13", TaskStatus::Pending),
+        add_task(&mut data, "c", TaskStatus::Pending),
+    ];
+
+    let aggregate = GroupAggregate::new(tasks.iter(), Local::now());
+    assert_eq!(aggregate.count, 3);
+    assert_eq!(aggregate.time_tracked, Duration::zero());
+    assert_eq!(
+        aggregate.earliest_due,
+        Some(Local.with_ymd_and_hms(2025, 3, 13, 0, 0, 0).unwrap())
+    );
+    assert_eq!(aggregate.to_string(), "3 tasks, first due 2025-03-13");
+
+    let aggregate = GroupAggregate {
+        count: 1,
+        time_tracked: Duration::minutes(125),
+        earliest_due: None,
+    };
+    assert_eq!(aggregate.to_string(), "1 task, 2h 05m tracked");
+}
+
+#[test]
+fn test_project_summary() {
+    let mut data = TaskData::default();
+    let tasks = [
+        add_task(&mut data, "a project:work", TaskStatus::Pending),
+        add_task(&mut data, "b project:work.release", TaskStatus::Active),
+        add_task(&mut data, "c project:work.release", TaskStatus::Completed),
+        add_task(&mut data, "d project:home", TaskStatus::Completed),
+        add_task(&mut data, "e project:work", TaskStatus::Deleted),
+        add_task(&mut data, "f", TaskStatus::Pending),
+    ];
+
+    let summary = ProjectSummary::from_tasks(tasks.iter());
+    assert_eq!(
+        summary,
+        vec![
+            ProjectSummary {
+                name: "".to_string(),
+                depth: 0,
+                remaining: 1,
+                completed: 0,
+            },
+            ProjectSummary {
+                name: "work".to_string(),
+                depth: 0,
+                remaining: 2,
+                completed: 1,
+            },
+            ProjectSummary {
+                name: "work.release".to_string(),
+                depth: 1,
+                remaining: 1,
+                completed: 1,
+            },
+        ]
+    );
+    assert_eq!(summary[1].get_completion(), 33);
+    assert_eq!(summary[2].get_completion(), 50);
+}
@@ -0,0 +1,458 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::BeeError;
+
+use super::{
+    ImportStatus, Priority, Task, TaskAnnotation, TaskData, TaskHistory, TaskImport, TaskStatus,
+};
+
+/// The identifier of the program that wrote a calendar
+const PRODUCT_ID: &str = "-//Bee//Bee task manager//EN";
+
+/// The content lines of a calendar are folded after this many octets
+const MAX_LINE_LENGTH: usize = 75;
+
+/// A to-do (VTODO) of an iCalendar file
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ICalTodo {
+    uid: String,
+    summary: String,
+    status: Option<String>,
+    created: Option<DateTime<Local>>,
+    due: Option<DateTime<Local>>,
+    completed: Option<DateTime<Local>>,
+    priority: Option<u8>,
+    categories: Vec<String>,
+    description: Option<String>,
+    /// Set when the to-do overrides a single occurrence of a recurring to-do, it then
+    /// has the UID of the recurring to-do
+    recurrence_id: Option<String>,
+    /// Everything that could not be read as it is
+    warnings: Vec<String>,
+}
+
+impl ICalTodo {
+    pub fn get_uid(&self) -> &str {
+        &self.uid
+    }
+
+    pub fn get_summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// The UUID of the task of the to-do. The calendars Bee exports use the UUIDs of
+    /// the tasks, the other UIDs always give the same UUID so that importing a
+    /// calendar again updates its tasks.
+    pub fn get_uuid(&self) -> Uuid {
+        Uuid::parse_str(&self.uid)
+            .unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_URL, self.uid.as_bytes()))
+    }
+
+    /// The open to-dos are pending, unless the task was already started
+    fn get_status(&self, previous: Option<&TaskStatus>) -> Result<TaskStatus, BeeError> {
+        match self.status.as_deref().map(|s| s.to_uppercase()).as_deref() {
+            None | Some("NEEDS-ACTION") | Some("IN-PROCESS") => match previous {
+                Some(TaskStatus::Active) => Ok(TaskStatus::Active),
+                _ => Ok(TaskStatus::Pending),
+            },
+            Some("COMPLETED") => Ok(TaskStatus::Completed),
+            Some("CANCELLED") => Ok(TaskStatus::Deleted),
+            Some(status) => Err(BeeError::parse(format!(
+                "Task '{}': invalid status '{}', expected NEEDS-ACTION, IN-PROCESS, \
+                COMPLETED or CANCELLED",
+                self.summary, status
+            ))),
+        }
+    }
+
+    /// iCalendar priorities go from 1 (highest) to 9 (lowest), 0 is undefined
+    fn get_priority(&self) -> Option<Priority> {
+        match self.priority {
+            Some(1..=4) => Some(Priority::High),
+            Some(5) => Some(Priority::Medium),
+            Some(6..=9) => Some(Priority::Low),
+            _ => None,
+        }
+    }
+}
+
+/// Escape the characters that have a meaning in the value of a property
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Split a list of values on the commas that are not escaped
+fn split_text_list(value: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' => values.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    values.push(current);
+    values
+        .iter()
+        .map(|value| unescape_text(value.trim()))
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Add a content line, folded so that no line is longer than 75 octets
+fn push_line(lines: &mut Vec<String>, line: String) {
+    let mut current = String::new();
+    for c in line.chars() {
+        if current.len() + c.len_utf8() > MAX_LINE_LENGTH {
+            lines.push(std::mem::take(&mut current));
+            current.push(' ');
+        }
+        current.push(c);
+    }
+    lines.push(current);
+}
+
+fn format_date(date: &DateTime<Local>) -> String {
+    date.with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// Dates in UTC ('20250310T170000Z'), in local time ('20250310T170000') or days
+/// ('20250310'), which start at midnight. The time zones given with TZID are not
+/// supported, those dates are read in local time.
+fn parse_date(value: &str) -> Result<DateTime<Local>, BeeError> {
+    if let Ok(date) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Ok(date.and_utc().with_timezone(&Local));
+    }
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").or_else(|_| {
+        NaiveDate::parse_from_str(value, "%Y%m%d").map(|day| day.and_time(NaiveTime::MIN))
+    });
+    local
+        .ok()
+        .and_then(|date| Local.from_local_datetime(&date).earliest())
+        .ok_or_else(|| BeeError::parse(format!("Invalid iCalendar date '{}'", value)))
+}
+
+/// The VTODO of a task. Tasks due at midnight are due on the whole day.
+fn push_todo(lines: &mut Vec<String>, task: &Task, now: &DateTime<Local>) {
+    lines.push("BEGIN:VTODO".to_string());
+    push_line(lines, format!("UID:{}", task.get_uuid()));
+    lines.push(format!("DTSTAMP:{}", format_date(now)));
+    lines.push(format!("CREATED:{}", format_date(task.get_date_created())));
+    push_line(
+        lines,
+        format!("SUMMARY:{}", escape_text(task.get_summary())),
+    );
+    if let Some(due) = task.get_date_due() {
+        if due.time() == NaiveTime::MIN {
+            lines.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+        } else {
+            lines.push(format!("DUE:{}", format_date(due)));
+        }
+    }
+    let status = match task.get_status() {
+        TaskStatus::Pending => "NEEDS-ACTION",
+        TaskStatus::Active => "IN-PROCESS",
+        TaskStatus::Completed => "COMPLETED",
+        TaskStatus::Deleted => "CANCELLED",
+    };
+    lines.push(format!("STATUS:{}", status));
+    if *task.get_status() == TaskStatus::Completed
+        && let Some(completed) = task.get_date_completed()
+    {
+        lines.push(format!("COMPLETED:{}", format_date(completed)));
+    }
+    if let Some(priority) = task.get_priority() {
+        let priority = match priority {
+            Priority::High => 1,
+            Priority::Medium => 5,
+            Priority::Low => 9,
+        };
+        lines.push(format!("PRIORITY:{}", priority));
+    }
+    if !task.get_tags().is_empty() {
+        let tags: Vec<String> = task.get_tags().iter().map(|t| escape_text(t)).collect();
+        push_line(lines, format!("CATEGORIES:{}", tags.join(",")));
+    }
+    if !task.get_annotations().is_empty() {
+        let annotations: Vec<&str> = task
+            .get_annotations()
+            .iter()
+            .map(|a| a.value.as_str())
+            .collect();
+        push_line(
+            lines,
+            format!("DESCRIPTION:{}", escape_text(&annotations.join("\n"))),
+        );
+    }
+    lines.push("END:VTODO".to_string());
+}
+
+/// A calendar with a to-do (VTODO) per task
+pub fn to_ical<'a>(tasks: impl Iterator<Item = &'a Task>, now: DateTime<Local>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+    ];
+    for task in tasks {
+        push_todo(&mut lines, task, &now);
+    }
+    lines.push("END:VCALENDAR".to_string());
+    // The lines of a calendar end with CRLF
+    lines.iter().map(|line| format!("{}\r\n", line)).collect()
+}
+
+/// Split a content line in its name, upper case, its parameters and its value
+fn split_content_line(line: &str) -> Option<(String, Vec<&str>, &str)> {
+    let mut in_quotes = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                let mut parts = line[..idx].split(';');
+                let name = parts.next().unwrap_or_default().to_uppercase();
+                return Some((name, parts.collect(), &line[idx + 1..]));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Read the date of a property, with a warning if it has a time zone
+fn parse_property_date(
+    todo: &mut ICalTodo,
+    name: &str,
+    parameters: &[&str],
+    value: &str,
+) -> Result<DateTime<Local>, BeeError> {
+    if let Some(tzid) = parameters.iter().find_map(|p| p.strip_prefix("TZID=")) {
+        todo.warnings.push(format!(
+            "Task '{}': the time zone '{}' of {} is not supported, the date was read in \
+            local time.",
+            todo.summary, tzid, name
+        ));
+    }
+    parse_date(value.trim())
+}
+
+/// Read the to-dos of an iCalendar file. The other components, such as the events,
+/// are ignored.
+pub fn parse_ical(content: &str) -> Result<Vec<ICalTodo>, BeeError> {
+    // Unfold the lines first, a line starting with a space or a tab continues the
+    // previous one
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    if lines.first().map(|line| line.trim().to_uppercase()) != Some("BEGIN:VCALENDAR".into()) {
+        return Err(BeeError::parse(
+            "Invalid iCalendar file, it should start with 'BEGIN:VCALENDAR'",
+        ));
+    }
+
+    let mut todos = Vec::new();
+    let mut components: Vec<String> = Vec::new();
+    let mut todo: Option<ICalTodo> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let (name, parameters, value) = split_content_line(line).ok_or_else(|| {
+            BeeError::parse(format!("Invalid iCalendar line {}: '{}'", idx + 1, line))
+        })?;
+        match name.as_str() {
+            "BEGIN" => {
+                let component = value.trim().to_uppercase();
+                if component == "VTODO"
+                    && components.last().map(|c| c.as_str()) == Some("VCALENDAR")
+                {
+                    todo = Some(ICalTodo::default());
+                }
+                components.push(component);
+                continue;
+            }
+            "END" => {
+                let component = value.trim().to_uppercase();
+                if components.pop() != Some(component.to_owned()) {
+                    return Err(BeeError::parse(format!(
+                        "Invalid iCalendar file, unexpected 'END:{}' on line {}",
+                        value,
+                        idx + 1
+                    )));
+                }
+                if component == "VTODO"
+                    && let Some(todo) = todo.take()
+                {
+                    if todo.uid.is_empty() {
+                        return Err(BeeError::parse(format!(
+                            "The to-do '{}' has no UID",
+                            todo.summary
+                        )));
+                    }
+                    todos.push(todo);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        // Only the properties of the to-do itself, not of its alarms
+        let Some(todo) = todo.as_mut() else {
+            continue;
+        };
+        if components.last().map(|c| c.as_str()) != Some("VTODO") {
+            continue;
+        }
+        match name.as_str() {
+            "UID" => todo.uid = value.trim().to_string(),
+            "SUMMARY" => todo.summary = unescape_text(value),
+            "STATUS" => todo.status = Some(value.trim().to_string()),
+            "CREATED" => todo.created = Some(parse_date(value.trim())?),
+            "DUE" => todo.due = Some(parse_property_date(todo, &name, &parameters, value)?),
+            "COMPLETED" => todo.completed = Some(parse_date(value.trim())?),
+            "PRIORITY" => {
+                todo.priority = Some(value.trim().parse().map_err(|_| {
+                    BeeError::parse(format!("Invalid iCalendar priority '{}'", value))
+                })?)
+            }
+            "CATEGORIES" => todo.categories.extend(split_text_list(value)),
+            "DESCRIPTION" => todo.description = Some(unescape_text(value)),
+            "RECURRENCE-ID" => todo.recurrence_id = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    if let Some(component) = components.last() {
+        return Err(BeeError::parse(format!(
+            "Invalid iCalendar file, 'END:{}' is missing",
+            component
+        )));
+    }
+    Ok(todos)
+}
+
+impl TaskData {
+    /// Create the task of a to-do, or update it if it was already imported. Only the
+    /// fields of the to-do are updated, the project of a task for instance is kept.
+    pub fn import_ical(&mut self, todo: &ICalTodo) -> Result<TaskImport, BeeError> {
+        // The override would replace the task of the whole recurring to-do
+        if let Some(recurrence_id) = &todo.recurrence_id {
+            return Ok(TaskImport {
+                status: ImportStatus::Skipped,
+                warnings: vec![format!(
+                    "The to-do '{}' changes the occurrence {} of a recurring to-do and was \
+                    skipped.",
+                    todo.summary, recurrence_id
+                )],
+            });
+        }
+
+        let uuid = todo.get_uuid();
+        let previous = self.tasks.get(&uuid).cloned();
+        let mut task = previous.clone().unwrap_or_else(|| Task {
+            uuid,
+            date_created: todo.created.unwrap_or_else(Local::now),
+            ..Task::default()
+        });
+
+        task.summary = todo.summary.to_owned();
+        task.status = todo.get_status(previous.as_ref().map(|t| t.get_status()))?;
+        task.date_completed = match task.status {
+            TaskStatus::Pending | TaskStatus::Active => None,
+            TaskStatus::Completed | TaskStatus::Deleted => todo
+                .completed
+                .or(task.date_completed)
+                .or_else(|| Some(Local::now())),
+        };
+        if task.status != TaskStatus::Active {
+            task.stop_interval(Local::now());
+        }
+        task.date_due = todo.due;
+        task.priority = todo.get_priority();
+        task.tags = todo.categories.to_owned();
+        // Each line of the description is an annotation. The annotations that are
+        // already known keep their date.
+        task.annotations = todo
+            .description
+            .iter()
+            .flat_map(|description| description.lines())
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                task.annotations
+                    .iter()
+                    .find(|a| a.value == line)
+                    .cloned()
+                    .unwrap_or_else(|| TaskAnnotation {
+                        value: line.to_string(),
+                        time: Local::now(),
+                    })
+            })
+            .collect();
+
+        let status = match previous {
+            Some(previous) if previous == task => {
+                return Ok(TaskImport {
+                    status: ImportStatus::Unchanged,
+                    warnings: todo.warnings.to_owned(),
+                });
+            }
+            Some(previous) => {
+                task.history.push(TaskHistory {
+                    value: "Updated from iCalendar".to_string(),
+                    time: Local::now(),
+                });
+                ImportStatus::Updated(Box::new(previous))
+            }
+            None => {
+                task.history.push(TaskHistory {
+                    value: "Imported from iCalendar".to_string(),
+                    time: Local::now(),
+                });
+                ImportStatus::Created
+            }
+        };
+
+        self.update_id(&mut task);
+        self.tasks.insert(task.uuid, task);
+        Ok(TaskImport {
+            status,
+            warnings: todo.warnings.to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "ical_test.rs"]
+mod ical_test;
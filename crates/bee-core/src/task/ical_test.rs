@@ -0,0 +1,263 @@
+use all_asserts::{assert_false, assert_true};
+use chrono::{Duration, TimeZone, Utc};
+
+use super::*;
+use crate::task::{Project, TaskProperties};
+
+const CALENDAR: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Team//Shared calendar//EN\r
+BEGIN:VEVENT\r
+UID:meeting@example.com\r
+SUMMARY:Weekly meeting\r
+DTSTART:20250310T090000Z\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:release-notes@example.com\r
+SUMMARY:Write the release notes\\, then send\r
+  them\r
+DUE;VALUE=DATE:20250314\r
+STATUS:NEEDS-ACTION\r
+PRIORITY:2\r
+CATEGORIES:work,release\\,notes\r
+CATEGORIES:docs\r
+DESCRIPTION:ask for the changelog\\nproofread\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+DESCRIPTION:Reminder\r
+END:VALARM\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1\r
+SUMMARY:Book the room\r
+DUE;TZID=Europe/Paris:20250311T170000\r
+STATUS:COMPLETED\r
+COMPLETED:20250309T120000Z\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+fn get_date(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Local> {
+    Utc.with_ymd_and_hms(year, month, day, hour, 0, 0)
+        .unwrap()
+        .with_timezone(&Local)
+}
+
+#[test]
+fn test_parse_ical() {
+    let todos = parse_ical(CALENDAR).unwrap();
+    assert_eq!(todos.len(), 2);
+
+    let todo = &todos[0];
+    assert_eq!(todo.get_uid(), "release-notes@example.com");
+    assert_eq!(
+        todo.get_summary(),
+        "Write the release notes, then send them"
+    );
+    assert_eq!(
+        todo.due,
+        Local
+            .with_ymd_and_hms(2025, 3, 14, 0, 0, 0)
+            .earliest()
+            .map(|d| d.with_timezone(&Local))
+    );
+    assert_eq!(todo.categories, vec!["work", "release,notes", "docs"]);
+    // The description of the alarm is not the one of the to-do
+    assert_eq!(
+        todo.description.as_deref(),
+        Some("ask for the changelog\nproofread")
+    );
+    assert_eq!(todo.get_priority(), Some(Priority::High));
+    assert_true!(todo.warnings.is_empty());
+    // The same UID always gives the same task
+    assert_eq!(todo.get_uuid(), parse_ical(CALENDAR).unwrap()[0].get_uuid());
+
+    let todo = &todos[1];
+    assert_eq!(
+        todo.get_uuid().to_string(),
+        "8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1"
+    );
+    assert_eq!(todo.completed, Some(get_date(2025, 3, 9, 12)));
+    assert_eq!(todo.warnings.len(), 1);
+    assert_true!(todo.warnings[0].contains("'Europe/Paris'"));
+}
+
+#[test]
+fn test_parse_ical_invalid() {
+    assert_true!(parse_ical("").is_err());
+    assert_true!(parse_ical("BEGIN:VTODO\nEND:VTODO\n").is_err());
+    assert_true!(parse_ical("BEGIN:VCALENDAR\nBEGIN:VTODO\nEND:VCALENDAR\n").is_err());
+    assert_true!(parse_ical("BEGIN:VCALENDAR\nBEGIN:VTODO\nUID:a\n").is_err());
+    assert_true!(
+        parse_ical("BEGIN:VCALENDAR\nBEGIN:VTODO\nSUMMARY:a\nEND:VTODO\nEND:VCALENDAR").is_err()
+    );
+    assert_true!(
+        parse_ical("BEGIN:VCALENDAR\nBEGIN:VTODO\nUID:a\nDUE:tomorrow\nEND:VTODO\nEND:VCALENDAR")
+            .is_err()
+    );
+    assert_true!(parse_ical("BEGIN:VCALENDAR\nno colon\nEND:VCALENDAR").is_err());
+    assert_eq!(
+        parse_ical("BEGIN:VCALENDAR\nEND:VCALENDAR").unwrap(),
+        Vec::new()
+    );
+}
+
+#[test]
+fn test_to_ical() {
+    let task = Task {
+        uuid: Uuid::parse_str("8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1").unwrap(),
+        summary: "Send the report; to the whole team, and to the customers who asked for it"
+            .to_string(),
+        status: TaskStatus::Active,
+        tags: vec!["work".to_string(), "urgent".to_string()],
+        date_created: get_date(2025, 3, 1, 12),
+        date_due: Some(get_date(2025, 3, 10, 17)),
+        priority: Some(Priority::Medium),
+        annotations: vec![
+            TaskAnnotation {
+                value: "ask for the figures".to_string(),
+                time: get_date(2025, 3, 2, 8),
+            },
+            TaskAnnotation {
+                value: "check the totals".to_string(),
+                time: get_date(2025, 3, 3, 8),
+            },
+        ],
+        ..Default::default()
+    };
+    let ical = to_ical([task].iter(), get_date(2025, 3, 5, 10));
+    assert_true!(ical.ends_with("\r\n"));
+    let lines: Vec<&str> = ical.split("\r\n").collect();
+    assert_eq!(
+        lines,
+        vec![
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "PRODID:-//Bee//Bee task manager//EN",
+            "BEGIN:VTODO",
+            "UID:8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1",
+            "DTSTAMP:20250305T100000Z",
+            "CREATED:20250301T120000Z",
+            "SUMMARY:Send the report\\; to the whole team\\, and to the customers who aske",
+            " d for it",
+            "DUE:20250310T170000Z",
+            "STATUS:IN-PROCESS",
+            "PRIORITY:5",
+            "CATEGORIES:work,urgent",
+            "DESCRIPTION:ask for the figures\\ncheck the totals",
+            "END:VTODO",
+            "END:VCALENDAR",
+            "",
+        ]
+    );
+    assert_true!(lines.iter().all(|line| line.len() <= MAX_LINE_LENGTH));
+}
+
+#[test]
+fn test_import_ical() {
+    let todos = parse_ical(CALENDAR).unwrap();
+    let mut data = TaskData::default();
+    let results: Vec<TaskImport> = todos
+        .iter()
+        .map(|todo| data.import_ical(todo).unwrap())
+        .collect();
+    assert_true!(results.iter().all(|r| r.status == ImportStatus::Created));
+    assert_eq!(results[1].warnings.len(), 1);
+
+    let task = data.get_task_map().get(&todos[0].get_uuid()).unwrap();
+    assert_eq!(task.get_id(), Some(1));
+    assert_eq!(task.get_status(), &TaskStatus::Pending);
+    assert_eq!(task.get_tags(), &vec!["work", "release,notes", "docs"]);
+    assert_eq!(task.get_priority(), &Some(Priority::High));
+    assert_eq!(task.get_annotations().len(), 2);
+    assert_eq!(task.get_annotations()[1].value, "proofread");
+    assert_eq!(
+        task.history.last().unwrap().value,
+        "Imported from iCalendar"
+    );
+
+    let task = data.get_task_map().get(&todos[1].get_uuid()).unwrap();
+    assert_eq!(task.get_id(), None);
+    assert_eq!(task.get_status(), &TaskStatus::Completed);
+    assert_eq!(task.get_date_completed(), &Some(get_date(2025, 3, 9, 12)));
+
+    // Importing the same calendar again changes nothing
+    for todo in &todos {
+        assert_eq!(
+            data.import_ical(todo).unwrap().status,
+            ImportStatus::Unchanged
+        );
+    }
+
+    // The fields the calendar doesn't know about are kept
+    let uuid = todos[0].get_uuid();
+    let mut task = data.get_owned(&uuid).unwrap();
+    task.project = Some(Project::from("work".to_string()));
+    data.set_task(task);
+    let updated = CALENDAR.replace("STATUS:NEEDS-ACTION", "STATUS:CANCELLED");
+    let todos = parse_ical(&updated).unwrap();
+    let result = data.import_ical(&todos[0]).unwrap();
+    assert_false!(result.status == ImportStatus::Unchanged);
+    let task = data.get_task_map().get(&uuid).unwrap();
+    assert_eq!(task.get_status(), &TaskStatus::Deleted);
+    assert_eq!(task.get_id(), None);
+    assert_true!(task.get_date_completed().is_some());
+    assert_eq!(task.get_project().as_ref().unwrap().get_name(), "work");
+    assert_eq!(task.history.last().unwrap().value, "Updated from iCalendar");
+}
+
+#[test]
+fn test_ical_round_trip() {
+    let now = Local::now();
+    let mut data = TaskData::default();
+    for (arguments, status) in [
+        ("write the report +work due:2025-03-10", TaskStatus::Pending),
+        ("buy milk priority:L", TaskStatus::Completed),
+    ] {
+        data.add_task(
+            &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+            status,
+        )
+        .unwrap();
+    }
+    let mut task = data
+        .get_task_map()
+        .values()
+        .find(|t| t.get_summary() == "write the report")
+        .cloned()
+        .unwrap();
+    task.date_due = Some(now + Duration::hours(3));
+    task.annotations.push(TaskAnnotation {
+        value: "the figures, from finance".to_string(),
+        time: now,
+    });
+    data.set_task(task);
+
+    let ical = to_ical(data.get_task_map().values(), now);
+    let mut imported = TaskData::default();
+    for todo in parse_ical(&ical).unwrap() {
+        imported.import_ical(&todo).unwrap();
+    }
+    for task in data.get_task_map().values() {
+        let copy = imported.get_task_map().get(task.get_uuid()).unwrap();
+        assert_eq!(copy.get_summary(), task.get_summary());
+        assert_eq!(copy.get_status(), task.get_status());
+        assert_eq!(copy.get_tags(), task.get_tags());
+        assert_eq!(copy.get_priority(), task.get_priority());
+        // iCalendar dates are to the second
+        assert_eq!(
+            copy.get_date_due().map(|d| d.timestamp()),
+            task.get_date_due().map(|d| d.timestamp())
+        );
+        assert_eq!(
+            copy.get_annotations()
+                .iter()
+                .map(|a| &a.value)
+                .collect::<Vec<_>>(),
+            task.get_annotations()
+                .iter()
+                .map(|a| &a.value)
+                .collect::<Vec<_>>()
+        );
+    }
+}
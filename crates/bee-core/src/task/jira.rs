@@ -0,0 +1,346 @@
+use std::fmt;
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Deserializer};
+use uuid::Uuid;
+
+use crate::BeeError;
+
+use super::{ImportStatus, Project, Task, TaskData, TaskHistory, TaskStatus};
+
+/// The history entries written by the synchronisation. The other entries are the
+/// changes made locally.
+const HISTORY_IMPORTED: &str = "Imported from Jira";
+const HISTORY_UPDATED: &str = "Updated from Jira";
+const HISTORY_PUSHED: &str = "Status pushed to Jira";
+
+/// The fields of an issue the synchronisation uses, to only request those
+pub const JIRA_FIELDS: &str = "summary,status,labels,project,duedate,resolutiondate,updated";
+
+/// The category of a Jira status. The statuses themselves depend on the workflow of
+/// each project, their category doesn't.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JiraStatusCategory {
+    New,
+    Indeterminate,
+    Done,
+    #[serde(other)]
+    Undefined,
+}
+
+impl JiraStatusCategory {
+    pub fn get_key(&self) -> &'static str {
+        match self {
+            JiraStatusCategory::New => "new",
+            JiraStatusCategory::Indeterminate => "indeterminate",
+            JiraStatusCategory::Done => "done",
+            JiraStatusCategory::Undefined => "undefined",
+        }
+    }
+
+    fn to_status(self) -> TaskStatus {
+        match self {
+            JiraStatusCategory::New | JiraStatusCategory::Undefined => TaskStatus::Pending,
+            JiraStatusCategory::Indeterminate => TaskStatus::Active,
+            JiraStatusCategory::Done => TaskStatus::Completed,
+        }
+    }
+
+    /// A deleted task has no equivalent in Jira
+    fn from_status(status: &TaskStatus) -> Option<JiraStatusCategory> {
+        match status {
+            TaskStatus::Pending => Some(JiraStatusCategory::New),
+            TaskStatus::Active => Some(JiraStatusCategory::Indeterminate),
+            TaskStatus::Completed => Some(JiraStatusCategory::Done),
+            TaskStatus::Deleted => None,
+        }
+    }
+}
+
+impl fmt::Display for JiraStatusCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_key())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+struct JiraStatusCategoryField {
+    key: JiraStatusCategory,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+struct JiraStatus {
+    name: String,
+    #[serde(rename = "statusCategory")]
+    category: JiraStatusCategoryField,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+struct JiraProject {
+    key: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+struct JiraFields {
+    summary: String,
+    status: JiraStatus,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    project: Option<JiraProject>,
+    #[serde(default, deserialize_with = "deserialize_optional_day")]
+    duedate: Option<DateTime<Local>>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    resolutiondate: Option<DateTime<Local>>,
+    #[serde(deserialize_with = "deserialize_date")]
+    updated: DateTime<Local>,
+}
+
+/// An issue as returned by the REST API of Jira (version 2)
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+/// A page of the results of a search
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct JiraSearchResult {
+    #[serde(rename = "startAt", default)]
+    pub start_at: usize,
+    #[serde(default)]
+    pub total: usize,
+    pub issues: Vec<JiraIssue>,
+}
+
+/// What the synchronisation does with an issue and its task
+#[derive(Debug, Clone, PartialEq)]
+pub enum JiraSync {
+    /// The task is created or updated from the issue
+    Pull,
+    /// The status of the issue is changed to the one of the task
+    Push(JiraStatusCategory),
+    Unchanged,
+}
+
+/// Jira writes its dates with the offset of the server, e.g. '2025-03-02T12:00:00.000+0000'
+fn parse_date(value: &str) -> Result<DateTime<Local>, BeeError> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .map(|date| date.with_timezone(&Local))
+        .map_err(|_| BeeError::parse(format!("Invalid Jira date '{}'", value)))
+}
+
+/// The due dates have no time, the task is due at the start of the day
+fn parse_day(value: &str) -> Result<DateTime<Local>, BeeError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|day| {
+            Local
+                .from_local_datetime(&day.and_hms_opt(0, 0, 0)?)
+                .earliest()
+        })
+        .ok_or_else(|| BeeError::parse(format!("Invalid Jira date '{}'", value)))
+}
+
+fn deserialize_date<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_date(&value).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<DateTime<Local>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) => parse_date(&value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+fn deserialize_optional_day<'de, D>(deserializer: D) -> Result<Option<DateTime<Local>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) => parse_day(&value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+fn is_sync_history(history: &TaskHistory) -> bool {
+    [HISTORY_IMPORTED, HISTORY_UPDATED, HISTORY_PUSHED].contains(&history.value.as_str())
+}
+
+impl JiraIssue {
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn get_summary(&self) -> &str {
+        &self.fields.summary
+    }
+
+    pub fn get_status_name(&self) -> &str {
+        &self.fields.status.name
+    }
+
+    pub fn get_status_category(&self) -> JiraStatusCategory {
+        self.fields.status.category.key
+    }
+
+    pub fn get_updated(&self) -> &DateTime<Local> {
+        &self.fields.updated
+    }
+
+    /// Compare the issue with its task, if it has one.
+    ///
+    /// When both changed since they were last synchronised, the latest change wins: the
+    /// time the issue was updated is compared with the time of the last entry of the
+    /// history of the task. Only the status of a task is pushed to Jira.
+    pub fn get_sync(&self, task: Option<&Task>) -> JiraSync {
+        let Some(task) = task else {
+            return JiraSync::Pull;
+        };
+        if task.matches_jira_issue(self) {
+            return JiraSync::Unchanged;
+        }
+
+        let last_sync = task.get_last_jira_sync();
+        let last_change = task.get_last_local_change();
+        let changed_locally = last_sync.is_none_or(|sync| last_change > sync);
+        let changed_remotely = last_sync.is_none_or(|sync| self.fields.updated > sync);
+        if !changed_locally || (changed_remotely && self.fields.updated >= last_change) {
+            return JiraSync::Pull;
+        }
+
+        match JiraStatusCategory::from_status(&task.status) {
+            Some(category) if self.get_status_category().to_status() != task.status => {
+                JiraSync::Push(category)
+            }
+            _ => JiraSync::Unchanged,
+        }
+    }
+}
+
+impl Task {
+    /// When the task was last created, updated or pushed by the synchronisation
+    fn get_last_jira_sync(&self) -> Option<DateTime<Local>> {
+        self.history
+            .iter()
+            .filter(|h| is_sync_history(h))
+            .map(|h| h.time)
+            .max()
+    }
+
+    /// When the task was last changed, not counting the synchronisations
+    fn get_last_local_change(&self) -> DateTime<Local> {
+        self.history
+            .iter()
+            .filter(|h| !is_sync_history(h))
+            .map(|h| h.time)
+            .max()
+            .unwrap_or(self.date_created)
+    }
+
+    fn matches_jira_issue(&self, issue: &JiraIssue) -> bool {
+        let mut task = self.clone();
+        task.apply_jira_issue(issue);
+        task == *self
+    }
+
+    /// Copy the fields of the issue to the task, without recording it in its history
+    fn apply_jira_issue(&mut self, issue: &JiraIssue) {
+        let fields = &issue.fields;
+        self.external_id = Some(issue.key.to_owned());
+        self.summary = fields.summary.to_owned();
+        self.tags = fields.labels.to_owned();
+        self.project = fields
+            .project
+            .as_ref()
+            .map(|p| Project::from(p.key.to_owned()));
+        self.date_due = fields.duedate;
+
+        let status = fields.status.category.key.to_status();
+        if status != self.status {
+            if status == TaskStatus::Active {
+                self.start_interval(Local::now());
+            } else {
+                self.stop_interval(Local::now());
+            }
+        }
+        self.date_completed = match status {
+            TaskStatus::Completed => fields
+                .resolutiondate
+                .or(self.date_completed)
+                .or_else(|| Some(Local::now())),
+            _ => None,
+        };
+        self.status = status;
+    }
+}
+
+impl TaskData {
+    pub fn get_task_by_external_id(&self, external_id: &str) -> Option<&Task> {
+        self.tasks
+            .values()
+            .find(|t| t.external_id.as_deref() == Some(external_id))
+    }
+
+    /// Create or update the task of a Jira issue, the tasks are found by the key of
+    /// their issue
+    pub fn import_jira_issue(&mut self, issue: &JiraIssue) -> ImportStatus {
+        let previous = self.get_task_by_external_id(&issue.key).cloned();
+        let mut task = previous.clone().unwrap_or_else(|| Task {
+            uuid: Uuid::new_v4(),
+            date_created: Local::now(),
+            ..Task::default()
+        });
+        task.apply_jira_issue(issue);
+
+        let status = match previous {
+            Some(previous) if previous == task => return ImportStatus::Unchanged,
+            Some(previous) => {
+                task.history.push(TaskHistory {
+                    value: HISTORY_UPDATED.to_string(),
+                    time: Local::now(),
+                });
+                ImportStatus::Updated(Box::new(previous))
+            }
+            None => {
+                task.history.push(TaskHistory {
+                    value: HISTORY_IMPORTED.to_string(),
+                    time: Local::now(),
+                });
+                ImportStatus::Created
+            }
+        };
+
+        self.update_id(&mut task);
+        self.tasks.insert(task.uuid, task);
+        status
+    }
+
+    /// Record that the status of the task was pushed to its issue, so that the change
+    /// isn't pushed again
+    pub fn set_jira_status_pushed(&mut self, uuid: &Uuid) {
+        if let Some(task) = self.tasks.get_mut(uuid) {
+            task.history.push(TaskHistory {
+                value: HISTORY_PUSHED.to_string(),
+                time: Local::now(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "jira_test.rs"]
+mod jira_test;
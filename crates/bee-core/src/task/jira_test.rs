@@ -0,0 +1,207 @@
+use all_asserts::assert_true;
+use chrono::{Duration, TimeZone, Utc};
+
+use super::*;
+
+fn issue_json(key: &str, category: &str, updated: DateTime<Local>) -> String {
+    format!(
+        r#"{{"key":"{}","fields":{{"summary":"Fix the login page",
+        "status":{{"name":"Status of {}","statusCategory":{{"key":"{}"}}}},
+        "labels":["frontend","urgent"],"project":{{"key":"WEB"}},"duedate":"2025-03-10",
+        "resolutiondate":null,"updated":"{}"}}}}"#,
+        key,
+        category,
+        category,
+        updated.format("%Y-%m-%dT%H:%M:%S%.3f%z")
+    )
+}
+
+fn issue(key: &str, category: &str, updated: DateTime<Local>) -> JiraIssue {
+    serde_json::from_str(&issue_json(key, category, updated)).unwrap()
+}
+
+/// Pretend the task was changed locally at the given time
+fn change_locally(data: &mut TaskData, key: &str, status: TaskStatus, time: DateTime<Local>) {
+    let mut task = data.get_task_by_external_id(key).unwrap().clone();
+    task.status = status;
+    task.history.push(TaskHistory {
+        value: "Status changed".to_string(),
+        time,
+    });
+    data.set_task(task);
+}
+
+#[test]
+fn test_parse_issue() {
+    let result: JiraSearchResult = serde_json::from_str(&format!(
+        r#"{{"startAt":0,"maxResults":50,"total":1,"issues":[{}]}}"#,
+        r#"{"key":"WEB-1","fields":{"summary":"Fix the login page",
+        "status":{"name":"In Review","statusCategory":{"key":"indeterminate"}},
+        "labels":[],"project":{"key":"WEB"},"duedate":null,
+        "updated":"2025-03-02T12:00:00.000+0000"}}"#
+    ))
+    .unwrap();
+    assert_eq!(result.total, 1);
+    let issue = &result.issues[0];
+    assert_eq!(issue.get_key(), "WEB-1");
+    assert_eq!(issue.get_status_name(), "In Review");
+    assert_eq!(
+        issue.get_status_category(),
+        JiraStatusCategory::Indeterminate
+    );
+    assert_eq!(
+        issue.get_updated(),
+        &Utc.with_ymd_and_hms(2025, 3, 2, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&Local)
+    );
+
+    // The categories Jira may add are not an error
+    let issue = issue_json("WEB-1", "unknown", Local::now());
+    let issue: JiraIssue = serde_json::from_str(&issue).unwrap();
+    assert_eq!(issue.get_status_category(), JiraStatusCategory::Undefined);
+
+    assert_true!(
+        serde_json::from_str::<JiraIssue>(
+            &issue_json("WEB-1", "new", Local::now()).replace("2025-03-10", "10/03/2025")
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_import_jira_issue() {
+    let mut data = TaskData::default();
+    let updated = Local::now() - Duration::hours(1);
+
+    let status = data.import_jira_issue(&issue("WEB-1", "indeterminate", updated));
+    assert_eq!(status, ImportStatus::Created);
+    let task = data.get_task_by_external_id("WEB-1").unwrap().clone();
+    assert_eq!(task.get_external_id(), &Some("WEB-1".to_string()));
+    assert_eq!(task.get_summary(), "Fix the login page");
+    assert_eq!(task.get_status(), &TaskStatus::Active);
+    assert_eq!(
+        task.get_tags(),
+        &vec!["frontend".to_string(), "urgent".to_string()]
+    );
+    assert_eq!(task.get_project(), &Some(Project::from("WEB".to_string())));
+    assert_eq!(
+        task.get_date_due(),
+        &Local.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).earliest()
+    );
+    assert_true!(task.get_id().is_some());
+    assert_eq!(task.get_intervals().len(), 1);
+
+    assert_eq!(
+        data.import_jira_issue(&issue("WEB-1", "indeterminate", updated)),
+        ImportStatus::Unchanged
+    );
+
+    let status = data.import_jira_issue(&issue("WEB-1", "done", Local::now()));
+    assert_eq!(status, ImportStatus::Updated(Box::new(task)));
+    let task = data.get_task_by_external_id("WEB-1").unwrap();
+    assert_eq!(task.get_status(), &TaskStatus::Completed);
+    assert_true!(task.get_date_completed().is_some());
+    assert_eq!(task.get_id(), None);
+    assert_true!(task.get_intervals()[0].end.is_some());
+    assert_eq!(data.get_task_map().len(), 1);
+}
+
+#[test]
+fn test_get_sync_new_issue() {
+    let issue = issue("WEB-1", "new", Local::now());
+    assert_eq!(issue.get_sync(None), JiraSync::Pull);
+}
+
+#[test]
+fn test_get_sync_unchanged() {
+    let mut data = TaskData::default();
+    let issue = issue("WEB-1", "new", Local::now() - Duration::hours(1));
+    data.import_jira_issue(&issue);
+    assert_eq!(
+        issue.get_sync(data.get_task_by_external_id("WEB-1")),
+        JiraSync::Unchanged
+    );
+}
+
+#[test]
+fn test_get_sync_changed_remotely() {
+    let mut data = TaskData::default();
+    data.import_jira_issue(&issue("WEB-1", "new", Local::now() - Duration::hours(2)));
+
+    let updated = issue("WEB-1", "done", Local::now() + Duration::seconds(1));
+    assert_eq!(
+        updated.get_sync(data.get_task_by_external_id("WEB-1")),
+        JiraSync::Pull
+    );
+}
+
+#[test]
+fn test_get_sync_changed_locally() {
+    let mut data = TaskData::default();
+    let issue = issue("WEB-1", "new", Local::now() - Duration::hours(2));
+    data.import_jira_issue(&issue);
+    change_locally(
+        &mut data,
+        "WEB-1",
+        TaskStatus::Completed,
+        Local::now() + Duration::seconds(1),
+    );
+
+    let task = data.get_task_by_external_id("WEB-1").unwrap();
+    assert_eq!(
+        issue.get_sync(Some(task)),
+        JiraSync::Push(JiraStatusCategory::Done)
+    );
+
+    // Once pushed, the issue is updated as well
+    let uuid = *task.get_uuid();
+    data.set_jira_status_pushed(&uuid);
+    let pushed = self::issue("WEB-1", "done", Local::now());
+    assert_eq!(
+        pushed.get_sync(data.get_task_by_external_id("WEB-1")),
+        JiraSync::Unchanged
+    );
+}
+
+#[test]
+fn test_get_sync_deleted_locally() {
+    let mut data = TaskData::default();
+    let issue = issue("WEB-1", "new", Local::now() - Duration::hours(2));
+    data.import_jira_issue(&issue);
+    change_locally(
+        &mut data,
+        "WEB-1",
+        TaskStatus::Deleted,
+        Local::now() + Duration::seconds(1),
+    );
+    assert_eq!(
+        issue.get_sync(data.get_task_by_external_id("WEB-1")),
+        JiraSync::Unchanged
+    );
+}
+
+#[test]
+fn test_get_sync_conflict() {
+    let mut data = TaskData::default();
+    data.import_jira_issue(&issue("WEB-1", "new", Local::now() - Duration::hours(3)));
+    let now = Local::now();
+    change_locally(
+        &mut data,
+        "WEB-1",
+        TaskStatus::Active,
+        now + Duration::hours(1),
+    );
+    let task = data.get_task_by_external_id("WEB-1").unwrap();
+
+    // The issue was changed before the task
+    let earlier = issue("WEB-1", "done", now + Duration::minutes(30));
+    assert_eq!(
+        earlier.get_sync(Some(task)),
+        JiraSync::Push(JiraStatusCategory::Indeterminate)
+    );
+
+    // The issue was changed after the task
+    let later = issue("WEB-1", "done", now + Duration::hours(2));
+    assert_eq!(later.get_sync(Some(task)), JiraSync::Pull);
+}
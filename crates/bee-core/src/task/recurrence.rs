@@ -0,0 +1,271 @@
+use std::fmt;
+
+use chrono::{
+    DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveTime, TimeZone, Weekday,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::BeeError;
+use crate::parser::{
+    matches_day_string, matches_month_string, matches_week_string, matches_year_string,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RecurrenceUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl RecurrenceUnit {
+    pub fn from_string(input: &str) -> Result<RecurrenceUnit, BeeError> {
+        match input.to_lowercase().as_str() {
+            v if matches_day_string(v) => Ok(RecurrenceUnit::Day),
+            v if matches_week_string(v) => Ok(RecurrenceUnit::Week),
+            v if matches_month_string(v) => Ok(RecurrenceUnit::Month),
+            v if matches_year_string(v) => Ok(RecurrenceUnit::Year),
+            _ => Err(BeeError::parse(format!(
+                "Invalid recurrence unit '{}'",
+                input
+            ))),
+        }
+    }
+
+    /// Add `count` units to `time`. Months and years follow the calendar, so a task
+    /// due on the 31st of January recurring every month is next due on the last day
+    /// of February.
+    fn add_to(&self, count: u32, time: DateTime<Local>) -> Result<DateTime<Local>, BeeError> {
+        let next = match self {
+            RecurrenceUnit::Day => time.checked_add_signed(Duration::days(count as i64)),
+            RecurrenceUnit::Week => time.checked_add_signed(Duration::days(7 * count as i64)),
+            RecurrenceUnit::Month => time.checked_add_months(Months::new(count)),
+            RecurrenceUnit::Year => 12u32
+                .checked_mul(count)
+                .and_then(|months| time.checked_add_months(Months::new(months))),
+        };
+        next.ok_or_else(|| out_of_range_error(time))
+    }
+
+    fn name(&self, plural: bool) -> String {
+        let name = match self {
+            RecurrenceUnit::Day => "day",
+            RecurrenceUnit::Week => "week",
+            RecurrenceUnit::Month => "month",
+            RecurrenceUnit::Year => "year",
+        };
+        if plural {
+            format!("{}s", name)
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+/// Defines when the next occurrence of a recurring task is due.
+///
+/// It is serialised as the same string the user would type after `recur:`
+/// (e.g. "every 2 weeks", "monday,friday", "1,15" or "after 3 days").
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Recurrence {
+    /// Every N units, counted from the previous due date
+    Every { count: u32, unit: RecurrenceUnit },
+    /// On each of the given days of the week
+    Weekdays(Vec<Weekday>),
+    /// On each of the given days of the month
+    MonthDays(Vec<u32>),
+    /// N units after the task has been completed
+    AfterCompletion { count: u32, unit: RecurrenceUnit },
+}
+
+fn weekday_name(day: &Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+fn out_of_range_error(time: DateTime<Local>) -> BeeError {
+    BeeError::InvalidTransition(format!(
+        "The next occurrence of a task due {} is out of the range of supported dates",
+        time.format("%Y-%m-%d")
+    ))
+}
+
+fn at_time(date: NaiveDate, time: NaiveTime) -> DateTime<Local> {
+    let naive = date.and_time(time);
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        // The time does not exist on that day (DST gap), use the hour after instead
+        .unwrap_or_else(|| {
+            Local
+                .from_local_datetime(&(naive + Duration::try_hours(1).unwrap()))
+                .earliest()
+                .unwrap()
+        })
+}
+
+impl Recurrence {
+    pub fn from_string(input: &str) -> Result<Recurrence, BeeError> {
+        let err_msg = format!(
+            "Invalid recurrence '{}'. Expected 'every <N> <unit>', 'after <N> <unit>', \
+            a list of week days (e.g. 'monday,friday') or a list of days of the month (e.g. '1,15')",
+            input
+        );
+        let words: Vec<&str> = input.split_whitespace().collect();
+
+        match words.as_slice() {
+            [keyword, rest @ ..] if *keyword == "every" || *keyword == "after" => {
+                let (count, unit) = match rest {
+                    [unit] => (1, RecurrenceUnit::from_string(unit)?),
+                    [count, unit] => (
+                        count
+                            .parse::<u32>()
+                            .map_err(|_| BeeError::parse(&err_msg))?,
+                        RecurrenceUnit::from_string(unit)?,
+                    ),
+                    _ => return Err(BeeError::parse(err_msg)),
+                };
+                if count == 0 {
+                    return Err(BeeError::parse(err_msg));
+                }
+                if *keyword == "every" {
+                    Ok(Recurrence::Every { count, unit })
+                } else {
+                    Ok(Recurrence::AfterCompletion { count, unit })
+                }
+            }
+            [list] => {
+                let values: Vec<&str> = list.split(',').filter(|v| !v.is_empty()).collect();
+                if values.is_empty() {
+                    return Err(BeeError::parse(err_msg));
+                }
+
+                if let Ok(mut days) = values
+                    .iter()
+                    .map(|v| v.parse::<u32>())
+                    .collect::<Result<Vec<_>, _>>()
+                {
+                    if days.iter().any(|d| *d == 0 || *d > 31) {
+                        return Err(BeeError::parse(err_msg));
+                    }
+                    days.sort_unstable();
+                    days.dedup();
+                    return Ok(Recurrence::MonthDays(days));
+                }
+
+                let mut weekdays = values
+                    .iter()
+                    .map(|v| v.parse::<Weekday>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| BeeError::parse(err_msg))?;
+                weekdays.sort_by_key(|d| d.num_days_from_monday());
+                weekdays.dedup();
+                Ok(Recurrence::Weekdays(weekdays))
+            }
+            _ => Err(BeeError::parse(err_msg)),
+        }
+    }
+
+    /// Compute when the next occurrence is due, given the due date of the occurrence
+    /// that was just completed (if any) and the time it was completed at.
+    ///
+    /// The returned date is always after the completion time, so completing an overdue
+    /// task does not create an occurrence that is already overdue. An error is returned
+    /// when the next occurrence would be out of the range of supported dates.
+    pub fn next_due(
+        &self,
+        previous_due: Option<DateTime<Local>>,
+        completed: DateTime<Local>,
+    ) -> Result<DateTime<Local>, BeeError> {
+        // Completing a task early should not make its next occurrence come sooner
+        let base = match previous_due {
+            Some(due) if due > completed => due,
+            _ => completed,
+        };
+        let time_of_day = previous_due
+            .map(|d| d.time())
+            .unwrap_or_else(|| NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+
+        match self {
+            Recurrence::Every { count, unit } => {
+                let mut next = unit.add_to(*count, previous_due.unwrap_or(completed))?;
+                while next <= completed {
+                    next = unit.add_to(*count, next)?;
+                }
+                Ok(next)
+            }
+            Recurrence::AfterCompletion { count, unit } => unit.add_to(*count, completed),
+            Recurrence::Weekdays(days) => {
+                let date = base
+                    .date_naive()
+                    .iter_days()
+                    .skip(1)
+                    .find(|d| days.contains(&d.weekday()))
+                    .ok_or_else(|| out_of_range_error(base))?;
+                Ok(at_time(date, time_of_day))
+            }
+            Recurrence::MonthDays(days) => {
+                let date = base
+                    .date_naive()
+                    .iter_days()
+                    .skip(1)
+                    .find(|d| days.contains(&d.day()))
+                    .ok_or_else(|| out_of_range_error(base))?;
+                Ok(at_time(date, time_of_day))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Recurrence::Every { count, unit } if *count == 1 => {
+                write!(f, "every {}", unit.name(false))
+            }
+            Recurrence::Every { count, unit } => write!(f, "every {} {}", count, unit.name(true)),
+            Recurrence::AfterCompletion { count, unit } => {
+                write!(f, "after {} {}", count, unit.name(*count > 1))
+            }
+            Recurrence::Weekdays(days) => write!(
+                f,
+                "{}",
+                days.iter().map(weekday_name).collect::<Vec<_>>().join(",")
+            ),
+            Recurrence::MonthDays(days) => write!(
+                f,
+                "{}",
+                days.iter()
+                    .map(|d| d.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(value: Recurrence) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = BeeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Recurrence::from_string(&value)
+    }
+}
+
+#[cfg(test)]
+#[path = "recurrence_test.rs"]
+mod recurrence_test;
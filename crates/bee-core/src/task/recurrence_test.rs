@@ -0,0 +1,160 @@
+use all_asserts::assert_true;
+use chrono::{Local, NaiveDate, TimeZone};
+
+use super::*;
+
+fn local_time(y: i32, m: u32, d: u32, h: u32) -> DateTime<Local> {
+    Local
+        .from_local_datetime(
+            &NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap(),
+        )
+        .earliest()
+        .unwrap()
+}
+
+#[test]
+fn test_from_string() {
+    assert_eq!(
+        Recurrence::from_string("every 2 weeks"),
+        Ok(Recurrence::Every {
+            count: 2,
+            unit: RecurrenceUnit::Week
+        })
+    );
+    assert_eq!(
+        Recurrence::from_string("every month"),
+        Ok(Recurrence::Every {
+            count: 1,
+            unit: RecurrenceUnit::Month
+        })
+    );
+    assert_eq!(
+        Recurrence::from_string("after 3 d"),
+        Ok(Recurrence::AfterCompletion {
+            count: 3,
+            unit: RecurrenceUnit::Day
+        })
+    );
+    assert_eq!(
+        Recurrence::from_string("friday,mon,monday"),
+        Ok(Recurrence::Weekdays(vec![Weekday::Mon, Weekday::Fri]))
+    );
+    assert_eq!(
+        Recurrence::from_string("15,1"),
+        Ok(Recurrence::MonthDays(vec![1, 15]))
+    );
+
+    assert_true!(Recurrence::from_string("every 0 days").is_err());
+    assert_true!(Recurrence::from_string("every 2 fortnights").is_err());
+    assert_true!(Recurrence::from_string("1,32").is_err());
+    assert_true!(Recurrence::from_string("monday,someday").is_err());
+    assert_true!(Recurrence::from_string("").is_err());
+}
+
+#[test]
+fn test_display_roundtrip() {
+    for value in [
+        "every week",
+        "every 2 months",
+        "after 1 day",
+        "after 3 days",
+        "monday,friday",
+        "1,15",
+    ] {
+        let recurrence = Recurrence::from_string(value).unwrap();
+        assert_eq!(recurrence.to_string(), value);
+    }
+
+    let recurrence = Recurrence::from_string("every 2 weeks").unwrap();
+    let serialised = serde_json::to_string(&recurrence).unwrap();
+    assert_eq!(serialised, "\"every 2 weeks\"");
+    assert_eq!(
+        serde_json::from_str::<Recurrence>(&serialised).unwrap(),
+        recurrence
+    );
+}
+
+#[test]
+fn test_next_due_every() {
+    let recurrence = Recurrence::from_string("every month").unwrap();
+    // Calendar months, not 30 days
+    assert_eq!(
+        recurrence.next_due(
+            Some(local_time(2025, 1, 31, 9)),
+            local_time(2025, 1, 30, 12)
+        ),
+        Ok(local_time(2025, 2, 28, 9))
+    );
+
+    // Completing an overdue task skips the occurrences that are already in the past
+    let recurrence = Recurrence::from_string("every week").unwrap();
+    assert_eq!(
+        recurrence.next_due(Some(local_time(2025, 3, 3, 9)), local_time(2025, 3, 18, 12)),
+        Ok(local_time(2025, 3, 24, 9))
+    );
+
+    // Without a due date, count from the completion
+    assert_eq!(
+        recurrence.next_due(None, local_time(2025, 3, 18, 12)),
+        Ok(local_time(2025, 3, 25, 12))
+    );
+}
+
+#[test]
+fn test_next_due_after_completion() {
+    let recurrence = Recurrence::from_string("after 3 days").unwrap();
+    assert_eq!(
+        recurrence.next_due(Some(local_time(2025, 3, 1, 9)), local_time(2025, 3, 10, 12)),
+        Ok(local_time(2025, 3, 13, 12))
+    );
+}
+
+#[test]
+fn test_next_due_weekdays() {
+    // 2025-03-03 is a Monday
+    let recurrence = Recurrence::from_string("monday,friday").unwrap();
+    assert_eq!(
+        recurrence.next_due(Some(local_time(2025, 3, 3, 9)), local_time(2025, 3, 1, 12)),
+        Ok(local_time(2025, 3, 7, 9))
+    );
+    assert_eq!(
+        recurrence.next_due(Some(local_time(2025, 3, 7, 9)), local_time(2025, 3, 8, 12)),
+        Ok(local_time(2025, 3, 10, 9))
+    );
+    assert_eq!(
+        recurrence.next_due(None, local_time(2025, 3, 4, 12)),
+        Ok(local_time(2025, 3, 7, 0))
+    );
+}
+
+#[test]
+fn test_next_due_month_days() {
+    let recurrence = Recurrence::from_string("1,31").unwrap();
+    assert_eq!(
+        recurrence.next_due(Some(local_time(2025, 1, 31, 9)), local_time(2025, 1, 31, 8)),
+        Ok(local_time(2025, 2, 1, 9))
+    );
+    // February has no 31st
+    assert_eq!(
+        recurrence.next_due(Some(local_time(2025, 2, 1, 9)), local_time(2025, 2, 1, 10)),
+        Ok(local_time(2025, 3, 1, 9))
+    );
+}
+
+#[test]
+fn test_next_due_out_of_range() {
+    let last_day = Local.from_utc_datetime(&NaiveDate::MAX.and_hms_opt(0, 0, 0).unwrap());
+    for recurrence in ["every day", "after 1 year", "monday", "1,15"] {
+        let recurrence = Recurrence::from_string(recurrence).unwrap();
+        assert_true!(recurrence.next_due(Some(last_day), last_day).is_err());
+    }
+    let recurrence = Recurrence::from_string("every 4000000000 years").unwrap();
+    assert_true!(
+        recurrence
+            .next_due(None, local_time(2025, 3, 1, 9))
+            .is_err()
+    );
+}
@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Local};
+
+use crate::BeeError;
+
+use super::{Task, TaskStatus};
+
+/// The fields the tasks of a report can be sorted on
+const SORT_FIELDS: &[&str] = &[
+    "id",
+    "uuid",
+    "summary",
+    "description",
+    "status",
+    "project",
+    "tags",
+    "priority",
+    "urgency",
+    "annotations",
+    "created",
+    "due",
+    "end",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortField {
+    Id,
+    Uuid,
+    Summary,
+    Description,
+    Status,
+    Project,
+    Tags,
+    Priority,
+    Urgency,
+    Annotations,
+    Created,
+    Due,
+    End,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// The value of a task field, as compared when sorting. Text is compared regardless of
+/// the case.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Number(i64),
+    Text(String),
+    Date(DateTime<Local>),
+}
+
+impl SortField {
+    fn from_string(input: &str) -> Option<SortField> {
+        match input.to_lowercase().as_str() {
+            "id" => Some(SortField::Id),
+            "uuid" => Some(SortField::Uuid),
+            "summary" => Some(SortField::Summary),
+            "description" => Some(SortField::Description),
+            "status" => Some(SortField::Status),
+            "project" => Some(SortField::Project),
+            "tags" | "tag" => Some(SortField::Tags),
+            "priority" => Some(SortField::Priority),
+            "urgency" => Some(SortField::Urgency),
+            "annotations" => Some(SortField::Annotations),
+            "created" | "date_created" => Some(SortField::Created),
+            "due" | "date_due" => Some(SortField::Due),
+            "end" | "date_completed" => Some(SortField::End),
+            _ => None,
+        }
+    }
+
+    /// The value of the field for this task, None if the task doesn't have one
+    fn get_value(&self, task: &Task) -> Option<SortValue> {
+        match self {
+            SortField::Id => task.get_id().map(|id| SortValue::Number(id as i64)),
+            SortField::Uuid => Some(SortValue::Text(task.get_uuid().to_string())),
+            SortField::Summary => Some(SortValue::Text(task.get_summary().to_lowercase())),
+            SortField::Description => task
+                .get_description()
+                .as_ref()
+                .map(|description| SortValue::Text(description.to_lowercase())),
+            // Following the lifecycle of a task rather than the alphabet
+            SortField::Status => Some(SortValue::Number(match task.get_status() {
+                TaskStatus::Active => 0,
+                TaskStatus::Pending => 1,
+                TaskStatus::Completed => 2,
+                TaskStatus::Deleted => 3,
+            })),
+            SortField::Project => task
+                .get_project()
+                .as_ref()
+                .map(|project| SortValue::Text(project.get_name().to_lowercase())),
+            SortField::Tags => {
+                if task.get_tags().is_empty() {
+                    return None;
+                }
+                let mut tags: Vec<String> = task
+                    .get_tags()
+                    .iter()
+                    .map(|tag| tag.to_lowercase())
+                    .collect();
+                tags.sort();
+                Some(SortValue::Text(tags.join(" ")))
+            }
+            SortField::Priority => task
+                .get_priority()
+                .map(|priority| SortValue::Number(priority as i64)),
+            SortField::Urgency => task.get_computed_urgency().map(SortValue::Number),
+            SortField::Annotations => Some(SortValue::Number(task.get_annotations().len() as i64)),
+            SortField::Created => Some(SortValue::Date(*task.get_date_created())),
+            SortField::Due => task.get_date_due().map(SortValue::Date),
+            SortField::End => task.get_date_completed().map(SortValue::Date),
+        }
+    }
+}
+
+/// One criterion of a sort order, e.g. 'due+' sorts the tasks by due date, the
+/// earliest first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortKey {
+    field: SortField,
+    direction: SortDirection,
+}
+
+impl SortKey {
+    /// Parse a field name followed by '+' (ascending) or '-' (descending). Without a
+    /// suffix, the tasks are sorted in ascending order.
+    pub fn from_string(input: &str) -> Result<SortKey, BeeError> {
+        let input = input.trim();
+        let (name, direction) = if let Some(name) = input.strip_suffix('+') {
+            (name, SortDirection::Ascending)
+        } else if let Some(name) = input.strip_suffix('-') {
+            (name, SortDirection::Descending)
+        } else {
+            (input, SortDirection::Ascending)
+        };
+
+        match SortField::from_string(name) {
+            Some(field) => Ok(SortKey { field, direction }),
+            None => Err(BeeError::parse(format!(
+                "Unknown sort field '{}', expected one of {}",
+                name,
+                SORT_FIELDS.join(", ")
+            ))),
+        }
+    }
+
+    fn compare(&self, lhs: &Task, rhs: &Task) -> Ordering {
+        match (self.field.get_value(lhs), self.field.get_value(rhs)) {
+            (Some(lhs), Some(rhs)) => match self.direction {
+                SortDirection::Ascending => lhs.cmp(&rhs),
+                SortDirection::Descending => rhs.cmp(&lhs),
+            },
+            // The tasks without a value come last, whatever the direction
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// Parse a list of sort keys, e.g. the `sort` of a report
+pub fn parse_sort_keys(input: &[String]) -> Result<Vec<SortKey>, BeeError> {
+    input
+        .iter()
+        .flat_map(|keys| keys.split(','))
+        .filter(|key| !key.trim().is_empty())
+        .map(SortKey::from_string)
+        .collect()
+}
+
+/// Compare two tasks on each key in turn. The tasks that are equal on all the keys
+/// are in the default order of the reports: the most urgent first, then the most
+/// recent one.
+pub fn compare_tasks(keys: &[SortKey], lhs: &Task, rhs: &Task) -> Ordering {
+    for key in keys {
+        let ordering = key.compare(lhs, rhs);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    rhs.cmp(lhs)
+        .then_with(|| lhs.get_uuid().cmp(rhs.get_uuid()))
+}
+
+#[cfg(test)]
+#[path = "sort_test.rs"]
+mod sort_test;
@@ -0,0 +1,105 @@
+use all_asserts::assert_true;
+
+use super::*;
+use crate::task::{TaskData, TaskProperties, TaskStatus};
+
+fn add_task(data: &mut TaskData, arguments: &str) -> Task {
+    data.add_task(
+        &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+        TaskStatus::Pending,
+    )
+    .unwrap()
+    .clone()
+}
+
+fn sort(tasks: &[Task], keys: &[&str]) -> Vec<String> {
+    let keys: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+    let keys = parse_sort_keys(&keys).unwrap();
+    let mut tasks: Vec<&Task> = tasks.iter().collect();
+    tasks.sort_by(|lhs, rhs| compare_tasks(&keys, lhs, rhs));
+    tasks.iter().map(|t| t.get_summary().to_string()).collect()
+}
+
+#[test]
+fn test_parse_sort_keys() {
+    assert_eq!(
+        SortKey::from_string("due+").unwrap(),
+        SortKey {
+            field: SortField::Due,
+            direction: SortDirection::Ascending
+        }
+    );
+    assert_eq!(
+        SortKey::from_string("date_completed-").unwrap(),
+        SortKey {
+            field: SortField::End,
+            direction: SortDirection::Descending
+        }
+    );
+    assert_eq!(
+        SortKey::from_string("project").unwrap(),
+        SortKey {
+            field: SortField::Project,
+            direction: SortDirection::Ascending
+        }
+    );
+    assert_eq!(
+        parse_sort_keys(&["due+,project-".to_string(), "urgency-".to_string()])
+            .unwrap()
+            .len(),
+        3
+    );
+
+    let err = SortKey::from_string("colour+").unwrap_err();
+    assert_true!(err.get_message().contains("Unknown sort field 'colour'"));
+}
+
+#[test]
+fn test_sort_multiple_keys() {
+    let mut data = TaskData::default();
+    let tasks = vec![
+        add_task(&mut data, "a project:work"),
+        add_task(&mut data, "b project:home"),
+        add_task(&mut data, "c project:work due:2030-01-01"),
+        add_task(&mut data, "d project:Garden due:2030-01-01"),
+    ];
+
+    assert_eq!(sort(&tasks, &["project+"]), vec!["d", "b", "c", "a"]);
+    assert_eq!(
+        sort(&tasks, &["due+", "project-"]),
+        vec!["c", "d", "a", "b"]
+    );
+    assert_eq!(sort(&tasks, &["summary-"]), vec!["d", "c", "b", "a"]);
+}
+
+#[test]
+fn test_sort_missing_values_last() {
+    let mut data = TaskData::default();
+    let tasks = vec![
+        add_task(&mut data, "a"),
+        add_task(&mut data, "b due:2030-01-01"),
+        add_task(&mut data, "c due:2031-01-01"),
+    ];
+
+    assert_eq!(sort(&tasks, &["due+"]), vec!["b", "c", "a"]);
+    assert_eq!(sort(&tasks, &["due-"]), vec!["c", "b", "a"]);
+}
+
+#[test]
+fn test_sort_ties_are_stable() {
+    let mut data = TaskData::default();
+    let tasks = [add_task(&mut data, "a"), add_task(&mut data, "b")];
+
+    // Equal on the key, the tasks fall back to the default order, and never compare
+    // as equal
+    let keys = parse_sort_keys(&["status+".to_string()]).unwrap();
+    assert_eq!(
+        compare_tasks(&keys, &tasks[0], &tasks[1]),
+        compare_tasks(&keys, &tasks[1], &tasks[0]).reverse()
+    );
+    assert_true!(compare_tasks(&keys, &tasks[0], &tasks[1]) != Ordering::Equal);
+    assert_eq!(
+        compare_tasks(&keys, &tasks[0], &tasks[0].clone()),
+        Ordering::Equal
+    );
+}
@@ -4,9 +4,10 @@ use log::debug;
 use uuid::Uuid;
 
 use crate::{
+    BeeError,
     lexer::{Lexer, Token, TokenType},
     parser::BaseParser,
-    task::{DependsOnIdentifier, Project, TaskProperties, TaskStatus},
+    task::{DependsOnIdentifier, Priority, Project, Recurrence, TaskProperties, TaskStatus},
 };
 
 #[derive(Debug, Default)]
@@ -94,7 +95,48 @@ impl TaskPropertyParser {
         parser
     }
 
-    pub fn parse_task_properties(&mut self) -> Result<TaskProperties, String> {
+    /// Read the tokens making up a recurrence expression and return them as a string
+    /// that can be given to `Recurrence::from_string`. This stops on the first token
+    /// that is not part of the expression.
+    fn read_recurrence_expr(&mut self) -> String {
+        let mut expr = String::default();
+
+        if self.current_token.token_type == TokenType::WordString
+            && (self.current_token.literal == "every" || self.current_token.literal == "after")
+        {
+            expr.push_str(&self.current_token.literal);
+            self.next_token();
+            self.skip_whitespace();
+            if self.current_token.token_type == TokenType::Int {
+                expr.push(' ');
+                expr.push_str(&self.current_token.literal);
+                self.next_token();
+                self.skip_whitespace();
+            }
+            if self.current_token.token_type == TokenType::WordString {
+                expr.push(' ');
+                expr.push_str(&self.current_token.literal);
+                self.next_token();
+            }
+            return expr;
+        }
+
+        // Lists of days, e.g. 'monday,friday' or '1,15'
+        while self.current_token.token_type != TokenType::Blank
+            && self.current_token.token_type != TokenType::Eof
+        {
+            expr.push_str(&self.current_token.literal);
+            self.next_token();
+        }
+        expr
+    }
+
+    pub fn parse_task_properties(&mut self) -> Result<TaskProperties, BeeError> {
+        self.parse_task_properties_impl()
+            .map_err(|e| self.suggest_keyword_in_error(e))
+    }
+
+    fn parse_task_properties_impl(&mut self) -> Result<TaskProperties, BeeError> {
         let err_msg_prefix: String = "could not parse the task property expression. ".to_string();
         let mut props = TaskProperties::default();
         if self.current_token.token_type == TokenType::Eof {
@@ -117,6 +159,15 @@ impl TaskPropertyParser {
                 | TokenType::FilterTokDateCreatedAfter
                 | TokenType::FilterTokDateEndBefore
                 | TokenType::FilterTokDateEndAfter
+                | TokenType::DependsOnAny
+                | TokenType::BlocksAny
+                | TokenType::PriorityAbove
+                | TokenType::Description
+                | TokenType::TextMatch
+                | TokenType::WordMatch
+                | TokenType::Regex
+                | TokenType::Comparison
+                | TokenType::Date
                 | TokenType::LeftParenthesis
                 | TokenType::RightParenthesis => {
                     if let Some(summary) = props.summary {
@@ -131,16 +182,20 @@ impl TaskPropertyParser {
                     self.skip_whitespace();
 
                     if self.current_token.token_type != TokenType::WordString {
-                        return Err(format!(
-                            "Expected a token of type WordString following a TokenTypeFilterStatus, found '{}' (value: '{}')",
-                            self.peek_token.token_type, self.peek_token.literal
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a status after 'status:', found {}",
+                                    self.current_token.describe()
+                                ),
                         ));
                     }
 
                     let status = match TaskStatus::from_string(&self.current_token.literal) {
                         Ok(st) => st,
                         Err(e) => {
-                            return Err(e);
+                            return Err(self.locate_error(e, self.current_token.span.to_owned()));
                         }
                     };
                     props.status = Some(status);
@@ -151,12 +206,17 @@ impl TaskPropertyParser {
                     self.skip_whitespace();
 
                     if self.current_token.token_type != TokenType::WordString {
-                        return Err(format!(
-                            "Expected a token of type WordString following a TokenTypeProjectPrefix, found '{}' (value: '{}')",
-                            self.peek_token.token_type, self.peek_token.literal
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a project name after 'project:', found {}",
+                                    self.current_token.describe()
+                                ),
                         ));
                     }
 
+                    let project_start = self.current_token.span.start;
                     let mut project_name = self.current_token.literal.to_string();
 
                     while self.peek_token.token_type == TokenType::TagMinusPrefix
@@ -166,20 +226,27 @@ impl TaskPropertyParser {
                         self.next_token();
                     }
 
+                    let project_span = project_start..self.current_token.span.end;
                     if project_name.ends_with('.') {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "A project name cannot end with a '.' (name: '{}')",
-                                project_name
-                            ));
+                        return Err(self.error_at(
+                            project_span,
+                            err_msg_prefix
+                                + &format!(
+                                    "A project name cannot end with a '.' (name: '{}')",
+                                    project_name
+                                ),
+                        ));
                     }
 
                     if project_name.ends_with('-') {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "A project name cannot end with a '-' (name: '{}')",
-                                project_name
-                            ));
+                        return Err(self.error_at(
+                            project_span,
+                            err_msg_prefix
+                                + &format!(
+                                    "A project name cannot end with a '-' (name: '{}')",
+                                    project_name
+                                ),
+                        ));
                     }
 
                     if project_name.to_lowercase() == "none" {
@@ -221,11 +288,14 @@ impl TaskPropertyParser {
                             props.depends_on = Some(Vec::new());
                         }
                         _ => {
-                            return Err(err_msg_prefix
-                                + &format!(
-                                    "Expected a token of type Uuid or Int following a TokenTypeDependsOn, found '{}' (value: '{}')",
-                                    self.current_token.token_type, self.current_token.literal
-                                ));
+                            return Err(self.error_at(
+                                self.current_token.span.to_owned(),
+                                err_msg_prefix
+                                    + &format!(
+                                        "Expected a task ID or UUID after 'depends:', found {}",
+                                        self.current_token.describe()
+                                    ),
+                            ));
                         }
                     }
                     if !new_depends_on.is_empty() {
@@ -233,21 +303,83 @@ impl TaskPropertyParser {
                     }
                     self.next_token();
                 }
+                TokenType::Recur => {
+                    self.next_token();
+                    self.skip_whitespace();
+
+                    if self.current_token.token_type == TokenType::WordString
+                        && self.current_token.literal == "none"
+                    {
+                        props.recurrence = Some(None);
+                        self.next_token();
+                    } else {
+                        let start = self.current_token.span.start;
+                        let recurrence = match Recurrence::from_string(&self.read_recurrence_expr())
+                        {
+                            Ok(recurrence) => recurrence,
+                            Err(e) => {
+                                let e = e.with_prefix(&err_msg_prefix);
+                                let end = self.current_token.span.start;
+                                return Err(self.locate_error(e, start..end));
+                            }
+                        };
+                        props.recurrence = Some(Some(recurrence));
+                    }
+                }
+                TokenType::Priority => {
+                    self.next_token();
+                    self.skip_whitespace();
+
+                    if self.current_token.token_type != TokenType::WordString
+                        && self.current_token.token_type != TokenType::Int
+                    {
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a priority after 'priority:', found {}",
+                                    self.current_token.describe()
+                                ),
+                        ));
+                    }
+
+                    if self.current_token.literal.to_lowercase() == "none" {
+                        props.priority = Some(None);
+                    } else {
+                        let priority = match Priority::from_string(&self.current_token.literal) {
+                            Ok(priority) => priority,
+                            Err(e) => {
+                                let e = e.with_prefix(&err_msg_prefix);
+                                return Err(
+                                    self.locate_error(e, self.current_token.span.to_owned())
+                                );
+                            }
+                        };
+                        props.priority = Some(Some(priority));
+                    }
+                    self.next_token();
+                }
                 TokenType::FilterTokDateDue => {
                     self.next_token();
                     self.skip_whitespace();
 
                     if self.current_token.token_type != TokenType::WordString
                         && self.current_token.token_type != TokenType::Int
+                        && self.current_token.token_type != TokenType::Date
                     {
-                        return Err(err_msg_prefix
-                            + &format!(
-                                "Expected a token of type String or Int following a TokenTypeFilterDateEnd, found '{}' (value: '{}')",
-                                self.peek_token.token_type, self.peek_token.literal
-                            ));
+                        return Err(self.error_at(
+                            self.current_token.span.to_owned(),
+                            err_msg_prefix
+                                + &format!(
+                                    "Expected a date after 'due:', found {}",
+                                    self.current_token.describe()
+                                ),
+                        ));
                     }
 
-                    let time = self.read_date_expr()?;
+                    let time = self
+                        .read_date_expr()
+                        .map_err(|e| e.with_prefix(&err_msg_prefix))?;
                     props.date_due = Some(time);
                     self.next_token();
                 }
@@ -147,3 +147,120 @@ fn test_task_properties_parser() {
     };
     assert_eq!(tp, props);
 }
+
+#[test]
+fn test_recurrence() {
+    let tp = from_string("water the plants recur:every 2 weeks");
+    let props = TaskProperties {
+        summary: Some("water the plants".to_owned()),
+        recurrence: Some(Some(Recurrence::from_string("every 2 weeks").unwrap())),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let tp = from_string("recur:monday,friday +chore");
+    let props = TaskProperties {
+        tags_add: Some(vec!["chore".to_owned()]),
+        recurrence: Some(Some(Recurrence::from_string("monday,friday").unwrap())),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let tp = from_string("recur:1,15 pay rent");
+    let props = TaskProperties {
+        summary: Some("pay rent".to_owned()),
+        recurrence: Some(Some(Recurrence::from_string("1,15").unwrap())),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let tp = from_string("recur:after 3 days");
+    let props = TaskProperties {
+        recurrence: Some(Some(Recurrence::from_string("after 3 days").unwrap())),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let tp = from_string("recur:none");
+    let props = TaskProperties {
+        recurrence: Some(None),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let lexer = Lexer::new("recur:every 2 fortnights".to_string());
+    let mut parser = TaskPropertyParser::new(lexer);
+    assert_true!(parser.parse_task_properties().is_err());
+}
+
+#[test]
+fn test_priority() {
+    let tp = from_string("call the bank priority:H");
+    let props = TaskProperties {
+        summary: Some("call the bank".to_owned()),
+        priority: Some(Some(Priority::High)),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let tp = from_string("priority:2");
+    let props = TaskProperties {
+        priority: Some(Some(Priority::Medium)),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let tp = from_string("priority:none");
+    let props = TaskProperties {
+        priority: Some(None),
+        ..TaskProperties::default()
+    };
+    assert_eq!(tp, props);
+
+    let lexer = Lexer::new("priority:urgent".to_string());
+    let mut parser = TaskPropertyParser::new(lexer);
+    assert_true!(parser.parse_task_properties().is_err());
+}
+
+#[test]
+fn test_error_location() {
+    // A misspelled keyword is pointed out when the parsing fails
+    let lexer = Lexer::new("buy milk priorty:H due:tomorow".to_string());
+    let mut parser = TaskPropertyParser::new(lexer);
+    let err = parser.parse_task_properties().unwrap_err();
+    assert_true!(
+        err.get_message()
+            .ends_with("'priorty:' is not a keyword, did you mean 'priority:'?")
+    );
+    assert_eq!(
+        err.get_diagnostic("add buy milk priorty:H due:tomorow")
+            .unwrap(),
+        "    add buy milk priorty:H due:tomorow\n                               ^^^^^^^"
+    );
+
+    let lexer = Lexer::new("buy milk priority:urgent".to_string());
+    let mut parser = TaskPropertyParser::new(lexer);
+    match parser.parse_task_properties() {
+        Err(BeeError::Parse {
+            location: Some(location),
+            ..
+        }) => assert_eq!(location.span, 18..24),
+        other => panic!("Expected a located parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_words_ending_with_colon() {
+    // The words looking like a misspelled keyword are part of the summary
+    for summary in [
+        "Dues: pay the rent",
+        "Tag: me",
+        "Note: buy milk",
+        "priorty:H",
+    ] {
+        let lexer = Lexer::new(summary.to_string());
+        let mut parser = TaskPropertyParser::new(lexer);
+        let props = parser.parse_task_properties().unwrap();
+        assert_eq!(props.summary.as_deref(), Some(summary));
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::{Deserialize, Deserializer};
+use uuid::Uuid;
+
+use crate::BeeError;
+
+use super::{
+    Link, LinkType, Priority, Project, Task, TaskAnnotation, TaskData, TaskHistory, TaskStatus,
+    TimeInterval,
+};
+
+/// Attributes Taskwarrior computes or uses for its own bookkeeping. They have no
+/// meaning once imported, so they are dropped without warning.
+const IGNORED_ATTRIBUTES: [&str; 5] = ["id", "urgency", "modified", "mask", "imask"];
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TaskwarriorAnnotation {
+    #[serde(deserialize_with = "deserialize_date")]
+    entry: DateTime<Local>,
+    description: String,
+}
+
+/// A task as printed by Taskwarrior's `task export`
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TaskwarriorTask {
+    uuid: Uuid,
+    description: String,
+    status: String,
+
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    entry: Option<DateTime<Local>>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    start: Option<DateTime<Local>>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    end: Option<DateTime<Local>>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    due: Option<DateTime<Local>>,
+
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    annotations: Vec<TaskwarriorAnnotation>,
+    #[serde(default, deserialize_with = "deserialize_depends")]
+    depends: Vec<Uuid>,
+
+    /// User defined attributes, and the attributes Bee doesn't know about
+    #[serde(flatten)]
+    other: BTreeMap<String, serde_json::Value>,
+}
+
+/// What happened to a task when importing it
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportStatus {
+    Created,
+    /// Contains the task as it was before the import
+    Updated(Box<Task>),
+    Unchanged,
+    Skipped,
+}
+
+/// The result of importing a task, from Taskwarrior or an iCalendar file
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskImport {
+    pub status: ImportStatus,
+    /// Everything that could not be imported for this task
+    pub warnings: Vec<String>,
+}
+
+/// Taskwarrior writes its dates in UTC, e.g. '20250301T120000Z'
+fn parse_date(value: &str) -> Result<DateTime<Local>, BeeError> {
+    if let Ok(date) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Ok(date.and_utc().with_timezone(&Local));
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Local))
+        .map_err(|_| BeeError::parse(format!("Invalid Taskwarrior date '{}'", value)))
+}
+
+fn deserialize_date<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_date(&value).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<DateTime<Local>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) => parse_date(&value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// Older versions of Taskwarrior export the dependencies as a single comma separated
+/// string instead of a list
+fn deserialize_depends<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Depends {
+        List(Vec<String>),
+        String(String),
+    }
+
+    let values = match Depends::deserialize(deserializer)? {
+        Depends::List(values) => values,
+        Depends::String(value) => value.split(',').map(|v| v.to_owned()).collect(),
+    };
+    values
+        .iter()
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| Uuid::parse_str(v.trim()).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+impl TaskwarriorTask {
+    pub fn get_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    fn get_status(&self) -> Result<TaskStatus, BeeError> {
+        match self.status.as_str() {
+            "pending" | "waiting" if self.start.is_some() => Ok(TaskStatus::Active),
+            "pending" | "waiting" => Ok(TaskStatus::Pending),
+            "completed" => Ok(TaskStatus::Completed),
+            "deleted" => Ok(TaskStatus::Deleted),
+            _ => Err(BeeError::parse(format!(
+                "Task '{}' has an unknown status '{}'",
+                self.description, self.status
+            ))),
+        }
+    }
+}
+
+/// Parse the output of `task export`. Both a JSON array and one JSON object per line
+/// (used by old versions of Taskwarrior) are accepted.
+pub fn parse_taskwarrior_export(content: &str) -> Result<Vec<TaskwarriorTask>, BeeError> {
+    let err_msg = |e: serde_json::Error| {
+        BeeError::parse(format!("Unable to read the Taskwarrior export: {}", e))
+    };
+    if content.trim_start().starts_with('[') {
+        return serde_json::from_str(content).map_err(err_msg);
+    }
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line.trim().trim_end_matches(',')).map_err(err_msg))
+        .collect()
+}
+
+impl TaskData {
+    /// Import a task exported by Taskwarrior. The Taskwarrior UUID is kept, so that a
+    /// task that was already imported is updated instead of being duplicated.
+    ///
+    /// `known_uuids` are the UUIDs the dependencies can refer to, any other dependency is
+    /// dropped with a warning.
+    pub fn import_taskwarrior(
+        &mut self,
+        tw_task: &TaskwarriorTask,
+        known_uuids: &HashSet<Uuid>,
+    ) -> Result<TaskImport, BeeError> {
+        let mut warnings = Vec::new();
+
+        if tw_task.status == "recurring" {
+            warnings.push(format!(
+                "Task '{}' is the template of a recurring task and was skipped, its \
+                occurrences are imported as regular tasks.",
+                tw_task.description
+            ));
+            return Ok(TaskImport {
+                status: ImportStatus::Skipped,
+                warnings,
+            });
+        }
+
+        let previous = self.tasks.get(&tw_task.uuid).cloned();
+        let mut task = previous.clone().unwrap_or_else(|| Task {
+            uuid: tw_task.uuid,
+            date_created: Local::now(),
+            ..Task::default()
+        });
+
+        task.summary = tw_task.description.to_owned();
+        task.status = tw_task.get_status()?;
+        task.tags = tw_task.tags.to_owned();
+        task.project = tw_task.project.to_owned().map(Project::from);
+        task.priority = match &tw_task.priority {
+            Some(priority) => Some(
+                Priority::from_string(priority)
+                    .map_err(|e| e.with_prefix(&format!("Task '{}': ", tw_task.description)))?,
+            ),
+            None => None,
+        };
+        if let Some(entry) = tw_task.entry {
+            task.date_created = entry;
+        }
+        // Taskwarrior only knows when an active task was started
+        if let Some(start) = tw_task.start
+            && task.status == TaskStatus::Active
+            && !task.intervals.iter().any(|i| i.end.is_none())
+        {
+            task.intervals.push(TimeInterval { start, end: None });
+        }
+        task.date_completed = tw_task.end;
+        task.date_due = tw_task.due;
+        task.annotations = tw_task
+            .annotations
+            .iter()
+            .map(|a| TaskAnnotation {
+                value: a.description.to_owned(),
+                time: a.entry,
+            })
+            .collect();
+
+        let mut depends_on = HashSet::new();
+        for dep_uuid in &tw_task.depends {
+            if known_uuids.contains(dep_uuid) {
+                depends_on.insert(*dep_uuid);
+            } else {
+                warnings.push(format!(
+                    "Task '{}' depends on the unknown task '{}', the dependency was not imported.",
+                    tw_task.description, dep_uuid
+                ));
+            }
+        }
+        // The order of the links is not meaningful, only replace them if they changed
+        if depends_on != task.get_depends_on().into_iter().cloned().collect() {
+            task.links.retain(|l| l.link_type != LinkType::DependsOn);
+            for dep_uuid in &tw_task.depends {
+                if depends_on.contains(dep_uuid) {
+                    task.links.push(Link {
+                        from: task.uuid,
+                        to: dep_uuid.to_owned(),
+                        link_type: LinkType::DependsOn,
+                    });
+                }
+            }
+        }
+
+        let not_imported: Vec<String> = tw_task
+            .other
+            .keys()
+            .filter(|k| !IGNORED_ATTRIBUTES.contains(&k.as_str()))
+            .map(|k| format!("'{}'", k))
+            .collect();
+        if !not_imported.is_empty() {
+            warnings.push(format!(
+                "Task '{}': the attributes {} are not supported and were not imported.",
+                tw_task.description,
+                not_imported.join(", ")
+            ));
+        }
+
+        let status = match previous {
+            Some(previous) if previous == task => {
+                return Ok(TaskImport {
+                    status: ImportStatus::Unchanged,
+                    warnings,
+                });
+            }
+            Some(previous) => {
+                task.history.push(TaskHistory {
+                    value: "Updated from Taskwarrior".to_string(),
+                    time: Local::now(),
+                });
+                ImportStatus::Updated(Box::new(previous))
+            }
+            None => {
+                task.history.push(TaskHistory {
+                    value: "Imported from Taskwarrior".to_string(),
+                    time: Local::now(),
+                });
+                ImportStatus::Created
+            }
+        };
+
+        self.update_id(&mut task);
+        self.tasks.insert(task.uuid, task);
+        Ok(TaskImport { status, warnings })
+    }
+}
+
+#[cfg(test)]
+#[path = "taskwarrior_test.rs"]
+mod taskwarrior_test;
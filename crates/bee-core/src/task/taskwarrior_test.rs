@@ -0,0 +1,170 @@
+use all_asserts::{assert_false, assert_true};
+use chrono::{TimeZone, Utc};
+
+use super::*;
+
+const EXPORT: &str = r#"[
+{"id":1,"description":"write the report","entry":"20250301T120000Z","modified":"20250302T120000Z",
+ "project":"work.report","status":"pending","tags":["work","urgent"],
+ "uuid":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1","urgency":8.2,"due":"20250310T170000Z",
+ "annotations":[{"entry":"20250302T080000Z","description":"ask for the figures"}]},
+{"id":2,"description":"send the report","entry":"20250301T130000Z","status":"pending",
+ "uuid":"1c0b7c2e-0f7d-4b44-8a11-1a6c2b1e9f02","depends":["8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1"],
+ "start":"20250303T090000Z","estimate":"2h","priority":"H"},
+{"id":0,"description":"buy milk","entry":"20250228T100000Z","end":"20250301T100000Z",
+ "status":"completed","uuid":"3f7c1a8e-7b8e-4e5b-bb0e-6c1de7a6a703"}
+]"#;
+
+fn import_all(data: &mut TaskData, tasks: &[TaskwarriorTask]) -> Vec<TaskImport> {
+    let known_uuids: HashSet<Uuid> = tasks.iter().map(|t| *t.get_uuid()).collect();
+    tasks
+        .iter()
+        .map(|t| data.import_taskwarrior(t, &known_uuids).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_parse_taskwarrior_export() {
+    let tasks = parse_taskwarrior_export(EXPORT).unwrap();
+    assert_eq!(tasks.len(), 3);
+    assert_eq!(tasks[0].get_description(), "write the report");
+    assert_eq!(
+        tasks[0].due,
+        Some(
+            Utc.with_ymd_and_hms(2025, 3, 10, 17, 0, 0)
+                .unwrap()
+                .with_timezone(&Local)
+        )
+    );
+    assert_eq!(tasks[1].depends, vec![*tasks[0].get_uuid()]);
+
+    // One task per line, with the dependencies as a string
+    let content = r#"{"description":"a","status":"pending","uuid":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1"},
+{"description":"b","status":"pending","uuid":"1c0b7c2e-0f7d-4b44-8a11-1a6c2b1e9f02","depends":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1"}
+"#;
+    let tasks = parse_taskwarrior_export(content).unwrap();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[1].depends, vec![*tasks[0].get_uuid()]);
+
+    assert_true!(parse_taskwarrior_export("[{\"description\":\"a\"}]").is_err());
+}
+
+#[test]
+fn test_import_taskwarrior() {
+    let tw_tasks = parse_taskwarrior_export(EXPORT).unwrap();
+    let mut data = TaskData::default();
+    let results = import_all(&mut data, &tw_tasks);
+
+    assert_true!(results.iter().all(|r| r.status == ImportStatus::Created));
+    // Only the UDA is reported, not Taskwarrior's bookkeeping
+    assert_true!(results[0].warnings.is_empty());
+    assert_eq!(
+        results[1].warnings,
+        vec![
+            "Task 'send the report': the attributes 'estimate' are not \
+            supported and were not imported."
+                .to_string()
+        ]
+    );
+
+    let report = data.get_task_map().get(tw_tasks[0].get_uuid()).unwrap();
+    assert_eq!(report.get_summary(), "write the report");
+    assert_eq!(report.get_status(), &TaskStatus::Pending);
+    assert_eq!(
+        report.get_tags(),
+        &vec!["work".to_string(), "urgent".to_string()]
+    );
+    assert_eq!(
+        report.get_project(),
+        &Some(Project::from("work.report".to_string()))
+    );
+    assert_eq!(report.get_date_due(), &tw_tasks[0].due);
+    assert_eq!(report.get_annotations().len(), 1);
+    assert_eq!(
+        report.get_annotations()[0].get_value(),
+        "ask for the figures"
+    );
+
+    let send = data.get_task_map().get(tw_tasks[1].get_uuid()).unwrap();
+    assert_eq!(send.get_status(), &TaskStatus::Active);
+    assert_eq!(send.get_priority(), &Some(Priority::High));
+    assert_eq!(report.get_priority(), &None);
+    assert_true!(send.depends_on(tw_tasks[0].get_uuid()));
+
+    let milk = data.get_task_map().get(tw_tasks[2].get_uuid()).unwrap();
+    assert_eq!(milk.get_status(), &TaskStatus::Completed);
+    assert_eq!(milk.get_date_completed(), &tw_tasks[2].end);
+    assert_eq!(milk.get_id(), None);
+
+    data.upkeep().unwrap();
+    assert_true!(
+        data.get_task_map()
+            .get(tw_tasks[0].get_uuid())
+            .unwrap()
+            .blocks(tw_tasks[1].get_uuid())
+    );
+}
+
+#[test]
+fn test_import_taskwarrior_again() {
+    let tw_tasks = parse_taskwarrior_export(EXPORT).unwrap();
+    let mut data = TaskData::default();
+    import_all(&mut data, &tw_tasks);
+
+    // Importing the same export doesn't change anything
+    let results = import_all(&mut data, &tw_tasks);
+    assert_true!(results.iter().all(|r| r.status == ImportStatus::Unchanged));
+    assert_eq!(data.get_task_map().len(), 3);
+
+    // The modified tasks are updated
+    let mut modified = tw_tasks.clone();
+    modified[0].description = "write the final report".to_string();
+    modified[0].status = "completed".to_string();
+    let results = import_all(&mut data, &modified);
+    match &results[0].status {
+        ImportStatus::Updated(previous) => assert_eq!(previous.get_summary(), "write the report"),
+        other => panic!("Unexpected import status {:?}", other),
+    }
+    assert_eq!(results[1].status, ImportStatus::Unchanged);
+    assert_eq!(data.get_task_map().len(), 3);
+
+    let report = data.get_task_map().get(tw_tasks[0].get_uuid()).unwrap();
+    assert_eq!(report.get_summary(), "write the final report");
+    assert_eq!(report.get_status(), &TaskStatus::Completed);
+    assert_eq!(
+        report.get_history().last().unwrap().value,
+        "Updated from Taskwarrior"
+    );
+}
+
+#[test]
+fn test_import_taskwarrior_unknown_dependency() {
+    let tw_tasks = parse_taskwarrior_export(EXPORT).unwrap();
+    let mut data = TaskData::default();
+    let result = data
+        .import_taskwarrior(&tw_tasks[1], &HashSet::default())
+        .unwrap();
+
+    assert_eq!(result.status, ImportStatus::Created);
+    assert_eq!(result.warnings.len(), 2);
+    assert_false!(
+        data.get_task_map()
+            .get(tw_tasks[1].get_uuid())
+            .unwrap()
+            .depends_on(tw_tasks[0].get_uuid())
+    );
+}
+
+#[test]
+fn test_import_taskwarrior_recurring_template() {
+    let tw_tasks = parse_taskwarrior_export(
+        r#"[{"description":"water the plants","status":"recurring","recur":"weekly",
+        "uuid":"8ae5e3d0-5d7e-4a1c-9a0e-2d27c5f1b0a1"}]"#,
+    )
+    .unwrap();
+    let mut data = TaskData::default();
+    let results = import_all(&mut data, &tw_tasks);
+
+    assert_eq!(results[0].status, ImportStatus::Skipped);
+    assert_true!(data.get_task_map().is_empty());
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate};
+use serde::Serialize;
+
+use crate::BeeError;
+use crate::parser::{matches_day_string, matches_month_string, matches_week_string};
+
+use super::{Task, TaskStatus};
+
+/// The length of the periods the tasks are counted over
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThroughputPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl ThroughputPeriod {
+    pub fn from_string(input: &str) -> Result<ThroughputPeriod, BeeError> {
+        match input.to_lowercase().as_str() {
+            v if v == "daily" || matches_day_string(v) => Ok(ThroughputPeriod::Day),
+            v if v == "weekly" || matches_week_string(v) => Ok(ThroughputPeriod::Week),
+            v if v == "monthly" || matches_month_string(v) => Ok(ThroughputPeriod::Month),
+            _ => Err(BeeError::parse(format!(
+                "Invalid period '{}', expected daily, weekly or monthly",
+                input
+            ))),
+        }
+    }
+
+    /// The first day of the period the date is in. Weeks start on Monday.
+    fn get_start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            ThroughputPeriod::Day => date,
+            ThroughputPeriod::Week => date
+                .checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64))
+                .unwrap_or(date),
+            ThroughputPeriod::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    fn get_next_start(&self, start: NaiveDate) -> NaiveDate {
+        match self {
+            ThroughputPeriod::Day => start.checked_add_days(Days::new(1)),
+            ThroughputPeriod::Week => start.checked_add_days(Days::new(7)),
+            ThroughputPeriod::Month => start.checked_add_months(Months::new(1)),
+        }
+        .unwrap_or(NaiveDate::MAX)
+    }
+
+    /// The name of a period, e.g. '2025-03-10', '2025-W11' or '2025-03'
+    fn get_label(&self, start: NaiveDate) -> String {
+        match self {
+            ThroughputPeriod::Day => start.format("%Y-%m-%d").to_string(),
+            ThroughputPeriod::Week => {
+                let week = start.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            ThroughputPeriod::Month => start.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// What happened to the tasks during a period
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ThroughputBucket {
+    pub period: String,
+    pub start: NaiveDate,
+    pub added: usize,
+    pub completed: usize,
+    pub deleted: usize,
+    /// The tasks that are still pending or active at the end of the period
+    pub remaining: usize,
+    /// The tasks completed so far, at the end of the period
+    pub done: usize,
+}
+
+/// The tasks added, completed and deleted over time, from the period the first task
+/// was created in to the current one
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Throughput {
+    pub period: ThroughputPeriod,
+    pub buckets: Vec<ThroughputBucket>,
+}
+
+impl Throughput {
+    pub fn new<'a>(
+        tasks: impl Iterator<Item = &'a Task>,
+        period: ThroughputPeriod,
+        now: DateTime<Local>,
+    ) -> Throughput {
+        // When each task was created, completed and deleted. The tasks created as
+        // deleted have a completion date instead of a deletion in their history.
+        let events: Vec<(NaiveDate, Option<NaiveDate>, Option<NaiveDate>)> = tasks
+            .map(|task| {
+                let completed = *task.get_date_completed();
+                let (completed, deleted) = match task.get_status() {
+                    TaskStatus::Completed => (completed, None),
+                    TaskStatus::Deleted => (None, task.get_date_deleted().or(completed)),
+                    TaskStatus::Pending | TaskStatus::Active => (None, None),
+                };
+                (
+                    task.get_date_created().date_naive(),
+                    completed.map(|date| date.date_naive()),
+                    deleted.map(|date| date.date_naive()),
+                )
+            })
+            .collect();
+
+        let mut buckets = Vec::new();
+        let Some(first) = events.iter().map(|(created, _, _)| *created).min() else {
+            return Throughput { period, buckets };
+        };
+
+        let last = period.get_start(now.date_naive());
+        let mut start = period.get_start(first);
+        while start <= last {
+            let end = period.get_next_start(start);
+            let in_period = |date: &Option<NaiveDate>| date.is_some_and(|d| start <= d && d < end);
+            let before_end = |date: &Option<NaiveDate>| date.is_some_and(|d| d < end);
+
+            let mut bucket = ThroughputBucket {
+                period: period.get_label(start),
+                start,
+                added: 0,
+                completed: 0,
+                deleted: 0,
+                remaining: 0,
+                done: 0,
+            };
+            for (created, completed, deleted) in &events {
+                if in_period(&Some(*created)) {
+                    bucket.added += 1;
+                }
+                if in_period(completed) {
+                    bucket.completed += 1;
+                }
+                if in_period(deleted) {
+                    bucket.deleted += 1;
+                }
+                if before_end(completed) {
+                    bucket.done += 1;
+                } else if *created < end && !before_end(deleted) {
+                    bucket.remaining += 1;
+                }
+            }
+            buckets.push(bucket);
+            start = end;
+        }
+        Throughput { period, buckets }
+    }
+}
+
+#[cfg(test)]
+#[path = "throughput_test.rs"]
+mod throughput_test;
@@ -0,0 +1,109 @@
+use all_asserts::assert_true;
+use chrono::TimeZone;
+
+use super::*;
+use crate::task::{TaskHistory, TaskStatus};
+
+fn at(month: u32, day: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, month, day, 12, 0, 0).unwrap()
+}
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+fn bucket(period: &str, start: NaiveDate, counts: [usize; 5]) -> ThroughputBucket {
+    ThroughputBucket {
+        period: period.to_string(),
+        start,
+        added: counts[0],
+        completed: counts[1],
+        deleted: counts[2],
+        remaining: counts[3],
+        done: counts[4],
+    }
+}
+
+fn tasks() -> Vec<Task> {
+    vec![
+        Task {
+            status: TaskStatus::Completed,
+            date_created: at(3, 4),
+            date_completed: Some(at(3, 11)),
+            ..Default::default()
+        },
+        Task {
+            status: TaskStatus::Deleted,
+            date_created: at(3, 11),
+            history: vec![TaskHistory {
+                value: "Status changed from 'pending' to 'deleted'".to_string(),
+                time: at(3, 18),
+            }],
+            ..Default::default()
+        },
+        Task {
+            status: TaskStatus::Pending,
+            date_created: at(3, 12),
+            ..Default::default()
+        },
+    ]
+}
+
+#[test]
+fn test_period_from_string() {
+    assert_eq!(
+        ThroughputPeriod::from_string("daily"),
+        Ok(ThroughputPeriod::Day)
+    );
+    assert_eq!(
+        ThroughputPeriod::from_string("week"),
+        Ok(ThroughputPeriod::Week)
+    );
+    assert_eq!(
+        ThroughputPeriod::from_string("Monthly"),
+        Ok(ThroughputPeriod::Month)
+    );
+    assert_true!(ThroughputPeriod::from_string("yearly").is_err());
+}
+
+#[test]
+fn test_get_date_deleted() {
+    let mut task = Task::default();
+    assert_eq!(task.get_date_deleted(), None);
+    task.delete();
+    assert_true!(task.get_date_deleted().is_some());
+
+    // Restoring a task forgets when it was deleted
+    task.status = TaskStatus::Pending;
+    assert_eq!(task.get_date_deleted(), None);
+}
+
+#[test]
+fn test_throughput_per_week() {
+    let tasks = tasks();
+    let throughput = Throughput::new(tasks.iter(), ThroughputPeriod::Week, at(3, 19));
+    assert_eq!(
+        throughput.buckets,
+        vec![
+            bucket("2025-W10", date(3, 3), [1, 0, 0, 1, 0]),
+            bucket("2025-W11", date(3, 10), [2, 1, 0, 2, 1]),
+            bucket("2025-W12", date(3, 17), [0, 0, 1, 1, 1]),
+        ]
+    );
+}
+
+#[test]
+fn test_throughput_per_month() {
+    let tasks = tasks();
+    let throughput = Throughput::new(tasks.iter(), ThroughputPeriod::Month, at(4, 2));
+    assert_eq!(
+        throughput.buckets,
+        vec![
+            bucket("2025-03", date(3, 1), [3, 1, 1, 1, 1]),
+            bucket("2025-04", date(4, 1), [0, 0, 0, 1, 1]),
+        ]
+    );
+
+    let throughput = Throughput::new([].iter(), ThroughputPeriod::Day, at(4, 2));
+    assert_true!(throughput.buckets.is_empty());
+}
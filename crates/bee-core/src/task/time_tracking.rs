@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Task;
+
+/// A period during which a task was active. The interval of a task that is still
+/// active has no end.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TimeInterval {
+    pub start: DateTime<Local>,
+    #[serde(default)]
+    pub end: Option<DateTime<Local>>,
+}
+
+impl TimeInterval {
+    /// The time spent in this interval between `from` and `to`. An open interval ends
+    /// at `now`.
+    pub fn duration_between(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+        now: DateTime<Local>,
+    ) -> Duration {
+        let start = self.start.max(from);
+        let end = self.end.unwrap_or(now).min(to);
+        if end > start {
+            end - start
+        } else {
+            Duration::zero()
+        }
+    }
+}
+
+/// Format a duration as hours and minutes, e.g. '2h 05m'
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimesheetTask {
+    pub uuid: Uuid,
+    pub summary: String,
+    pub duration: Duration,
+}
+
+/// The time tracked over a period, per task, project and tag
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Timesheet {
+    /// The tasks with some time tracked, the longest first
+    pub tasks: Vec<TimesheetTask>,
+    /// The tasks without a project are under an empty name
+    pub projects: BTreeMap<String, Duration>,
+    /// A task with several tags counts for each of them
+    pub tags: BTreeMap<String, Duration>,
+    pub total: Duration,
+}
+
+impl Timesheet {
+    pub fn new<'a>(
+        tasks: impl Iterator<Item = &'a Task>,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+        now: DateTime<Local>,
+    ) -> Timesheet {
+        let mut timesheet = Timesheet {
+            total: Duration::zero(),
+            ..Timesheet::default()
+        };
+
+        for task in tasks {
+            let duration = task.get_time_tracked_between(from, to, now);
+            if duration.is_zero() {
+                continue;
+            }
+
+            let project = task
+                .get_project()
+                .as_ref()
+                .map(|p| p.get_name().to_owned())
+                .unwrap_or_default();
+            *timesheet
+                .projects
+                .entry(project)
+                .or_insert(Duration::zero()) += duration;
+            for tag in task.get_tags() {
+                *timesheet
+                    .tags
+                    .entry(tag.to_owned())
+                    .or_insert(Duration::zero()) += duration;
+            }
+            timesheet.total += duration;
+            timesheet.tasks.push(TimesheetTask {
+                uuid: *task.get_uuid(),
+                summary: task.get_summary().to_owned(),
+                duration,
+            });
+        }
+
+        timesheet
+            .tasks
+            .sort_by(|a, b| b.duration.cmp(&a.duration).then(a.summary.cmp(&b.summary)));
+        timesheet
+    }
+}
+
+#[cfg(test)]
+#[path = "time_tracking_test.rs"]
+mod time_tracking_test;
@@ -0,0 +1,124 @@
+use all_asserts::assert_true;
+use chrono::TimeZone;
+
+use super::*;
+use crate::task::{Project, TaskData, TaskProperties, TaskStatus};
+
+fn at(hour: u32, minute: u32) -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(2025, 3, 10, hour, minute, 0)
+        .unwrap()
+}
+
+fn interval(start: DateTime<Local>, end: Option<DateTime<Local>>) -> TimeInterval {
+    TimeInterval { start, end }
+}
+
+#[test]
+fn test_duration_between() {
+    let i = interval(at(9, 0), Some(at(11, 0)));
+    assert_eq!(
+        i.duration_between(at(0, 0), at(23, 0), at(23, 0)),
+        Duration::hours(2)
+    );
+    // Only the part of the interval within the range counts
+    assert_eq!(
+        i.duration_between(at(10, 30), at(23, 0), at(23, 0)),
+        Duration::minutes(30)
+    );
+    assert_eq!(
+        i.duration_between(at(12, 0), at(23, 0), at(23, 0)),
+        Duration::zero()
+    );
+
+    // An open interval ends now
+    let i = interval(at(9, 0), None);
+    assert_eq!(
+        i.duration_between(at(0, 0), at(23, 0), at(9, 45)),
+        Duration::minutes(45)
+    );
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::zero()), "0h 00m");
+    assert_eq!(format_duration(Duration::minutes(125)), "2h 05m");
+    assert_eq!(format_duration(Duration::hours(30)), "30h 00m");
+}
+
+#[test]
+fn test_start_stop_records_intervals() {
+    let mut data = TaskData::default();
+    let uuid = *data
+        .add_task(
+            &TaskProperties::from(&["write report".to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap()
+        .get_uuid();
+
+    let mut start = TaskProperties::default();
+    start.set_active_status(true);
+    let mut stop = TaskProperties::default();
+    stop.set_active_status(false);
+
+    data.apply(&uuid, &start).unwrap();
+    let task = data.get_owned(&uuid).unwrap();
+    assert_eq!(task.get_intervals().len(), 1);
+    assert_eq!(task.get_intervals()[0].end, None);
+
+    data.apply(&uuid, &stop).unwrap();
+    data.apply(&uuid, &start).unwrap();
+    let mut task = data.get_owned(&uuid).unwrap();
+    assert_eq!(task.get_intervals().len(), 2);
+    assert_true!(task.get_intervals()[0].end.is_some());
+
+    // Completing the task closes the open interval
+    task.done().unwrap();
+    assert_true!(task.get_intervals().iter().all(|i| i.end.is_some()));
+}
+
+#[test]
+fn test_timesheet() {
+    let mut work = Task {
+        summary: "write report".to_owned(),
+        project: Some(Project::from("work".to_owned())),
+        tags: vec!["office".to_owned(), "writing".to_owned()],
+        intervals: vec![
+            interval(at(9, 0), Some(at(10, 0))),
+            interval(at(14, 0), Some(at(14, 30))),
+        ],
+        ..Task::default()
+    };
+    work.uuid = Uuid::new_v4();
+    let home = Task {
+        uuid: Uuid::new_v4(),
+        summary: "fix the sink".to_owned(),
+        tags: vec!["writing".to_owned()],
+        intervals: vec![interval(at(18, 0), None)],
+        ..Task::default()
+    };
+    let untracked = Task {
+        uuid: Uuid::new_v4(),
+        summary: "buy milk".to_owned(),
+        ..Task::default()
+    };
+
+    let tasks = [work.clone(), home.clone(), untracked];
+    let timesheet = Timesheet::new(tasks.iter(), at(0, 0), at(23, 0), at(20, 0));
+
+    assert_eq!(timesheet.total, Duration::minutes(210));
+    assert_eq!(timesheet.tasks.len(), 2);
+    assert_eq!(timesheet.tasks[0].uuid, home.uuid);
+    assert_eq!(timesheet.tasks[0].duration, Duration::hours(2));
+    assert_eq!(timesheet.tasks[1].duration, Duration::minutes(90));
+    assert_eq!(timesheet.projects["work"], Duration::minutes(90));
+    assert_eq!(timesheet.projects[""], Duration::hours(2));
+    assert_eq!(timesheet.tags["office"], Duration::minutes(90));
+    assert_eq!(timesheet.tags["writing"], Duration::minutes(210));
+
+    // Only the morning
+    let timesheet = Timesheet::new(tasks.iter(), at(0, 0), at(12, 0), at(20, 0));
+    assert_eq!(timesheet.total, Duration::hours(1));
+    assert_eq!(timesheet.tasks.len(), 1);
+}
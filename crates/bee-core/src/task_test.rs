@@ -31,7 +31,9 @@ fn test_task_status_from_str() {
 
     assert_eq!(
         TaskStatus::from_string("invalid"),
-        Err("Invalid task status name".to_string())
+        Err(BeeError::parse(
+            "Invalid status 'invalid', expected active, pending, completed or deleted"
+        ))
     );
 }
 
@@ -419,7 +421,7 @@ fn test_sort_tasks() {
         )
         .single()
         .unwrap();
-    let mut tasks = vec![
+    let mut tasks = [
         Task {
             id: Some(2),
             urgency: Some(2),
@@ -438,7 +440,7 @@ fn test_sort_tasks() {
     assert_eq!(tasks[0].id, Some(1));
     assert_eq!(tasks[1].id, Some(2));
 
-    let mut tasks = vec![
+    let mut tasks = [
         Task {
             id: Some(2),
             urgency: Some(2),
@@ -457,7 +459,7 @@ fn test_sort_tasks() {
     assert_eq!(tasks[0].id, Some(1));
     assert_eq!(tasks[1].id, Some(2));
 
-    let mut tasks = vec![
+    let mut tasks = [
         Task {
             id: Some(2),
             urgency: None,
@@ -476,7 +478,7 @@ fn test_sort_tasks() {
     assert_eq!(tasks[0].id, Some(1));
     assert_eq!(tasks[1].id, Some(2));
 
-    let mut tasks = vec![
+    let mut tasks = [
         Task {
             id: Some(2),
             urgency: None,
@@ -495,3 +497,117 @@ fn test_sort_tasks() {
     assert_eq!(tasks[0].id, Some(1));
     assert_eq!(tasks[1].id, Some(2));
 }
+
+#[test]
+fn test_done_recurring_task() {
+    let mut task = setup_task();
+    let mut props = setup_task_property();
+    props.recurrence = Some(Some(Recurrence::from_string("every week").unwrap()));
+    let _ = task.apply(&props);
+    assert_true!(task.get_recurrence().is_some());
+
+    let next_task = task.done().unwrap().unwrap();
+    assert_eq!(task.get_status(), &TaskStatus::Completed);
+    assert_ne!(next_task.get_uuid(), task.get_uuid());
+    assert_eq!(next_task.get_status(), &TaskStatus::Pending);
+    assert_eq!(next_task.get_summary(), task.get_summary());
+    assert_eq!(next_task.get_tags(), task.get_tags());
+    assert_eq!(next_task.get_recurrence(), task.get_recurrence());
+    assert_true!(next_task.get_date_due().unwrap() > Local::now());
+
+    // Completing it again should not create another occurrence
+    assert_true!(task.done().unwrap().is_none());
+
+    // Tasks that are not recurring do not have a next occurrence
+    let mut task = setup_task();
+    assert_true!(task.done().unwrap().is_none());
+
+    // The task is not completed when its next occurrence is out of range
+    let mut task = setup_task();
+    task.recurrence = Some(Recurrence::from_string("every 4000000000 years").unwrap());
+    assert_true!(task.done().is_err());
+    assert_eq!(task.get_status(), &TaskStatus::Pending);
+}
+
+#[test]
+fn test_apply_priority() {
+    let mut task = setup_task();
+    let mut props = setup_task_property();
+    props.priority = Some(Some(Priority::High));
+    let _ = task.apply(&props);
+    assert_eq!(task.get_priority(), &Some(Priority::High));
+    assert_eq!(
+        task.get_history().last().unwrap().value,
+        "Priority set to 'H'"
+    );
+
+    props.priority = Some(None);
+    let _ = task.apply(&props);
+    assert_eq!(task.get_priority(), &None);
+}
+
+#[test]
+fn test_priority_from_string() {
+    assert_eq!(Priority::from_string("H"), Ok(Priority::High));
+    assert_eq!(Priority::from_string("medium"), Ok(Priority::Medium));
+    assert_eq!(Priority::from_string("1"), Ok(Priority::Low));
+    assert_true!(Priority::from_string("4").is_err());
+    assert_true!(Priority::from_string("urgent").is_err());
+    assert_true!(Priority::Low < Priority::High);
+}
+
+#[test]
+fn test_compute_urgency_priority() {
+    let mut task = setup_task();
+    assert_eq!(task.compute_urgency_with(&[]), Ok(0));
+
+    task.priority = Some(Priority::High);
+    assert_eq!(task.compute_urgency_with(&[]), Ok(6));
+
+    let coefficients = [
+        CoeffientField {
+            field: "priority".to_string(),
+            value: None,
+            coefficient: 1,
+        },
+        CoeffientField {
+            field: "priority".to_string(),
+            value: Some("H".to_string()),
+            coefficient: 10,
+        },
+    ];
+    assert_eq!(task.compute_urgency_with(&coefficients), Ok(10));
+    task.priority = Some(Priority::Low);
+    assert_eq!(task.compute_urgency_with(&coefficients), Ok(1));
+
+    let invalid = [CoeffientField {
+        field: "priority".to_string(),
+        value: Some("urgent".to_string()),
+        coefficient: 1,
+    }];
+    assert_true!(task.compute_urgency_with(&invalid).is_err());
+}
+
+#[test]
+fn test_apply_description() {
+    let mut task = setup_task();
+    let mut props = setup_task_property();
+    props.set_description(&Some("Some notes\non two lines".to_string()));
+    let _ = task.apply(&props);
+    assert_eq!(
+        task.get_description(),
+        &Some("Some notes\non two lines".to_string())
+    );
+    assert_eq!(
+        task.get_history().last().unwrap().value,
+        "Description updated"
+    );
+
+    props.set_description(&None);
+    let _ = task.apply(&props);
+    assert_eq!(task.get_description(), &None);
+    assert_eq!(
+        task.get_history().last().unwrap().value,
+        "Description removed"
+    );
+}
@@ -0,0 +1,504 @@
+use std::panic::{self, UnwindSafe};
+
+use log::{debug, error};
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use bee_actions::{ActionRegistry, command_parser::ParsedCommand};
+use bee_core::{
+    BeeError,
+    config::get_config,
+    filters::{self, Filter},
+    task::Task,
+};
+use bee_storage::{
+    execute::{load_action, run_action},
+    storage::Store,
+};
+
+use crate::printer::JsonPrinter;
+
+/// An error sent back with its HTTP status
+#[derive(Debug, PartialEq)]
+pub struct ApiError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: &str) -> ApiError {
+        ApiError {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// The errors of the actions are due to the request, the others to the server
+impl From<BeeError> for ApiError {
+    fn from(error: BeeError) -> Self {
+        let status = match error {
+            BeeError::UnknownTask(_) => 404,
+            BeeError::InvalidTransition(_) => 409,
+            BeeError::Parse { .. } | BeeError::InvalidCommand(_) | BeeError::Hook(_) => 400,
+            BeeError::Storage(_)
+            | BeeError::Config(_)
+            | BeeError::Remote(_)
+            | BeeError::Output(_) => 500,
+        };
+        ApiError {
+            status,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The requests the API can handle
+#[derive(Debug, PartialEq)]
+pub enum Endpoint {
+    /// GET /tasks?filter=<filter>
+    List { filter: String },
+    /// GET /tasks/<uuid>
+    Get { uuid: Uuid },
+    /// POST /tasks
+    Add { arguments: String },
+    /// PATCH /tasks/<uuid>
+    Modify { uuid: Uuid, arguments: String },
+    /// POST /tasks/<uuid>/done
+    Done { uuid: Uuid },
+    /// DELETE /tasks/<uuid>
+    Delete { uuid: Uuid },
+    /// POST /undo
+    Undo,
+}
+
+/// The body of the requests adding or modifying a task, e.g.
+/// '{"arguments": "buy milk +home due:tomorrow"}'
+#[derive(Deserialize)]
+struct ArgumentsBody {
+    arguments: String,
+}
+
+fn read_arguments(body: &str) -> Result<String, ApiError> {
+    serde_json::from_str::<ArgumentsBody>(body)
+        .map(|b| b.arguments)
+        .map_err(|e| ApiError::new(400, &format!("Invalid request body: {}", e)))
+}
+
+/// Read a parameter of the query string, '+' standing for a space
+fn get_query_parameter(query: &str, name: &str) -> Result<Option<String>, ApiError> {
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        if key == name {
+            return percent_decode_str(&value.replace('+', " "))
+                .decode_utf8()
+                .map(|v| Some(v.into_owned()))
+                .map_err(|_| ApiError::new(400, "Invalid encoding in the query string."));
+        }
+    }
+    Ok(None)
+}
+
+impl Endpoint {
+    pub fn from_request(method: &str, url: &str, body: &str) -> Result<Endpoint, ApiError> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let parse_uuid = |value: &str| {
+            Uuid::parse_str(value)
+                .map_err(|_| ApiError::new(404, &format!("'{}' is not a valid UUID.", value)))
+        };
+
+        match (method, segments.as_slice()) {
+            ("GET", ["tasks"]) => Ok(Endpoint::List {
+                filter: get_query_parameter(query, "filter")?.unwrap_or_default(),
+            }),
+            ("POST", ["tasks"]) => Ok(Endpoint::Add {
+                arguments: read_arguments(body)?,
+            }),
+            ("GET", ["tasks", uuid]) => Ok(Endpoint::Get {
+                uuid: parse_uuid(uuid)?,
+            }),
+            ("PATCH", ["tasks", uuid]) => Ok(Endpoint::Modify {
+                uuid: parse_uuid(uuid)?,
+                arguments: read_arguments(body)?,
+            }),
+            ("DELETE", ["tasks", uuid]) => Ok(Endpoint::Delete {
+                uuid: parse_uuid(uuid)?,
+            }),
+            ("POST", ["tasks", uuid, "done"]) => Ok(Endpoint::Done {
+                uuid: parse_uuid(uuid)?,
+            }),
+            ("POST", ["undo"]) => Ok(Endpoint::Undo),
+            (_, ["tasks"]) | (_, ["tasks", _]) | (_, ["tasks", _, "done"]) | (_, ["undo"]) => {
+                Err(ApiError::new(
+                    405,
+                    &format!("Method {} is not allowed on {}", method, path),
+                ))
+            }
+            _ => Err(ApiError::new(404, &format!("Unknown endpoint {}", path))),
+        }
+    }
+
+    /// The task the endpoint works on, which must exist
+    fn get_target(&self) -> Option<&Uuid> {
+        match self {
+            Endpoint::Get { uuid }
+            | Endpoint::Modify { uuid, .. }
+            | Endpoint::Done { uuid }
+            | Endpoint::Delete { uuid } => Some(uuid),
+            Endpoint::List { .. } | Endpoint::Add { .. } | Endpoint::Undo => None,
+        }
+    }
+
+    /// The endpoints printing tasks return them, the others return the tasks they changed
+    fn is_read_only(&self) -> bool {
+        matches!(self, Endpoint::List { .. } | Endpoint::Get { .. })
+    }
+
+    /// The command line doing the same, logged with the undos
+    fn describe(&self) -> String {
+        match self {
+            Endpoint::List { filter } => format!("{} list", filter).trim().to_string(),
+            Endpoint::Get { uuid } => format!("{} info", uuid),
+            Endpoint::Add { arguments } => format!("add {}", arguments),
+            Endpoint::Modify { uuid, arguments } => format!("{} modify {}", uuid, arguments),
+            Endpoint::Done { uuid } => format!("{} done", uuid),
+            Endpoint::Delete { uuid } => format!("{} delete", uuid),
+            Endpoint::Undo => "undo".to_string(),
+        }
+    }
+
+    /// Build the command the CLI would have parsed. Unlike the CLI, the listing is not
+    /// restricted by the default report and the active context is ignored.
+    fn to_parsed_command(&self) -> Result<ParsedCommand, ApiError> {
+        let (name, filter, arguments): (&str, Box<dyn Filter>, Vec<String>) = match self {
+            Endpoint::List { filter } => ("list", filters::from(&[filter.to_owned()])?, vec![]),
+            Endpoint::Get { uuid } => ("info", uuid_filter(uuid)?, vec![]),
+            Endpoint::Add { arguments } => (
+                "add",
+                filters::from(&get_config().get_default_report().filters)?,
+                vec![arguments.to_owned()],
+            ),
+            Endpoint::Modify { uuid, arguments } => {
+                ("modify", uuid_filter(uuid)?, vec![arguments.to_owned()])
+            }
+            Endpoint::Done { uuid } => ("done", uuid_filter(uuid)?, vec![]),
+            Endpoint::Delete { uuid } => ("delete", uuid_filter(uuid)?, vec![]),
+            Endpoint::Undo => ("undo", filters::new_empty(), vec![]),
+        };
+
+        let mut command = ActionRegistry::get_parsed_commands()
+            .into_iter()
+            .find(|c| c.command == name)
+            .unwrap_or_else(|| panic!("The action '{}' is not registered", name));
+        command.filters = filter;
+        command.arguments = arguments;
+        command.report_kind = get_config().get_default_report().clone();
+        Ok(command)
+    }
+}
+
+fn uuid_filter(uuid: &Uuid) -> Result<Box<dyn Filter>, BeeError> {
+    filters::from(&[uuid.to_string()])
+}
+
+/// Run the action of an endpoint the way the command line does: load the tasks, run
+/// the action and its hooks, then write what changed and the undo log.
+fn run_endpoint<S: Store>(
+    endpoint: &Endpoint,
+    printer: &JsonPrinter,
+) -> Result<Vec<Task>, ApiError> {
+    let mut command = endpoint.to_parsed_command()?;
+    let mut action = load_action::<S>(&mut command, S::load_contexts()?)?;
+    if let Some(uuid) = endpoint.get_target()
+        && !action.get_tasks().get_task_map().contains_key(uuid)
+    {
+        return Err(ApiError::new(404, &format!("Task '{}' not found.", uuid)));
+    }
+
+    let changes = run_action::<S>(action.as_mut(), printer, &endpoint.describe())?;
+    if endpoint.is_read_only() {
+        Ok(printer.take_tasks())
+    } else {
+        Ok(changes.into_iter().map(|c| c.after).collect())
+    }
+}
+
+/// Handle a request, returning the status and the JSON body of the response
+pub fn handle_request<S: Store>(method: &str, url: &str, body: &str) -> (u16, String) {
+    debug!("{} {}", method, url);
+    catch_panic(|| respond_to::<S>(method, url, body))
+}
+
+/// Answer 500 when handling a request panics, so that one request can't stop the server
+fn catch_panic(handler: impl FnOnce() -> (u16, String) + UnwindSafe) -> (u16, String) {
+    panic::catch_unwind(handler).unwrap_or_else(|payload| {
+        let reason = payload
+            .downcast_ref::<&str>()
+            .map(|r| r.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        error!("Handling the request panicked: {}", reason);
+        (
+            500,
+            json!({"error": "Internal error while handling the request."}).to_string(),
+        )
+    })
+}
+
+fn respond_to<S: Store>(method: &str, url: &str, body: &str) -> (u16, String) {
+    let printer = JsonPrinter::default();
+    let result = Endpoint::from_request(method, url, body)
+        .and_then(|endpoint| run_endpoint::<S>(&endpoint, &printer).map(|t| (endpoint, t)));
+
+    match result {
+        Ok((endpoint, tasks)) => {
+            let status = if matches!(endpoint, Endpoint::Add { .. }) {
+                201
+            } else {
+                200
+            };
+            let output = printer.into_output(tasks);
+            (
+                status,
+                serde_json::to_string(&output).expect("Failed to serialize the response"),
+            )
+        }
+        Err(e) => {
+            let output = printer.into_output(Vec::new());
+            (
+                e.status,
+                json!({"error": e.message, "messages": output.messages}).to_string(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+    use bee_core::task::TaskStatus;
+    use bee_storage::storage::JsonStore;
+
+    use super::*;
+
+    const UUID: &str = "f1b1b6a4-3c5e-4a8e-9d3c-2b7e0e6f6a11";
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(
+            catch_panic(|| (200, "{}".to_string())),
+            (200, "{}".to_string())
+        );
+
+        let (status, content) = catch_panic(|| panic!("the handler failed"));
+        assert_eq!(status, 500);
+        assert_true!(content.contains("Internal error"));
+    }
+
+    #[test]
+    fn test_from_request() {
+        let uuid = Uuid::parse_str(UUID).unwrap();
+        assert_eq!(
+            Endpoint::from_request("GET", "/tasks", ""),
+            Ok(Endpoint::List {
+                filter: String::new()
+            })
+        );
+        assert_eq!(
+            Endpoint::from_request("GET", "/tasks?filter=project%3Awork+%2Boffice", ""),
+            Ok(Endpoint::List {
+                filter: "project:work +office".to_string()
+            })
+        );
+        assert_eq!(
+            Endpoint::from_request("POST", "/tasks", r#"{"arguments": "buy milk +home"}"#),
+            Ok(Endpoint::Add {
+                arguments: "buy milk +home".to_string()
+            })
+        );
+        assert_eq!(
+            Endpoint::from_request("GET", &format!("/tasks/{}", UUID), ""),
+            Ok(Endpoint::Get { uuid })
+        );
+        assert_eq!(
+            Endpoint::from_request(
+                "PATCH",
+                &format!("/tasks/{}/", UUID),
+                r#"{"arguments": "+urgent"}"#
+            ),
+            Ok(Endpoint::Modify {
+                uuid,
+                arguments: "+urgent".to_string()
+            })
+        );
+        assert_eq!(
+            Endpoint::from_request("POST", &format!("/tasks/{}/done", UUID), ""),
+            Ok(Endpoint::Done { uuid })
+        );
+        assert_eq!(
+            Endpoint::from_request("DELETE", &format!("/tasks/{}", UUID), ""),
+            Ok(Endpoint::Delete { uuid })
+        );
+        assert_eq!(
+            Endpoint::from_request("POST", "/undo", ""),
+            Ok(Endpoint::Undo)
+        );
+    }
+
+    #[test]
+    fn test_from_request_errors() {
+        let status = |method: &str, url: &str, body: &str| {
+            Endpoint::from_request(method, url, body)
+                .unwrap_err()
+                .status
+        };
+        assert_eq!(status("GET", "/projects", ""), 404);
+        assert_eq!(status("GET", "/tasks/12", ""), 404);
+        assert_eq!(status("PUT", "/tasks", ""), 405);
+        assert_eq!(status("GET", "/undo", ""), 405);
+        assert_eq!(status("POST", "/tasks", "buy milk"), 400);
+        assert_eq!(status("POST", "/tasks", r#"{"summary": "buy milk"}"#), 400);
+    }
+
+    #[test]
+    fn test_api_error_from_bee_error() {
+        let status = |error: BeeError| ApiError::from(error).status;
+        assert_eq!(status(BeeError::parse("Invalid filter")), 400);
+        assert_eq!(status(BeeError::UnknownTask("No task 12".to_string())), 404);
+        assert_eq!(
+            status(BeeError::InvalidTransition("Already active".to_string())),
+            409
+        );
+        assert_eq!(status(BeeError::Storage("Disk full".to_string())), 500);
+        assert_eq!(
+            ApiError::from(BeeError::Hook("Rejected".to_string())),
+            ApiError::new(400, "Rejected")
+        );
+    }
+
+    #[test]
+    fn test_to_parsed_command() {
+        let uuid = Uuid::parse_str(UUID).unwrap();
+        let command = Endpoint::Modify {
+            uuid,
+            arguments: "+urgent".to_string(),
+        }
+        .to_parsed_command()
+        .unwrap();
+        assert_eq!(command.command, "modify");
+        assert_eq!(command.arguments, vec!["+urgent".to_string()]);
+        assert_eq!(&command.filters, &uuid_filter(&uuid).unwrap());
+
+        let command = Endpoint::Undo.to_parsed_command().unwrap();
+        assert_eq!(command.command, "undo");
+        assert_true!(command.arguments.is_empty());
+
+        assert_true!(
+            Endpoint::List {
+                filter: "project:".to_string()
+            }
+            .to_parsed_command()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let uuid = Uuid::parse_str(UUID).unwrap();
+        assert_eq!(Endpoint::Done { uuid }.describe(), format!("{} done", UUID));
+        assert_eq!(
+            Endpoint::List {
+                filter: String::new()
+            }
+            .describe(),
+            "list"
+        );
+    }
+
+    fn parse(content: &str) -> serde_json::Value {
+        serde_json::from_str(content).unwrap()
+    }
+
+    fn load_task(uuid: &str) -> Task {
+        let tasks = JsonStore::load_tasks(None, None).unwrap();
+        tasks.get_task_map()[&Uuid::parse_str(uuid).unwrap()].clone()
+    }
+
+    #[test]
+    fn test_handle_request() {
+        let data_home = tempfile::tempdir().unwrap();
+        // SAFETY: this is the only test of the crate reading or writing the store, the
+        // others never look for its data files
+        unsafe { std::env::set_var("BEE_DATA_HOME", data_home.path()) };
+
+        let (status, content) =
+            handle_request::<JsonStore>("POST", "/tasks", r#"{"arguments": "buy milk +home"}"#);
+        assert_eq!(status, 201);
+        let output = parse(&content);
+        assert_eq!(output["tasks"].as_array().unwrap().len(), 1);
+        assert_eq!(output["tasks"][0]["summary"], "buy milk");
+        assert_eq!(output["tasks"][0]["tags"], json!(["home"]));
+        let uuid = output["tasks"][0]["uuid"].as_str().unwrap().to_string();
+        assert_eq!(load_task(&uuid).get_summary(), "buy milk");
+
+        let (status, content) = handle_request::<JsonStore>("GET", "/tasks?filter=%2Bhome", "");
+        assert_eq!(status, 200);
+        assert_eq!(parse(&content)["tasks"][0]["uuid"], uuid.as_str());
+
+        let (status, content) = handle_request::<JsonStore>(
+            "PATCH",
+            &format!("/tasks/{}", uuid),
+            r#"{"arguments": "project:shop"}"#,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(parse(&content)["tasks"][0]["project"]["name"], "shop");
+        assert_eq!(
+            load_task(&uuid).get_project().as_ref().unwrap().get_name(),
+            "shop"
+        );
+
+        let (status, content) =
+            handle_request::<JsonStore>("POST", &format!("/tasks/{}/done", uuid), "");
+        assert_eq!(status, 200);
+        assert_eq!(parse(&content)["tasks"][0]["status"], "Completed");
+        assert_eq!(*load_task(&uuid).get_status(), TaskStatus::Completed);
+
+        let (status, content) = handle_request::<JsonStore>("POST", "/undo", "");
+        assert_eq!(status, 200);
+        assert_eq!(parse(&content)["tasks"][0]["status"], "Pending");
+        assert_eq!(*load_task(&uuid).get_status(), TaskStatus::Pending);
+
+        let (status, content) =
+            handle_request::<JsonStore>("DELETE", &format!("/tasks/{}", uuid), "");
+        assert_eq!(status, 200);
+        assert_eq!(parse(&content)["tasks"][0]["status"], "Deleted");
+        assert_eq!(*load_task(&uuid).get_status(), TaskStatus::Deleted);
+
+        // The undone 'done' isn't logged anymore, the last action is the deletion
+        let commands: Vec<String> = JsonStore::load_undos(10)
+            .unwrap()
+            .into_iter()
+            .map(|undo| undo.command)
+            .collect();
+        assert_eq!(
+            commands,
+            vec![
+                "add buy milk +home".to_string(),
+                format!("{} modify project:shop", uuid),
+                format!("{} delete", uuid),
+            ]
+        );
+
+        // Listing doesn't log anything
+        let (status, _) = handle_request::<JsonStore>("GET", "/tasks", "");
+        assert_eq!(status, 200);
+        assert_eq!(JsonStore::load_undos(10).unwrap().len(), 3);
+
+        let (status, content) = handle_request::<JsonStore>("GET", &format!("/tasks/{}", UUID), "");
+        assert_eq!(status, 404);
+        assert_true!(parse(&content)["error"].as_str().unwrap().contains(UUID));
+    }
+}
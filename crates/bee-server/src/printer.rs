@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use bee_core::{BeeError, Printer, config::ReportConfig, task::Task};
+use serde::Serialize;
+
+/// Collect what the actions print, to send it back in the response instead of
+/// writing it to stdout
+#[derive(Default)]
+pub struct JsonPrinter {
+    messages: RefCell<Vec<String>>,
+    errors: RefCell<Vec<String>>,
+    tasks: RefCell<Vec<Task>>,
+}
+
+/// The body of a successful response
+#[derive(Serialize, Debug, PartialEq)]
+pub struct JsonOutput {
+    pub messages: Vec<String>,
+    pub errors: Vec<String>,
+    pub tasks: Vec<Task>,
+}
+
+impl JsonPrinter {
+    /// The tasks printed by the action, the oldest first
+    pub fn take_tasks(&self) -> Vec<Task> {
+        let mut tasks = self.tasks.take();
+        tasks.sort_by_key(|t| *t.get_date_created());
+        tasks
+    }
+
+    pub fn into_output(self, tasks: Vec<Task>) -> JsonOutput {
+        JsonOutput {
+            messages: self.messages.into_inner(),
+            errors: self.errors.into_inner(),
+            tasks,
+        }
+    }
+}
+
+impl Printer for JsonPrinter {
+    fn print_list_of_tasks(&self, tasks: Vec<&Task>, _: &ReportConfig) -> Result<(), BeeError> {
+        self.tasks
+            .borrow_mut()
+            .extend(tasks.into_iter().map(|t| t.to_owned()));
+        Ok(())
+    }
+
+    fn print_task_info(&self, task: &Task) -> Result<(), BeeError> {
+        self.tasks.borrow_mut().push(task.to_owned());
+        Ok(())
+    }
+
+    fn show_help(&self, _: &HashMap<String, String>) -> Result<(), BeeError> {
+        Err(BeeError::InvalidCommand(
+            "The help is not available through the API.".to_string(),
+        ))
+    }
+
+    fn show_information_message(&self, message: &str) {
+        self.messages.borrow_mut().push(message.to_string());
+    }
+
+    fn error(&self, message: &str) {
+        self.errors.borrow_mut().push(message.to_string());
+    }
+
+    fn print_raw(&self, message: &str) {
+        self.messages.borrow_mut().push(message.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use all_asserts::assert_true;
+
+    use super::*;
+    use bee_core::task::{TaskData, TaskProperties, TaskStatus};
+
+    #[test]
+    fn test_collect_output() {
+        let mut data = TaskData::default();
+        for summary in ["first", "second"] {
+            data.add_task(
+                &TaskProperties::from(&[summary.to_owned()]).unwrap(),
+                TaskStatus::Pending,
+            )
+            .unwrap();
+        }
+
+        let printer = JsonPrinter::default();
+        printer.show_information_message("Created task 1.");
+        printer.error("Something went wrong");
+        printer
+            .print_list_of_tasks(data.to_vec(), &ReportConfig::default())
+            .unwrap();
+        assert_true!(printer.show_help(&HashMap::new()).is_err());
+
+        let tasks = printer.take_tasks();
+        assert_eq!(tasks.len(), 2);
+        assert_true!(tasks[0].get_date_created() <= tasks[1].get_date_created());
+
+        let output = printer.into_output(tasks);
+        assert_eq!(output.messages, vec!["Created task 1.".to_string()]);
+        assert_eq!(output.errors, vec!["Something went wrong".to_string()]);
+    }
+}
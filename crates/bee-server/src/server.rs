@@ -0,0 +1,68 @@
+mod api;
+mod printer;
+
+use std::process::exit;
+
+use bee_core::config::{self, StorageBackend, get_config};
+use bee_storage::{
+    sqlite_storage::SqliteStore,
+    storage::{JsonStore, Store},
+};
+use log::{error, info};
+use tiny_http::{Header, Request, Response, Server};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+fn respond<S: Store>(mut request: Request) {
+    let mut body = String::new();
+    let (status, content) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => api::handle_request::<S>(request.method().as_str(), request.url(), &body),
+        Err(e) => (
+            400,
+            serde_json::json!({"error": format!("Unable to read the request: {}", e)}).to_string(),
+        ),
+    };
+
+    let header = Header::from_bytes("Content-Type", "application/json")
+        .expect("Invalid Content-Type header");
+    let response = Response::from_string(content)
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        error!("Unable to send the response: {}", e);
+    }
+}
+
+/// The requests are handled one after the other, like successive commands
+fn serve<S: Store>(server: Server) {
+    for request in server.incoming_requests() {
+        respond::<S>(request);
+    }
+}
+
+fn main() {
+    env_logger::init();
+
+    if let Err(msg) = config::load_config() {
+        eprintln!("Error: {}", msg);
+        exit(1);
+    }
+
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let server = match Server::http(&address) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Error: Unable to listen on {}: {}", address, e);
+            exit(1);
+        }
+    };
+    info!("Listening on http://{}", address);
+    println!("Listening on http://{}", address);
+
+    match get_config().storage {
+        StorageBackend::Json => serve::<JsonStore>(server),
+        StorageBackend::Sqlite => serve::<SqliteStore>(server),
+    }
+}
@@ -0,0 +1,96 @@
+use log::{debug, trace};
+
+use bee_actions::{
+    ActionRegistry, TaskAction,
+    command_parser::ParsedCommand,
+    hooks::{HookRunner, TaskChange, get_changes},
+};
+use bee_core::{BeeError, Printer, config::get_config, context::Contexts, task::TaskProperties};
+
+use crate::storage::Store;
+
+/// Load the tasks of a parsed command and build its action, along with the undo log
+/// and the contexts
+pub fn load_action<S: Store>(
+    command: &mut ParsedCommand,
+    contexts: Contexts,
+) -> Result<Box<dyn TaskAction>, BeeError> {
+    let undos = S::load_undos(get_config().undo_depth)?;
+    debug!("Loaded {} undos", undos.len());
+    trace!("Undos: {:?}", undos);
+
+    let props = if command.arguments_as_filters {
+        None
+    } else {
+        Some(TaskProperties::from(&command.arguments)?)
+    };
+
+    let mut tasks = S::load_tasks(Some(&command.filters), props)?;
+    command.filters.convert_id_to_uuid(tasks.get_id_to_uuid());
+
+    for undo_action in &undos {
+        tasks.set_undos(&undo_action.tasks);
+    }
+
+    let mut action = ActionRegistry::get_action_from_command_parser(command);
+    action.set_tasks(tasks);
+    action.set_undos(undos);
+    action.set_redos(S::load_redos()?);
+    action.set_contexts(contexts);
+    Ok(action)
+}
+
+/// Perform an action and its hooks, then write what it changed. Nothing is written for
+/// the actions only showing the tasks. `command_line` is logged with the undos.
+/// The changes of the tasks are returned.
+pub fn run_action<S: Store>(
+    action: &mut dyn TaskAction,
+    printer: &dyn Printer,
+    command_line: &str,
+) -> Result<Vec<TaskChange>, BeeError> {
+    let tasks_before = action.get_tasks().clone();
+    let contexts_before = action.get_contexts().clone();
+    let undo_count = action.get_undos().len();
+    let redo_count = action.get_redos().len();
+
+    // A pre-hook rejecting a change aborts the action before anything is written
+    let hooks = HookRunner::from_config();
+    let hook_changes = hooks.run_action(action, printer)?;
+    let changes = get_changes(&tasks_before, action.get_tasks());
+
+    if !changes.is_empty() {
+        S::write_tasks(action.get_tasks())?;
+    }
+    if action.get_contexts() != &contexts_before {
+        S::write_contexts(action.get_contexts())?;
+    }
+
+    let mut undos = action.get_undos().to_owned();
+    let mut redos = action.get_redos().to_owned();
+    let is_new_action = undos.iter().any(|undo| undo.is_new());
+    if is_new_action || undos.len() != undo_count || redos.len() != redo_count {
+        // A new action makes the actions that were undone impossible to redo
+        if is_new_action {
+            redos.clear();
+        }
+        for undo in undos.iter_mut().filter(|undo| undo.is_new()) {
+            undo.command = command_line.to_owned();
+        }
+        S::log_undo(get_config().undo_depth, undos)?;
+        S::log_redos(redos)?;
+    }
+
+    hooks.run_post_hooks(&hook_changes, printer);
+    Ok(changes)
+}
+
+/// Load the tasks of a parsed command, perform its action and write the result
+pub fn execute<S: Store>(
+    command: &mut ParsedCommand,
+    contexts: Contexts,
+    printer: &dyn Printer,
+    command_line: &str,
+) -> Result<Vec<TaskChange>, BeeError> {
+    let mut action = load_action::<S>(command, contexts)?;
+    run_action::<S>(action.as_mut(), printer, command_line)
+}
@@ -1 +1,3 @@
+pub mod execute;
+pub mod sqlite_storage;
 pub mod storage;
@@ -0,0 +1,628 @@
+use log::debug;
+use rusqlite::{Connection, params, params_from_iter, types::Value};
+use serde::Serialize;
+use uuid::Uuid;
+
+use bee_actions::ActionUndo;
+use bee_core::{
+    BeeError,
+    context::Contexts,
+    filters::{self, Filter, FilterCondition},
+    task::{DependsOnIdentifier, Task, TaskData, TaskProperties, TaskStatus},
+};
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::storage::{JsonStore, Store, find_data_file, get_sqlite_file_path};
+
+#[cfg(test)]
+#[path = "sqlite_storage_test.rs"]
+mod sqlite_storage_test;
+
+/// The task itself is stored as JSON in the `data` column. The other columns are
+/// only there so that the filters can be run by SQLite. The `id` column is the
+/// only source of truth for the ID of a task.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS tasks (
+    uuid TEXT PRIMARY KEY NOT NULL,
+    id INTEGER,
+    status TEXT NOT NULL,
+    project TEXT,
+    date_created INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS tasks_id ON tasks(id);
+CREATE INDEX IF NOT EXISTS tasks_status ON tasks(status);
+CREATE INDEX IF NOT EXISTS tasks_project ON tasks(project);
+
+CREATE TABLE IF NOT EXISTS task_tags (
+    uuid TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (uuid, tag)
+);
+CREATE INDEX IF NOT EXISTS task_tags_tag ON task_tags(tag);
+
+CREATE TABLE IF NOT EXISTS undos (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    data TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS redos (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    data TEXT NOT NULL
+);
+
+-- A single row, holding the contexts as JSON
+CREATE TABLE IF NOT EXISTS contexts (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    data TEXT NOT NULL
+);
+";
+
+// Stay well below the maximum number of parameters SQLite accepts in a query
+const MAX_QUERY_PARAMS: usize = 500;
+
+#[derive(Default)]
+pub struct SqliteStore {}
+
+impl Store for SqliteStore {
+    #[allow(clippy::borrowed_box)]
+    fn load_tasks(
+        filter: Option<&Box<dyn Filter>>,
+        props: Option<TaskProperties>,
+    ) -> Result<TaskData, BeeError> {
+        let conn = open_database()?;
+        load_tasks_from(&conn, filter, props)
+    }
+
+    fn write_tasks(data: &TaskData) -> Result<TaskData, BeeError> {
+        let mut conn = open_database()?;
+        write_tasks_to(&mut conn, data)
+    }
+
+    fn load_undos(last_count: usize) -> Result<Vec<ActionUndo>, BeeError> {
+        let conn = open_database()?;
+        load_undos_from(&conn, last_count)
+    }
+
+    fn log_undo(count: usize, updated_undos: Vec<ActionUndo>) -> Result<(), BeeError> {
+        let mut conn = open_database()?;
+        log_undo_to(&mut conn, count, updated_undos)
+    }
+
+    fn load_redos() -> Result<Vec<ActionUndo>, BeeError> {
+        let conn = open_database()?;
+        load_redos_from(&conn)
+    }
+
+    fn log_redos(redos: Vec<ActionUndo>) -> Result<(), BeeError> {
+        let mut conn = open_database()?;
+        log_redos_to(&mut conn, redos)
+    }
+
+    fn load_contexts() -> Result<Contexts, BeeError> {
+        let conn = open_database()?;
+        load_contexts_from(&conn)
+    }
+
+    fn write_contexts(contexts: &Contexts) -> Result<(), BeeError> {
+        let conn = open_database()?;
+        write_contexts_to(&conn, contexts)
+    }
+}
+
+/// Copy the tasks and the undo log of the JSON store into the SQLite database.
+/// The JSON files are left untouched.
+/// Returns the number of tasks that were migrated.
+pub fn migrate_from_json() -> Result<usize, BeeError> {
+    if find_data_file().is_err() {
+        return Err(BeeError::Storage(
+            "No JSON data file was found, there is nothing to migrate.".to_string(),
+        ));
+    }
+
+    let mut conn = open_database()?;
+    let task_count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
+        .map_err(db_error)?;
+    if task_count > 0 {
+        return Err(BeeError::Storage(format!(
+            "The database '{}' already contains tasks, refusing to migrate into it.",
+            get_sqlite_file_path()
+        )));
+    }
+
+    let data = JsonStore::load_tasks(None, None)?;
+    let undos = JsonStore::load_undos(usize::MAX)?;
+    import_into(&mut conn, &data, undos)
+}
+
+fn import_into(
+    conn: &mut Connection,
+    data: &TaskData,
+    undos: Vec<ActionUndo>,
+) -> Result<usize, BeeError> {
+    write_tasks_to(conn, data)?;
+    log_undo_to(conn, 0, undos)?;
+    Ok(data.get_task_map().len())
+}
+
+fn db_error(e: rusqlite::Error) -> BeeError {
+    BeeError::Storage(format!("Database error: {}", e))
+}
+
+/// The JSON stored in the `data` column
+fn to_json<T: Serialize + ?Sized>(value: &T, what: &str) -> Result<String, BeeError> {
+    serde_json::to_string(value)
+        .map_err(|e| BeeError::Storage(format!("Unable to serialize the {}: {}", what, e)))
+}
+
+fn open_database() -> Result<Connection, BeeError> {
+    let path = get_sqlite_file_path();
+    if let Some(parent) = Path::new(&path).parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(|e| {
+            BeeError::Storage(format!(
+                "Unable to create the directory {:?}: {}",
+                parent, e
+            ))
+        })?;
+    }
+
+    debug!("Opening the database at {}", path);
+    let conn = Connection::open(&path)
+        .map_err(|e| BeeError::Storage(format!("Unable to open the database '{}': {}", path, e)))?;
+    init_schema(&conn)?;
+    Ok(conn)
+}
+
+fn init_schema(conn: &Connection) -> Result<(), BeeError> {
+    conn.execute_batch(SCHEMA).map_err(db_error)
+}
+
+/// Turn the condition into a SQL expression on the `tasks` table. The values are
+/// pushed to `values` so that they can be bound to the query.
+fn condition_to_sql(condition: &FilterCondition, values: &mut Vec<Value>) -> String {
+    match condition {
+        FilterCondition::Any => "1".to_string(),
+        FilterCondition::Status(status) => {
+            values.push(Value::Text(status.to_string()));
+            "status = ?".to_string()
+        }
+        FilterCondition::Project(name) => {
+            // Sub projects start with the name of their parent
+            values.push(Value::Integer(name.chars().count() as i64));
+            values.push(Value::Text(name.to_owned()));
+            "substr(project, 1, ?) = ?".to_string()
+        }
+        FilterCondition::Tag { name, include } => {
+            values.push(Value::Text(name.to_owned()));
+            let operator = if *include { "IN" } else { "NOT IN" };
+            format!(
+                "uuid {} (SELECT uuid FROM task_tags WHERE tag = ?)",
+                operator
+            )
+        }
+        FilterCondition::Uuid(uuid) => {
+            values.push(Value::Text(uuid.to_string()));
+            "uuid = ?".to_string()
+        }
+        FilterCondition::TaskId(id) => {
+            values.push(Value::Integer(*id as i64));
+            "id = ?".to_string()
+        }
+        FilterCondition::And(children) => format!(
+            "({})",
+            children
+                .iter()
+                .map(|c| condition_to_sql(c, values))
+                .collect::<Vec<_>>()
+                .join(" AND ")
+        ),
+        FilterCondition::Or(children) => format!(
+            "({})",
+            children
+                .iter()
+                .map(|c| condition_to_sql(c, values))
+                .collect::<Vec<_>>()
+                .join(" OR ")
+        ),
+    }
+}
+
+fn task_from_row(data: &str, id: Option<i64>) -> Result<Task, BeeError> {
+    let mut value: serde_json::Value = serde_json::from_str(data).map_err(|e| {
+        BeeError::Storage(format!("Unable to read a task from the database: {}", e))
+    })?;
+    // The ID stored along with the task may be outdated
+    value["id"] = serde_json::json!(id);
+    serde_json::from_value(value)
+        .map_err(|e| BeeError::Storage(format!("Unable to read a task from the database: {}", e)))
+}
+
+fn select_tasks(
+    conn: &Connection,
+    where_clause: &str,
+    values: Vec<Value>,
+) -> Result<Vec<Task>, BeeError> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT data, id FROM tasks WHERE {}",
+            where_clause
+        ))
+        .map_err(db_error)?;
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?))
+        })
+        .map_err(db_error)?;
+
+    rows.map(|row| {
+        let (data, id) = row.map_err(db_error)?;
+        task_from_row(&data, id)
+    })
+    .collect()
+}
+
+fn select_tasks_by_uuid(conn: &Connection, uuids: &[Uuid]) -> Result<Vec<Task>, BeeError> {
+    let mut tasks = Vec::new();
+    for chunk in uuids.chunks(MAX_QUERY_PARAMS) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        tasks.extend(select_tasks(
+            conn,
+            &format!("uuid IN ({})", placeholders),
+            chunk.iter().map(|u| Value::Text(u.to_string())).collect(),
+        )?);
+    }
+    Ok(tasks)
+}
+
+fn load_id_to_uuid(conn: &Connection) -> Result<HashMap<usize, Uuid>, BeeError> {
+    let mut stmt = conn
+        .prepare("SELECT id, uuid FROM tasks WHERE id IS NOT NULL")
+        .map_err(db_error)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(db_error)?;
+
+    let mut id_to_uuid = HashMap::default();
+    for row in rows {
+        let (id, uuid) = row.map_err(db_error)?;
+        let uuid = Uuid::parse_str(&uuid).map_err(|e| {
+            BeeError::Storage(format!("Invalid UUID '{}' in the database: {}", uuid, e))
+        })?;
+        id_to_uuid.insert(id as usize, uuid);
+    }
+    Ok(id_to_uuid)
+}
+
+/// Load every task that can be reached from `tasks` (or from `extra_uuids`) by
+/// following the links in between tasks. The upkeep of the links needs to know
+/// about all of them.
+fn load_linked_tasks(
+    conn: &Connection,
+    tasks: &mut HashMap<Uuid, Task>,
+    extra_uuids: Vec<Uuid>,
+) -> Result<(), BeeError> {
+    let mut to_load: Vec<Uuid> = tasks
+        .values()
+        .flat_map(|t| t.get_extra_uuid())
+        .chain(extra_uuids)
+        .filter(|uuid| !tasks.contains_key(uuid))
+        .collect();
+
+    while !to_load.is_empty() {
+        to_load.sort_unstable();
+        to_load.dedup();
+        debug!("Loading {} linked tasks", to_load.len());
+
+        let loaded = select_tasks_by_uuid(conn, &to_load)?;
+        to_load = Vec::new();
+        for task in loaded {
+            to_load.extend(task.get_extra_uuid());
+            tasks.insert(*task.get_uuid(), task);
+        }
+        to_load.retain(|uuid| !tasks.contains_key(uuid));
+    }
+    Ok(())
+}
+
+#[allow(clippy::borrowed_box)]
+fn load_tasks_from(
+    conn: &Connection,
+    filter: Option<&Box<dyn Filter>>,
+    props: Option<TaskProperties>,
+) -> Result<TaskData, BeeError> {
+    let id_to_uuid = load_id_to_uuid(conn)?;
+    let filter = match filter {
+        Some(filter) => {
+            let mut filter = filter.clone();
+            filter.convert_id_to_uuid(&id_to_uuid);
+            filter
+        }
+        None => filters::new_empty(),
+    };
+    debug!("Loading tasks using filter:\n{}", &filter.to_string());
+
+    let mut values = Vec::new();
+    let where_clause = condition_to_sql(&filters::to_condition(filter.as_ref()), &mut values);
+    debug!("Loading tasks matching the SQL condition: {}", where_clause);
+
+    let mut tasks: HashMap<Uuid, Task> = select_tasks(conn, &where_clause, values)?
+        .into_iter()
+        .map(|t| (*t.get_uuid(), t))
+        .collect();
+
+    let mut referenced_uuids = Vec::new();
+    if let Some(props) = props {
+        for task_identifier in props.get_referenced_tasks() {
+            match task_identifier {
+                DependsOnIdentifier::Uuid(uuid) => referenced_uuids.push(uuid),
+                DependsOnIdentifier::Usize(id) => {
+                    referenced_uuids.push(*id_to_uuid.get(&id).ok_or(BeeError::UnknownTask(
+                        format!("The given id {} doesn't correspond to any known task.", id),
+                    ))?)
+                }
+            }
+        }
+    }
+    load_linked_tasks(conn, &mut tasks, referenced_uuids.clone())?;
+
+    let mut data = TaskData::default();
+    for task in tasks.into_values() {
+        data.set_task(task);
+    }
+    for (id, uuid) in &id_to_uuid {
+        data.insert_id_to_uuid(*id, *uuid);
+    }
+    data.upkeep_links()?;
+
+    // The SQL condition only narrows down the tasks, the linked tasks that were loaded
+    // may not match the filter either
+    let mut new_data = data.filter(&filter);
+    for uuid in &referenced_uuids {
+        new_data.insert_extra_task(data.get_owned(uuid).ok_or(BeeError::UnknownTask(format!(
+            "The given uuid {} doesn't correspond to any known task.",
+            uuid
+        )))?);
+    }
+    for uuid in data.get_linked_tasks(&referenced_uuids) {
+        if let Some(task) = data.get_owned(&uuid) {
+            new_data.insert_extra_task(task);
+        }
+    }
+    debug!("Loaded {} tasks.", new_data.get_task_map().len());
+
+    Ok(new_data)
+}
+
+fn write_tasks_to(conn: &mut Connection, data: &TaskData) -> Result<TaskData, BeeError> {
+    // Both the stored and the updated version of the tasks are needed to find
+    // every task whose links may have to be updated
+    let updated_uuids: Vec<Uuid> = data.get_task_map().keys().cloned().collect();
+    let mut tasks: HashMap<Uuid, Task> = select_tasks_by_uuid(conn, &updated_uuids)?
+        .into_iter()
+        .map(|t| (*t.get_uuid(), t))
+        .collect();
+    let updated_links = data
+        .get_task_map()
+        .values()
+        .flat_map(|t| t.get_extra_uuid())
+        .collect();
+    load_linked_tasks(conn, &mut tasks, updated_links)?;
+
+    let mut stored_tasks = TaskData::default();
+    for task in tasks.into_values() {
+        stored_tasks.set_task(task);
+    }
+    for task in data.get_task_map().values() {
+        stored_tasks.set_task(task.clone());
+    }
+    stored_tasks.upkeep_links()?;
+
+    let tx = conn.transaction().map_err(db_error)?;
+    for task in stored_tasks.get_task_map().values() {
+        let uuid = task.get_uuid().to_string();
+        let task_as_json = to_json(task, "task")?;
+        tx.execute(
+            "INSERT INTO tasks (uuid, id, status, project, date_created, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(uuid) DO UPDATE SET
+                id = excluded.id,
+                status = excluded.status,
+                project = excluded.project,
+                date_created = excluded.date_created,
+                data = excluded.data",
+            params![
+                uuid,
+                task.get_id().map(|id| id as i64),
+                task.get_status().to_string(),
+                task.get_project().as_ref().map(|p| p.get_name().to_owned()),
+                task.get_date_created().timestamp_micros(),
+                task_as_json,
+            ],
+        )
+        .map_err(db_error)?;
+
+        tx.execute("DELETE FROM task_tags WHERE uuid = ?1", [&uuid])
+            .map_err(db_error)?;
+        for tag in task.get_tags() {
+            tx.execute(
+                "INSERT OR IGNORE INTO task_tags (uuid, tag) VALUES (?1, ?2)",
+                [&uuid, tag],
+            )
+            .map_err(db_error)?;
+        }
+    }
+    update_ids(&tx)?;
+    tx.commit().map_err(db_error)?;
+
+    // Read the tasks back to get their new IDs
+    let uuids: Vec<Uuid> = stored_tasks.get_task_map().keys().cloned().collect();
+    let mut written_tasks = TaskData::default();
+    for task in select_tasks_by_uuid(conn, &uuids)? {
+        if let Some(id) = task.get_id() {
+            written_tasks.insert_id_to_uuid(id, *task.get_uuid());
+        }
+        written_tasks.set_task(task);
+    }
+    Ok(written_tasks)
+}
+
+/// Give the pending and active tasks their ID, ordered by creation date, the same
+/// way `TaskData::upkeep` does it. Only the rows whose ID changed are updated.
+fn update_ids(conn: &Connection) -> Result<(), BeeError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT uuid, id, status FROM tasks
+             WHERE id IS NOT NULL OR status IN (?1, ?2)
+             ORDER BY date_created",
+        )
+        .map_err(db_error)?;
+    let rows = stmt
+        .query_map(
+            [
+                TaskStatus::Pending.to_string(),
+                TaskStatus::Active.to_string(),
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<i64>>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )
+        .map_err(db_error)?;
+
+    let mut next_id = 1;
+    let mut updates = Vec::new();
+    for row in rows {
+        let (uuid, id, status) = row.map_err(db_error)?;
+        let new_id = match TaskStatus::from_string(&status)? {
+            TaskStatus::Pending | TaskStatus::Active => {
+                next_id += 1;
+                Some(next_id - 1)
+            }
+            TaskStatus::Completed | TaskStatus::Deleted => None,
+        };
+        if new_id != id {
+            updates.push((uuid, new_id));
+        }
+    }
+
+    debug!("Updating the ID of {} tasks", updates.len());
+    for (uuid, id) in updates {
+        conn.execute(
+            "UPDATE tasks SET id = ?1 WHERE uuid = ?2",
+            params![id, uuid],
+        )
+        .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+fn load_undos_from(conn: &Connection, last_count: usize) -> Result<Vec<ActionUndo>, BeeError> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM undos ORDER BY seq DESC LIMIT ?1")
+        .map_err(db_error)?;
+    let rows = stmt
+        .query_map([i64::try_from(last_count).unwrap_or(i64::MAX)], |row| {
+            row.get::<_, String>(0)
+        })
+        .map_err(db_error)?;
+
+    let mut undos = rows
+        .map(|row| {
+            serde_json::from_str(&row.map_err(db_error)?).map_err(|e| {
+                BeeError::Storage(format!("Unable to read an undo from the database: {}", e))
+            })
+        })
+        .collect::<Result<Vec<ActionUndo>, BeeError>>()?;
+    undos.reverse();
+    Ok(undos)
+}
+
+fn load_redos_from(conn: &Connection) -> Result<Vec<ActionUndo>, BeeError> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM redos ORDER BY seq")
+        .map_err(db_error)?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(db_error)?;
+
+    rows.map(|row| {
+        serde_json::from_str(&row.map_err(db_error)?).map_err(|e| {
+            BeeError::Storage(format!("Unable to read a redo from the database: {}", e))
+        })
+    })
+    .collect()
+}
+
+fn log_redos_to(conn: &mut Connection, redos: Vec<ActionUndo>) -> Result<(), BeeError> {
+    let tx = conn.transaction().map_err(db_error)?;
+    tx.execute("DELETE FROM redos", []).map_err(db_error)?;
+    for redo in redos {
+        tx.execute(
+            "INSERT INTO redos (data) VALUES (?1)",
+            [to_json(&redo, "redo")?],
+        )
+        .map_err(db_error)?;
+    }
+    tx.commit().map_err(db_error)
+}
+
+fn load_contexts_from(conn: &Connection) -> Result<Contexts, BeeError> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM contexts WHERE id = 0")
+        .map_err(db_error)?;
+    let mut rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(db_error)?;
+
+    match rows.next() {
+        Some(row) => serde_json::from_str(&row.map_err(db_error)?).map_err(|e| {
+            BeeError::Storage(format!(
+                "Unable to read the contexts from the database: {}",
+                e
+            ))
+        }),
+        None => Ok(Contexts::default()),
+    }
+}
+
+fn write_contexts_to(conn: &Connection, contexts: &Contexts) -> Result<(), BeeError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO contexts (id, data) VALUES (0, ?1)",
+        [to_json(contexts, "contexts")?],
+    )
+    .map_err(db_error)?;
+    Ok(())
+}
+
+/// Replace the last `count` undos with `updated_undos`
+fn log_undo_to(
+    conn: &mut Connection,
+    count: usize,
+    updated_undos: Vec<ActionUndo>,
+) -> Result<(), BeeError> {
+    let tx = conn.transaction().map_err(db_error)?;
+    tx.execute(
+        "DELETE FROM undos WHERE seq IN (SELECT seq FROM undos ORDER BY seq DESC LIMIT ?1)",
+        [i64::try_from(count).unwrap_or(i64::MAX)],
+    )
+    .map_err(db_error)?;
+    for undo in updated_undos {
+        tx.execute(
+            "INSERT INTO undos (data) VALUES (?1)",
+            [to_json(&undo, "undo")?],
+        )
+        .map_err(db_error)?;
+    }
+    tx.commit().map_err(db_error)
+}
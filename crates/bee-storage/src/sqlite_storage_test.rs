@@ -0,0 +1,260 @@
+use super::*;
+
+use all_asserts::assert_true;
+use bee_actions::ActionUndoType;
+use bee_core::context::Context;
+
+fn init_connection() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    init_schema(&conn).unwrap();
+    conn
+}
+
+fn add_task(data: &mut TaskData, arguments: &str) -> Uuid {
+    let task = data
+        .add_task(
+            &TaskProperties::from(&[arguments.to_owned()]).unwrap(),
+            TaskStatus::Pending,
+        )
+        .unwrap();
+    let (id, uuid) = (task.get_id().unwrap(), *task.get_uuid());
+    data.insert_id_to_uuid(id, uuid);
+    uuid
+}
+
+fn load(conn: &Connection, filter: &str) -> TaskData {
+    let filter = filters::from(&[filter.to_owned()]).unwrap();
+    load_tasks_from(conn, Some(&filter), None).unwrap()
+}
+
+#[test]
+fn test_condition_to_sql() {
+    let mut values = Vec::new();
+    let condition = FilterCondition::And(vec![
+        FilterCondition::Or(vec![
+            FilterCondition::Status(TaskStatus::Pending),
+            FilterCondition::TaskId(3),
+        ]),
+        FilterCondition::Tag {
+            name: "home".to_owned(),
+            include: false,
+        },
+        FilterCondition::Project("work".to_owned()),
+    ]);
+    assert_eq!(
+        condition_to_sql(&condition, &mut values),
+        "((status = ? OR id = ?) AND uuid NOT IN (SELECT uuid FROM task_tags WHERE tag = ?) \
+         AND substr(project, 1, ?) = ?)"
+    );
+    assert_eq!(
+        values,
+        vec![
+            Value::Text("pending".to_owned()),
+            Value::Integer(3),
+            Value::Text("home".to_owned()),
+            Value::Integer(4),
+            Value::Text("work".to_owned()),
+        ]
+    );
+
+    let mut values = Vec::new();
+    assert_eq!(condition_to_sql(&FilterCondition::Any, &mut values), "1");
+    assert_true!(values.is_empty());
+}
+
+#[test]
+fn test_write_and_load_tasks() {
+    let mut conn = init_connection();
+    let mut data = TaskData::default();
+    let uuid_home = add_task(&mut data, "clean the house +home proj:chores");
+    let uuid_work = add_task(&mut data, "write the report +work proj:work.report");
+    add_task(&mut data, "call the bank proj:chores.admin");
+    write_tasks_to(&mut conn, &data).unwrap();
+
+    let loaded = load(&conn, "+home");
+    assert_eq!(loaded.get_task_map().len(), 1);
+    assert_eq!(
+        loaded.get_task_map().get(&uuid_home).unwrap().get_summary(),
+        "clean the house"
+    );
+
+    assert_eq!(load(&conn, "proj:chores").get_task_map().len(), 2);
+    assert_eq!(load(&conn, "-home").get_task_map().len(), 2);
+
+    let loaded = load(&conn, "2");
+    assert_eq!(loaded.get_task_map().len(), 1);
+    assert_true!(loaded.get_task_map().contains_key(&uuid_work));
+    // All the IDs are known, even for the tasks that were not loaded
+    assert_eq!(loaded.get_id_to_uuid().len(), 3);
+
+    // Filters that are not pushed down to SQLite still apply
+    let loaded = load(&conn, "proj:chores and bank");
+    assert_eq!(loaded.get_task_map().len(), 1);
+    assert_eq!(
+        loaded.get_task_map().values().next().unwrap().get_summary(),
+        "call the bank"
+    );
+}
+
+#[test]
+fn test_write_tasks_updates_ids() {
+    let mut conn = init_connection();
+    let mut data = TaskData::default();
+    let first = add_task(&mut data, "first");
+    let second = add_task(&mut data, "second");
+    write_tasks_to(&mut conn, &data).unwrap();
+
+    let mut loaded = load(&conn, "1");
+    loaded.task_done(&first).unwrap();
+    let written = write_tasks_to(&mut conn, &loaded).unwrap();
+    assert_eq!(written.get_task_map().get(&first).unwrap().get_id(), None);
+
+    let loaded = load(&conn, "status:pending");
+    assert_eq!(loaded.get_task_map().len(), 1);
+    assert_eq!(
+        loaded.get_task_map().get(&second).unwrap().get_id(),
+        Some(1)
+    );
+    assert_eq!(load(&conn, "status:completed").get_task_map().len(), 1);
+}
+
+#[test]
+fn test_load_linked_tasks() {
+    let mut conn = init_connection();
+    let mut data = TaskData::default();
+    let blocking = add_task(&mut data, "blocking");
+    let blocked = add_task(&mut data, "blocked depends:1");
+    write_tasks_to(&mut conn, &data).unwrap();
+
+    let loaded = load(&conn, "2");
+    assert_eq!(loaded.get_task_map().len(), 1);
+    assert_true!(loaded.get_extra_tasks().contains_key(&blocking));
+    assert_true!(
+        loaded
+            .get_extra_tasks()
+            .get(&blocking)
+            .unwrap()
+            .blocks(&blocked)
+    );
+
+    // Completing the blocking task removes the dependency on it
+    let mut loaded = load(&conn, "1");
+    loaded.task_done(&blocking).unwrap();
+    write_tasks_to(&mut conn, &loaded).unwrap();
+
+    let loaded = load(&conn, "status:pending");
+    assert_true!(
+        loaded
+            .get_task_map()
+            .get(&blocked)
+            .unwrap()
+            .get_depends_on()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_undos() {
+    let mut conn = init_connection();
+    let undo = |summary: &str| {
+        let mut data = TaskData::default();
+        let uuid = add_task(&mut data, summary);
+        ActionUndo::new(ActionUndoType::Add, vec![data.get_owned(&uuid).unwrap()])
+    };
+
+    log_undo_to(&mut conn, 0, vec![undo("first"), undo("second")]).unwrap();
+    assert_eq!(load_undos_from(&conn, 10).unwrap().len(), 2);
+
+    let undos = load_undos_from(&conn, 1).unwrap();
+    assert_eq!(undos.len(), 1);
+    assert_eq!(undos[0].tasks[0].get_summary(), "second");
+
+    // Replace the last undo
+    log_undo_to(&mut conn, 1, vec![undo("third"), undo("fourth")]).unwrap();
+    let summaries: Vec<_> = load_undos_from(&conn, 10)
+        .unwrap()
+        .iter()
+        .map(|u| u.tasks[0].get_summary().to_owned())
+        .collect();
+    assert_eq!(summaries, vec!["first", "third", "fourth"]);
+}
+
+#[test]
+fn test_redos() {
+    let mut conn = init_connection();
+    let redo = |command: &str| {
+        let mut redo = ActionUndo::new(ActionUndoType::Modify, Vec::new());
+        redo.command = command.to_owned();
+        redo
+    };
+
+    log_redos_to(&mut conn, vec![redo("first"), redo("second")]).unwrap();
+    let commands: Vec<_> = load_redos_from(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.command)
+        .collect();
+    assert_eq!(commands, vec!["first", "second"]);
+
+    log_redos_to(&mut conn, Vec::new()).unwrap();
+    assert_true!(load_redos_from(&conn).unwrap().is_empty());
+}
+
+#[test]
+fn test_contexts() {
+    let conn = init_connection();
+    assert_eq!(load_contexts_from(&conn).unwrap(), Contexts::default());
+
+    let mut contexts = Contexts::default();
+    contexts.definitions.insert(
+        "work".to_string(),
+        Context::from_filter(&["+work".to_string()]).unwrap(),
+    );
+    contexts.active = Some("work".to_string());
+    write_contexts_to(&conn, &contexts).unwrap();
+    assert_eq!(load_contexts_from(&conn).unwrap(), contexts);
+
+    contexts.active = None;
+    write_contexts_to(&conn, &contexts).unwrap();
+    assert_eq!(load_contexts_from(&conn).unwrap(), contexts);
+}
+
+#[test]
+fn test_import_into() {
+    let mut conn = init_connection();
+    let mut data = TaskData::default();
+    add_task(&mut data, "first");
+    add_task(&mut data, "second depends:1");
+    add_task(&mut data, "third");
+
+    assert_eq!(import_into(&mut conn, &data, Vec::new()).unwrap(), 3);
+    assert_eq!(load(&conn, "status:pending").get_task_map().len(), 3);
+}
+
+#[test]
+fn test_load_transitive_links() {
+    let mut conn = init_connection();
+    let mut data = TaskData::default();
+    let first = add_task(&mut data, "first");
+    let second = add_task(&mut data, "second depends:1");
+    let third = add_task(&mut data, "third depends:2");
+    add_task(&mut data, "unrelated");
+    write_tasks_to(&mut conn, &data).unwrap();
+
+    // The whole chain is loaded, not only the direct neighbours
+    let loaded = load(&conn, "3");
+    assert_eq!(loaded.get_task_map().len(), 1);
+    assert_eq!(loaded.get_extra_tasks().len(), 2);
+    assert_true!(loaded.get_extra_tasks().contains_key(&first));
+
+    let loaded = load(&conn, "depends.any:1");
+    let mut uuids: Vec<_> = loaded.get_task_map().keys().cloned().collect();
+    uuids.sort_unstable();
+    let mut expected = vec![second, third];
+    expected.sort_unstable();
+    assert_eq!(uuids, expected);
+
+    let loaded = load(&conn, "blocks.any:3 and status:pending");
+    assert_eq!(loaded.get_task_map().len(), 2);
+    assert_true!(loaded.get_task_map().contains_key(&first));
+}
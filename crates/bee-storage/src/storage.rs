@@ -1,8 +1,11 @@
 use log::debug;
+use serde::{Serialize, de::DeserializeOwned};
 use uuid::Uuid;
 
 use bee_actions::ActionUndo;
 use bee_core::{
+    BeeError,
+    context::Contexts,
     filters::{self, Filter},
     task::{DependsOnIdentifier, TaskData, TaskProperties},
 };
@@ -11,7 +14,6 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io;
-use std::io::Read;
 use std::path::{Path, PathBuf};
 
 #[path = "storage_test.rs"]
@@ -22,11 +24,18 @@ pub trait Store {
     fn load_tasks(
         filter: Option<&Box<dyn Filter>>,
         props: Option<TaskProperties>,
-    ) -> Result<TaskData, String>;
+    ) -> Result<TaskData, BeeError>;
     /// Will write the task and return the TaskData written
-    fn write_tasks(data: &TaskData) -> Result<TaskData, String>;
-    fn load_undos(last_count: usize) -> Vec<ActionUndo>;
-    fn log_undo(count: usize, updated_undos: Vec<ActionUndo>);
+    fn write_tasks(data: &TaskData) -> Result<TaskData, BeeError>;
+    fn load_undos(last_count: usize) -> Result<Vec<ActionUndo>, BeeError>;
+    fn log_undo(count: usize, updated_undos: Vec<ActionUndo>) -> Result<(), BeeError>;
+    /// The actions that were undone and can be redone, the next one to redo last
+    fn load_redos() -> Result<Vec<ActionUndo>, BeeError>;
+    /// Replace all the actions that can be redone
+    fn log_redos(redos: Vec<ActionUndo>) -> Result<(), BeeError>;
+    /// The contexts defined by the user, and the active one
+    fn load_contexts() -> Result<Contexts, BeeError>;
+    fn write_contexts(contexts: &Contexts) -> Result<(), BeeError>;
 }
 
 #[derive(Default)]
@@ -37,16 +46,13 @@ impl Store for JsonStore {
     fn load_tasks(
         filter: Option<&Box<dyn Filter>>,
         props: Option<TaskProperties>,
-    ) -> Result<TaskData, String> {
+    ) -> Result<TaskData, BeeError> {
         debug!(
             "Loading tasks using filter:\n{}",
             &filter.unwrap_or(&filters::new_empty()).to_string()
         );
         let mut data = match find_data_file() {
-            Ok(data_file) => {
-                serde_json::from_str(&fs::read_to_string(data_file).expect("unable to read file"))
-                    .unwrap()
-            }
+            Ok(data_file) => from_json(&read_file(&data_file)?, &data_file, "tasks")?,
             Err(_) => TaskData::default(),
         };
 
@@ -79,136 +85,171 @@ impl Store for JsonStore {
             data.get_task_map().len()
         );
 
-        // TODO: I need to get all the tasks that can be possibly reached from the
-        // filtered tasks. Currently I am only reaching the classes that are first
-        // degree neighbour of my filtered tasks.
-        // Allowing all tasks will allow to update their field accordingly when we're
-        // going dependency update and such.
-        let extra_uuids: Vec<_> = new_data
-            .get_task_map()
-            .values()
-            .flat_map(|task| task.get_extra_uuid())
-            .collect();
-
-        // Load extra uuids from the TaskProperties
+        // Load the tasks referenced by the TaskProperties, along with every task that can
+        // be reached from them. The tasks reachable from the filtered tasks are already
+        // part of the extra tasks.
+        let mut referenced_uuids = Vec::new();
         if let Some(props) = props {
             for task_identifier in props.get_referenced_tasks() {
                 match task_identifier {
-                    DependsOnIdentifier::Uuid(uuid) => {
-                        debug!("Adding extra task with uuid {} from TaskProperties", uuid);
-                        new_data.insert_extra_task(data.get_owned(&uuid).unwrap())
-                    }
+                    DependsOnIdentifier::Uuid(uuid) => referenced_uuids.push(uuid),
                     DependsOnIdentifier::Usize(id) => {
-                        if let Some(uuid) = id_to_uuid.get(&id) {
-                            debug!(
-                                "Adding extra task with id {} and uuid {} from TaskProperties",
-                                id, uuid
-                            );
-                            new_data.insert_extra_task(data.get_owned(uuid).unwrap())
-                        } else {
-                            unreachable!("Could not find task with id {}", id);
-                        }
+                        referenced_uuids.push(*id_to_uuid.get(&id).ok_or(BeeError::UnknownTask(
+                            format!("The given id {} doesn't correspond to any known task.", id),
+                        ))?)
                     }
                 }
             }
         }
-
-        for uuid in extra_uuids {
-            let task = data.get_owned(&uuid).unwrap();
-            debug!(
-                "Adding extra task with id {:?} and uuid {} as extra task",
-                task.get_id(),
-                uuid
-            );
-            new_data.insert_extra_task(data.get_owned(&uuid).unwrap())
+        for uuid in &referenced_uuids {
+            new_data.insert_extra_task(data.get_owned(uuid).ok_or(BeeError::UnknownTask(
+                format!(
+                    "The given uuid {} doesn't correspond to any known task.",
+                    uuid
+                ),
+            ))?);
+        }
+        for uuid in data.get_linked_tasks(&referenced_uuids) {
+            if let Some(task) = data.get_owned(&uuid) {
+                debug!(
+                    "Adding extra task with id {:?} and uuid {} as extra task",
+                    task.get_id(),
+                    uuid
+                );
+                new_data.insert_extra_task(task);
+            }
         }
 
         Ok(new_data)
     }
 
-    fn write_tasks(data: &TaskData) -> Result<TaskData, String> {
+    fn write_tasks(data: &TaskData) -> Result<TaskData, BeeError> {
         let mut stored_tasks = Self::load_tasks(None, None)?;
         for t in data.get_task_map().values() {
             stored_tasks.set_task(t.clone());
         }
         stored_tasks.upkeep()?;
 
-        let tasks_as_json =
-            serde_json::to_string_pretty(&stored_tasks).expect("Failed to serialize tasks to JSON");
+        let data_file = match find_data_file() {
+            Ok(file) => file,
+            Err(_) => create_path_if_not_exist(&get_data_file_path())?,
+        };
+        write_file(&data_file, &to_json(&stored_tasks, "tasks")?)?;
+
+        Ok(stored_tasks)
+    }
+
+    fn load_undos(last_count: usize) -> Result<Vec<ActionUndo>, BeeError> {
+        let undos = load_undo_file(find_logged_file())?;
+        let len = undos.len();
+        if last_count >= len {
+            Ok(undos)
+        } else {
+            Ok(undos[len - last_count..].to_vec())
+        }
+    }
 
-        match find_data_file() {
-            Ok(_) => (),
-            Err(_) => create_path_if_not_exist(&get_data_file_path()),
+    fn log_undo(count: usize, updated_undos: Vec<ActionUndo>) -> Result<(), BeeError> {
+        let data_file = match find_logged_file() {
+            Ok(file) => file,
+            Err(_) => create_path_if_not_exist(&get_logged_tasks_file_path())?,
+        };
+
+        let mut undos = load_undo_file(Ok(data_file.to_owned()))?;
+        if undos.len() <= count {
+            undos = updated_undos;
+        } else {
+            undos.splice(undos.len() - count.., updated_undos);
         }
 
-        let data_file = find_data_file().expect("Failed to find data file");
+        write_file(&data_file, &to_json(&undos, "undo log")?)
+    }
+
+    fn load_redos() -> Result<Vec<ActionUndo>, BeeError> {
+        load_undo_file(find_redo_file())
+    }
 
-        fs::write(data_file, tasks_as_json).expect("Could not write data file");
+    fn log_redos(redos: Vec<ActionUndo>) -> Result<(), BeeError> {
+        let data_file = match find_redo_file() {
+            Ok(file) => file,
+            Err(_) if redos.is_empty() => return Ok(()),
+            Err(_) => create_path_if_not_exist(&get_redo_file_path())?,
+        };
 
-        Ok(stored_tasks)
+        write_file(&data_file, &to_json(&redos, "redo log")?)
     }
 
-    fn load_undos(last_count: usize) -> Vec<ActionUndo> {
-        match find_logged_file() {
+    fn load_contexts() -> Result<Contexts, BeeError> {
+        match find_contexts_file() {
             Ok(data_file) => {
-                let undos: Vec<ActionUndo> = serde_json::from_str(
-                    &fs::read_to_string(data_file).expect("unable to read file"),
-                )
-                .unwrap();
-                let len = undos.len();
-                if last_count >= len {
-                    undos[..].to_vec()
-                } else {
-                    undos[len - last_count..].to_vec()
+                let data = read_file(&data_file)?;
+                if data.is_empty() {
+                    return Ok(Contexts::default());
                 }
+                from_json(&data, &data_file, "contexts")
             }
-            Err(_) => Vec::default(),
+            Err(_) => Ok(Contexts::default()),
         }
     }
 
-    fn log_undo(count: usize, updated_undos: Vec<ActionUndo>) {
-        // Assuming find_logged_file and create_path_if_not_exist functions exist
-        let data_file = match find_logged_file() {
+    fn write_contexts(contexts: &Contexts) -> Result<(), BeeError> {
+        let data_file = match find_contexts_file() {
             Ok(file) => file,
-            Err(_) => {
-                create_path_if_not_exist(&get_logged_tasks_file_path());
-                find_logged_file().expect("Failed to find or create logged file")
-            }
+            Err(_) if contexts == &Contexts::default() => return Ok(()),
+            Err(_) => create_path_if_not_exist(&get_contexts_file_path())?,
         };
 
-        let mut undos: Vec<ActionUndo> = Vec::new();
+        write_file(&data_file, &to_json(contexts, "contexts")?)
+    }
+}
 
-        if let Ok(mut file) = fs::File::open(&data_file) {
-            let mut data = String::new();
-            file.read_to_string(&mut data)
-                .expect("Failed to read data file");
-            if !data.is_empty() {
-                undos = serde_json::from_str(&data).expect("Failed to parse JSON");
-            }
-        }
+/// Read the undos or the redos of a file, an empty or missing file has none
+fn load_undo_file(data_file: Result<String, io::Error>) -> Result<Vec<ActionUndo>, BeeError> {
+    let Ok(data_file) = data_file else {
+        return Ok(Vec::default());
+    };
+    let data = read_file(&data_file)?;
+    if data.is_empty() {
+        return Ok(Vec::default());
+    }
+    from_json(&data, &data_file, "actions")
+}
 
-        if undos.len() <= count {
-            undos = updated_undos;
-        } else {
-            undos.splice(undos.len() - count.., updated_undos);
-        }
+fn read_file(path: &str) -> Result<String, BeeError> {
+    fs::read_to_string(path)
+        .map_err(|e| BeeError::Storage(format!("Unable to read '{}': {}", path, e)))
+}
 
-        let updated_data = serde_json::to_string_pretty(&undos).expect("Failed to serialize data");
-        fs::write(&data_file, updated_data).expect("Failed to write to data file");
-    }
+fn write_file(path: &str, data: &str) -> Result<(), BeeError> {
+    fs::write(path, data)
+        .map_err(|e| BeeError::Storage(format!("Unable to write '{}': {}", path, e)))
+}
+
+/// Parse the `what` (e.g. "tasks") stored in `path`
+fn from_json<T: DeserializeOwned>(data: &str, path: &str, what: &str) -> Result<T, BeeError> {
+    serde_json::from_str(data)
+        .map_err(|e| BeeError::Storage(format!("Unable to read the {} in '{}': {}", what, path, e)))
 }
 
-// Function to create a path if it doesn't exist
-fn create_path_if_not_exist(path: &str) {
-    fs::create_dir_all(
-        Path::new(path)
-            .parent()
-            .expect("Failed to get directory part of path"),
-    )
-    .expect("Failed to create directories");
-
-    fs::File::create(path).expect("Failed to create or truncate the file");
-    // File is automatically closed when it goes out of scope
+fn to_json<T: Serialize + ?Sized>(value: &T, what: &str) -> Result<String, BeeError> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| BeeError::Storage(format!("Unable to serialize the {}: {}", what, e)))
+}
+
+/// Create an empty file at `path`, along with its directories, and return the path
+fn create_path_if_not_exist(path: &str) -> Result<String, BeeError> {
+    if let Some(directory) = Path::new(path).parent() {
+        fs::create_dir_all(directory).map_err(|e| {
+            BeeError::Storage(format!(
+                "Unable to create the directory '{}': {}",
+                directory.display(),
+                e
+            ))
+        })?;
+    }
+    fs::File::create(path)
+        .map_err(|e| BeeError::Storage(format!("Unable to create '{}': {}", path, e)))?;
+    Ok(path.to_owned())
 }
 
 // FileSystem trait for abstracting file system operations
@@ -238,7 +279,7 @@ impl Env for RealEnv {
 }
 
 // Function to find data file
-fn find_data_file() -> Result<String, io::Error> {
+pub(crate) fn find_data_file() -> Result<String, io::Error> {
     get_data_file_impl(&RealFileSystem, &RealEnv, "bee-data.json", true)
 }
 
@@ -255,6 +296,32 @@ fn find_logged_file() -> Result<String, io::Error> {
     get_data_file_impl(&RealFileSystem, &RealEnv, "bee-logged-tasks.json", true)
 }
 
+const REDO_FILE_NAME: &str = "bee-redo-tasks.json";
+
+fn get_redo_file_path() -> String {
+    get_data_file_impl(&RealFileSystem, &RealEnv, REDO_FILE_NAME, false).unwrap_or_default()
+}
+
+fn find_redo_file() -> Result<String, io::Error> {
+    get_data_file_impl(&RealFileSystem, &RealEnv, REDO_FILE_NAME, true)
+}
+
+const CONTEXTS_FILE_NAME: &str = "bee-contexts.json";
+
+fn get_contexts_file_path() -> String {
+    get_data_file_impl(&RealFileSystem, &RealEnv, CONTEXTS_FILE_NAME, false).unwrap_or_default()
+}
+
+fn find_contexts_file() -> Result<String, io::Error> {
+    get_data_file_impl(&RealFileSystem, &RealEnv, CONTEXTS_FILE_NAME, true)
+}
+
+pub(crate) const SQLITE_DATA_FILE_NAME: &str = "bee-data.db";
+
+pub(crate) fn get_sqlite_file_path() -> String {
+    get_data_file_impl(&RealFileSystem, &RealEnv, SQLITE_DATA_FILE_NAME, false).unwrap_or_default()
+}
+
 // getDataFileImpl provides utility to find where we store the file on the filesystem
 fn get_data_file_impl<'a>(
     fs: &(impl FileSystem + 'a),
@@ -262,7 +329,12 @@ fn get_data_file_impl<'a>(
     filename: &str,
     find_file_only: bool,
 ) -> Result<String, io::Error> {
-    if filename != "bee-data.json" && filename != "bee-logged-tasks.json" {
+    if filename != "bee-data.json"
+        && filename != "bee-logged-tasks.json"
+        && filename != REDO_FILE_NAME
+        && filename != CONTEXTS_FILE_NAME
+        && filename != SQLITE_DATA_FILE_NAME
+    {
         panic!("Invalid filename given to 'get_data_file_impl'");
     }
 
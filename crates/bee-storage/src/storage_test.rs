@@ -188,3 +188,31 @@ fn test_find_data_file_with_custom_data_home() {
     let path = get_data_file_impl(&mock_fs, &mock_env, "bee-data.json", true);
     assert_eq!(path.unwrap(), "/custom/bee/bee-data.json");
 }
+
+#[test]
+fn test_load_corrupt_files() {
+    let path = env::temp_dir().join(format!("bee-corrupt-{}.json", Uuid::new_v4()));
+    let path = path.to_string_lossy().into_owned();
+
+    fs::write(&path, "{ not json").unwrap();
+    assert!(matches!(
+        load_undo_file(Ok(path.to_owned())),
+        Err(BeeError::Storage(_))
+    ));
+    assert!(matches!(
+        from_json::<TaskData>(&read_file(&path).unwrap(), &path, "tasks"),
+        Err(BeeError::Storage(_))
+    ));
+
+    // An empty or missing file has no undos
+    fs::write(&path, "").unwrap();
+    assert_eq!(load_undo_file(Ok(path.to_owned())).unwrap().len(), 0);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(read_file(&path), Err(BeeError::Storage(_))));
+    assert_eq!(
+        load_undo_file(Err(io::Error::new(io::ErrorKind::NotFound, "")))
+            .unwrap()
+            .len(),
+        0
+    );
+}